@@ -1,11 +1,15 @@
 fn main() {
-    cxx_build::bridge("src/lib.rs")
+    cxx_build::bridges(["src/lib.rs", "src/distance.rs", "src/filtered.rs"])
         .file("src/geogram_ffi.cpp")
         .file("include/geogram_predicates_psm/Predicates_psm.cpp") // we need to add the ..._psm.cpp to the compile list, just as when compiling in c++
         .std("c++20")
         .compile("cxx-lab");
 
     println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=src/distance.rs");
+    println!("cargo:rerun-if-changed=src/filtered.rs");
     println!("cargo:rerun-if-changed=src/geogram_ffi.cpp");
     println!("cargo:rerun-if-changed=include/geogram_ffi.h");
+    println!("cargo:rerun-if-changed=include/geogram_predicates_psm/Predicates_psm.cpp");
+    println!("cargo:rerun-if-changed=include/geogram_predicates_psm/Predicates_psm.h");
 }
@@ -12,7 +12,7 @@ fn main() {
 
     let mode = args[1].as_str();
 
-    let p0 = [nextafter(12., std::f64::INFINITY), 12.];
+    let p0 = [nextafter(12., f64::INFINITY), 12.];
     // let p0 = [12., 12.];
     let p1 = [-12., -12.];
     let p2 = [24., 24.];
@@ -45,8 +45,7 @@ fn naive_incircle_2d(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], p: &[f64; 2]) ->
     let m32 = c[1] - p[1];
     let m33 = m31.powi(2) + m32.powi(2);
 
-    (m11 * (m22 * m33 - m23 * m32) - m12 * (m21 * m33 - m23 * m31) + m13 * (m21 * m32 - m22 * m31))
-        * -1.0
+    -(m11 * (m22 * m33 - m23 * m32) - m12 * (m21 * m33 - m23 * m31) + m13 * (m21 * m32 - m22 * m31))
 }
 
 fn usage(name: &str) -> ! {
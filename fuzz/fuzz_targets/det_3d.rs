@@ -0,0 +1,6 @@
+#![no_main]
+
+use geogram_predicates_fuzz::Points;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|points: Points<3, 3>| geogram_predicates_fuzz::check_det_3d(points));
@@ -0,0 +1,5 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|x: f64| geogram_predicates_fuzz::check_geo_sgn(x));
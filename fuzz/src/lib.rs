@@ -0,0 +1,418 @@
+//! Input generation and checks shared by the fuzz targets.
+//!
+//! Every target calls one of the `check_*` functions below, which
+//! - calls the bridged predicate, if it has no precondition, on any input, to find crashes and hangs,
+//! - and, where the exact arithmetic of geogram can neither overflow nor underflow (see [`exact`]), compares the result with [`geogram_predicates::reference`], and with the predicate evaluated on swapped arguments.
+//!
+//! The predicates with Simulation of Simplicity assert that their simplex is not flat, and that assertion fails on non-finite coordinates. They are only called on inputs inside the exact domain.
+//!
+//! Note that `cargo fuzz` only instruments the Rust code. For coverage guidance inside geogram, build with `CXXFLAGS="-fsanitize=fuzzer-no-link"`.
+
+#![allow(non_snake_case)]
+
+use arbitrary::Arbitrary;
+use geogram_predicates as gp;
+use geogram_predicates::reference;
+
+/// `K` points with `N` coordinates.
+#[derive(Arbitrary, Debug)]
+pub enum Points<const N: usize, const K: usize> {
+    /// Arbitrary bit patterns, including subnormals, huge magnitudes, NaN and infinities.
+    Raw([[f64; N]; K]),
+    /// Small integers, scaled by a power of two per axis. These are often degenerate, and reach the range checks of the filters, e.g. `min_max < 1e-58` and `max_max < 1e61` in geogram's `in_sphere_3d_filter_optim`.
+    Grid {
+        coords: [[i8; N]; K],
+        exponents: [i8; N],
+    },
+}
+
+impl<const N: usize, const K: usize> Points<N, K> {
+    pub fn get(&self) -> [[f64; N]; K] {
+        match self {
+            Points::Raw(points) => *points,
+            Points::Grid { coords, exponents } => coords.map(|p| {
+                std::array::from_fn(|k| f64::from(p[k]) * 2f64.powi(2 * i32::from(exponents[k])))
+            }),
+        }
+    }
+}
+
+/// The binary magnitudes of the coordinates along one axis: every difference of two coordinates is smaller than `2^hi` in absolute value, and a multiple of `2^lo`.
+#[derive(Clone, Copy, Debug)]
+struct Bits {
+    hi: i32,
+    lo: i32,
+}
+
+/// An axis on which all coordinates are zero, whose terms vanish.
+const ZERO_AXIS: Bits = Bits {
+    hi: -4096,
+    lo: 4096,
+};
+
+/// Head room for the sums of products in the exact predicates.
+const SLACK: i32 = 8;
+
+/// Head room for the Veltkamp split in `two_product`, which multiplies each factor, including partial products, by `2^27 + 1`.
+const SPLIT: i32 = 28;
+
+fn axis_bits(values: impl IntoIterator<Item = f64>) -> Option<Bits> {
+    let mut bits = ZERO_AXIS;
+    for x in values {
+        if !x.is_finite() {
+            return None;
+        }
+        if x == 0.0 {
+            continue;
+        }
+        // x = m * 2^e, with an integer m.
+        let raw = x.abs().to_bits();
+        let (m, e) = match (raw >> 52) as i32 {
+            0 => (raw, -1074),
+            exp => (raw & ((1 << 52) - 1) | (1 << 52), exp - 1075),
+        };
+        bits.hi = bits.hi.max(e + 64 - m.leading_zeros() as i32 + 1);
+        bits.lo = bits.lo.min(e + m.trailing_zeros() as i32);
+    }
+    Some(bits)
+}
+
+/// Tells whether geogram's expansion arithmetic is exact on the given points, i.e. whether no product of a `monomial`, given as the list of axes of its factors, nor of a part of it, nor the splitting of a factor, can overflow or underflow.
+pub fn exact<const N: usize>(points: &[[f64; N]], monomials: &[&[usize]]) -> bool {
+    let Some(axes) = (0..N)
+        .map(|k| axis_bits(points.iter().map(|p| p[k])))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+    monomials.iter().all(|m| {
+        let hi: i32 = m.iter().map(|&k| axes[k].hi.max(0)).sum();
+        let lo: i32 = m.iter().map(|&k| axes[k].lo.min(0)).sum();
+        hi + SPLIT + SLACK <= 1023 && lo >= -1074
+    })
+}
+
+/// Checks that a sign is one of `-1`, `0`, `1`.
+fn check_sign(s: i16) {
+    assert!((-1..=1).contains(&s), "invalid sign {s}");
+}
+
+pub fn check_orient_2d(points: Points<2, 3>) {
+    let [a, b, c] = points.get();
+    gp::initialize();
+    let s = gp::orient_2d(&a, &b, &c);
+    check_sign(s);
+    if exact(&[a, b, c], &[&[0, 1]]) {
+        assert_eq!(s, reference::orient_2d(&a, &b, &c));
+        assert_eq!(-s, gp::orient_2d(&b, &a, &c));
+        assert_eq!(-s, gp::orient_2d(&a, &c, &b));
+    }
+}
+
+pub fn check_orient_3d(points: Points<3, 4>) {
+    let [a, b, c, d] = points.get();
+    gp::initialize();
+    let s = gp::orient_3d(&a, &b, &c, &d);
+    check_sign(s);
+    if exact(&[a, b, c, d], &[&[0, 1, 2]]) {
+        assert_eq!(s, reference::orient_3d(&a, &b, &c, &d));
+        assert_eq!(-s, gp::orient_3d(&b, &a, &c, &d));
+        assert_eq!(-s, gp::orient_3d(&a, &b, &d, &c));
+    }
+}
+
+pub fn check_orient_3d_inexact(points: Points<3, 4>) {
+    let [a, b, c, d] = points.get();
+    check_sign(gp::orient_3d_inexact(&a, &b, &c, &d));
+}
+
+pub fn check_det_3d(points: Points<3, 3>) {
+    let [a, b, c] = points.get();
+    gp::initialize();
+    let s = gp::det_3d(&a, &b, &c);
+    check_sign(s);
+    if exact(&[a, b, c], &[&[0, 1, 2]]) {
+        assert_eq!(s, reference::det_3d(&a, &b, &c));
+        assert_eq!(-s, gp::det_3d(&b, &a, &c));
+        assert_eq!(-s, gp::det_3d(&a, &c, &b));
+    }
+}
+
+pub fn check_det_4d(points: Points<4, 4>) {
+    let [a, b, c, d] = points.get();
+    gp::initialize();
+    let s = gp::det_4d(&a, &b, &c, &d);
+    check_sign(s);
+    if exact(&[a, b, c, d], &[&[0, 1, 2, 3]]) {
+        assert_eq!(s, reference::det_4d(&a, &b, &c, &d));
+        assert_eq!(-s, gp::det_4d(&b, &a, &c, &d));
+        assert_eq!(-s, gp::det_4d(&a, &b, &d, &c));
+    }
+}
+
+pub fn check_dot_3d(points: Points<3, 3>) {
+    let [a, b, c] = points.get();
+    gp::initialize();
+    let s = gp::dot_3d(&a, &b, &c);
+    check_sign(s);
+    if exact(&[a, b, c], &[&[0, 0], &[1, 1], &[2, 2]]) {
+        assert_eq!(s, reference::dot_3d(&a, &b, &c));
+        // The dot product is symmetric.
+        assert_eq!(s, gp::dot_3d(&a, &c, &b));
+    }
+}
+
+pub fn check_geo_sgn(x: f64) {
+    let s = gp::geo_sgn(x);
+    check_sign(s);
+    if !x.is_nan() {
+        assert_eq!(s, -gp::geo_sgn(-x));
+    }
+}
+
+pub fn check_points_are_identical(points: Points<3, 2>) {
+    let [p, q] = points.get();
+    let identical = gp::points_are_identical_3d(&p, &q);
+    assert_eq!(identical, p == q);
+    assert_eq!(identical, gp::points_are_identical_3d(&q, &p));
+    let [p, q] = [p, q].map(|p| [p[0], p[1]]);
+    assert_eq!(gp::points_are_identical_2d(&p, &q), p == q);
+}
+
+pub fn check_points_are_colinear_3d(points: Points<3, 3>) {
+    let [a, b, c] = points.get();
+    gp::initialize();
+    let colinear = gp::points_are_colinear_3d(&a, &b, &c);
+    if exact(&[a, b, c], &[&[0, 1, 2]]) {
+        assert_eq!(colinear, reference::points_are_colinear_3d(&a, &b, &c));
+        assert_eq!(colinear, gp::points_are_colinear_3d(&b, &c, &a));
+    }
+}
+
+/// The monomials of geogram's `side3` predicate, of degree 8 in the coordinates.
+const SIDE3: [&[usize]; 3] = [&[0; 8], &[1; 8], &[2; 8]];
+
+pub fn check_in_circle_2d_SOS(points: Points<2, 4>) {
+    let points = points.get();
+    gp::initialize();
+    let [a, b, c, p] = &points;
+    if !exact(&points, &SIDE3[..2]) || reference::orient_2d(a, b, c) == 0 {
+        return;
+    }
+    let s = gp::in_circle_2d_SOS(a, b, c, p);
+    check_sign(s);
+    assert_eq!(s, reference::in_circle_2d_SOS(a, b, c, p));
+    // The result does not depend on the order of the vertices.
+    assert_eq!(s, gp::in_circle_2d_SOS(b, a, c, p));
+    assert_eq!(s, gp::in_circle_2d_SOS(a, c, b, p));
+}
+
+pub fn check_in_circle_3d_SOS(points: Points<3, 4>) {
+    let points = points.get();
+    gp::initialize();
+    let [a, b, c, p] = &points;
+    if !exact(&points, &SIDE3) || reference::points_are_colinear_3d(a, b, c) {
+        return;
+    }
+    let s = gp::in_circle_3d_SOS(a, b, c, p);
+    check_sign(s);
+    assert_eq!(s, reference::in_circle_3d_SOS(a, b, c, p));
+    assert_eq!(s, gp::in_circle_3d_SOS(b, a, c, p));
+    assert_eq!(s, gp::in_circle_3d_SOS(a, c, b, p));
+}
+
+/// The monomials of geogram's `side4` predicate in 3d: a 3x3 determinant times a squared distance.
+const SIDE4: [&[usize]; 3] = [&[0, 1, 2, 0, 0], &[0, 1, 2, 1, 1], &[0, 1, 2, 2, 2]];
+
+pub fn check_in_sphere_3d_SOS(points: Points<3, 5>) {
+    let mut points = points.get();
+    gp::initialize();
+    if !exact(&points, &SIDE4) {
+        return;
+    }
+    // The filter supposes a positively oriented tetrahedron.
+    match reference::orient_3d(&points[0], &points[1], &points[2], &points[3]) {
+        0 => return,
+        -1 => points.swap(0, 1),
+        _ => {}
+    }
+    let [a, b, c, d, p] = &points;
+    let s = gp::in_sphere_3d_SOS(a, b, c, d, p);
+    check_sign(s);
+    assert_eq!(s, reference::in_sphere_3d_SOS(a, b, c, d, p));
+    // Even permutations keep the orientation, and the result.
+    assert_eq!(s, gp::in_sphere_3d_SOS(b, a, d, c, p));
+    assert_eq!(s, gp::in_sphere_3d_SOS(a, c, d, b, p));
+    // Exchanging a vertex with the query point flips the sign, up to the orientations of the tetrahedra.
+    let o = reference::orient_3d(p, b, c, d);
+    if o == 1 {
+        assert_eq!(-s, gp::in_sphere_3d_SOS(p, b, c, d, a));
+    } else if o == -1 {
+        assert_eq!(s, gp::in_sphere_3d_SOS(b, p, c, d, a));
+    }
+}
+
+/// Splits the last coordinate off, as the height of a lifted point.
+fn lift<const N: usize, const M: usize>(p: &[f64; N]) -> ([f64; M], f64) {
+    (std::array::from_fn(|k| p[k]), p[N - 1])
+}
+
+pub fn check_orient_2dlifted_SOS(points: Points<3, 4>) {
+    let points = points.get();
+    gp::initialize();
+    if !exact(&points, &[&[0, 1, 2]]) {
+        return;
+    }
+    let [(a, h_a), (b, h_b), (c, h_c), (p, h_p)] = points.each_ref().map(lift::<3, 2>);
+    if reference::orient_2d(&a, &b, &c) == 0 {
+        return;
+    }
+    let s = gp::orient_2dlifted_SOS(&a, &b, &c, &p, h_a, h_b, h_c, h_p);
+    check_sign(s);
+    assert_eq!(
+        s,
+        reference::orient_2dlifted_SOS(&a, &b, &c, &p, h_a, h_b, h_c, h_p)
+    );
+    // Above or below does not depend on the order of the vertices.
+    assert_eq!(
+        s,
+        gp::orient_2dlifted_SOS(&b, &a, &c, &p, h_b, h_a, h_c, h_p)
+    );
+    assert_eq!(
+        s,
+        gp::orient_2dlifted_SOS(&a, &c, &b, &p, h_a, h_c, h_b, h_p)
+    );
+    // Exchanging a vertex with the query point flips the sign, up to the orientations of the triangles.
+    let o = reference::orient_2d(&p, &b, &c);
+    if o != 0 {
+        let swapped = gp::orient_2dlifted_SOS(&p, &b, &c, &a, h_p, h_b, h_c, h_a);
+        assert_eq!(s * reference::orient_2d(&a, &b, &c), -swapped * o);
+    }
+}
+
+pub fn check_orient_3dlifted_SOS(points: Points<4, 5>) {
+    let points = points.get();
+    gp::initialize();
+    if !exact(&points, &[&[0, 1, 2, 3]]) {
+        return;
+    }
+    let [(a, h_a), (b, h_b), (c, h_c), (d, h_d), (p, h_p)] = points.each_ref().map(lift::<4, 3>);
+    let o = reference::orient_3d(&a, &b, &c, &d);
+    if o == 0 {
+        return;
+    }
+    let s = gp::orient_3dlifted_SOS(&a, &b, &c, &d, &p, h_a, h_b, h_c, h_d, h_p);
+    check_sign(s);
+    assert_eq!(
+        s,
+        reference::orient_3dlifted_SOS(&a, &b, &c, &d, &p, h_a, h_b, h_c, h_d, h_p)
+    );
+    assert_eq!(
+        s,
+        gp::orient_3dlifted_SOS(&b, &a, &c, &d, &p, h_b, h_a, h_c, h_d, h_p)
+    );
+    assert_eq!(
+        s,
+        gp::orient_3dlifted_SOS(&a, &b, &d, &c, &p, h_a, h_b, h_d, h_c, h_p)
+    );
+    let o_swapped = reference::orient_3d(&p, &b, &c, &d);
+    if o_swapped != 0 {
+        let swapped = gp::orient_3dlifted_SOS(&p, &b, &c, &d, &a, h_p, h_b, h_c, h_d, h_a);
+        assert_eq!(s * o, -swapped * o_swapped);
+    }
+}
+
+/// Checks that the result of a checked predicate is a valid sign or a consistent error, and that input in its safe
+/// range is never rejected as out of range.
+fn check_result(
+    result: Result<gp::Sign, gp::PredicateError>,
+    range: gp::range::SafeRange,
+    args: &[&[f64]],
+) {
+    if args.iter().all(|a| a.iter().all(|&x| range.contains(x))) {
+        assert_ne!(result, Err(gp::PredicateError::Overflow));
+    }
+    match result {
+        Ok(s) => check_sign(s),
+        Err(gp::PredicateError::NonFinite { arg_index }) => {
+            assert!(args[..arg_index]
+                .iter()
+                .flat_map(|a| a.iter())
+                .all(|x| x.is_finite()));
+            assert!(args[arg_index].iter().any(|x| !x.is_finite()));
+        }
+        Err(_) => assert!(args.iter().flat_map(|a| a.iter()).all(|x| x.is_finite())),
+    }
+}
+
+/// Calls every checked predicate, none of which may abort, whatever the input.
+pub fn check_checked(points: Points<4, 5>) {
+    let points = points.get();
+    gp::initialize();
+    let [a, b, c, d, p] = &points;
+    let [a2, b2, c2, p2] = [a, b, c, p].map(|q| lift::<4, 2>(q).0);
+    let [a3, b3, c3, d3, p3] = points.each_ref().map(|q| lift::<4, 3>(q).0);
+    let [h_a, h_b, h_c, h_d, h_p] = points.map(|q| q[3]);
+
+    check_result(
+        gp::checked_det_3d(&a3, &b3, &c3),
+        gp::range::DET_3D,
+        &[&a3, &b3, &c3],
+    );
+    check_result(
+        gp::checked_det_4d(a, b, c, d),
+        gp::range::DET_4D,
+        &[a, b, c, d],
+    );
+    check_result(
+        gp::checked_dot_3d(&a3, &b3, &c3),
+        gp::range::DOT_3D,
+        &[&a3, &b3, &c3],
+    );
+    check_result(
+        gp::checked_orient_2d(&a2, &b2, &c2),
+        gp::range::ORIENT_2D,
+        &[&a2, &b2, &c2],
+    );
+    check_result(
+        gp::checked_orient_3d(&a3, &b3, &c3, &d3),
+        gp::range::ORIENT_3D,
+        &[&a3, &b3, &c3, &d3],
+    );
+    check_result(
+        gp::checked_in_circle_2d_SOS(&a2, &b2, &c2, &p2),
+        gp::range::IN_CIRCLE_2D_SOS,
+        &[&a2, &b2, &c2, &p2],
+    );
+    check_result(
+        gp::checked_in_circle_3d_SOS(&a3, &b3, &c3, &p3),
+        gp::range::IN_CIRCLE_3D_SOS,
+        &[&a3, &b3, &c3, &p3],
+    );
+    check_result(
+        gp::checked_in_sphere_3d_SOS(&a3, &b3, &c3, &d3, &p3),
+        gp::range::IN_SPHERE_3D_SOS,
+        &[&a3, &b3, &c3, &d3, &p3],
+    );
+    check_result(
+        gp::checked_orient_2dlifted_SOS(&a2, &b2, &c2, &p2, h_a, h_b, h_c, h_p),
+        gp::range::ORIENT_2DLIFTED_SOS,
+        &[&a2, &b2, &c2, &p2, &[h_a], &[h_b], &[h_c], &[h_p]],
+    );
+    check_result(
+        gp::checked_orient_3dlifted_SOS(&a3, &b3, &c3, &d3, &p3, h_a, h_b, h_c, h_d, h_p),
+        gp::range::ORIENT_3DLIFTED_SOS,
+        &[
+            &a3,
+            &b3,
+            &c3,
+            &d3,
+            &p3,
+            &[h_a],
+            &[h_b],
+            &[h_c],
+            &[h_d],
+            &[h_p],
+        ],
+    );
+}
@@ -4,22 +4,45 @@
 
 // Namespace to handle calls into geogram from rust.
 namespace GEOGRAM {
-    
+
+// Mirrored by the shared enum `FilterMode` of the rust bridge.
+enum class FilterMode : uint8_t {
+    FilterAndExact,
+    ExactOnly,
+};
+
 int16_t det_3d(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c);
+int16_t det_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c);
 int16_t det_4d(const ::std::array<double, 4> &a, const ::std::array<double, 4> &b, const ::std::array<double, 4> &c, const ::std::array<double, 4> &d);
+int16_t det_4d_filtered(const ::std::array<double, 4> &a, const ::std::array<double, 4> &b, const ::std::array<double, 4> &c, const ::std::array<double, 4> &d);
 int16_t dot_3d(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c);
+int16_t dot_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c);
+uint64_t filters_skipped();
 int16_t geo_sgn(double x);
+FilterMode get_filter_mode();
 int16_t in_circle_2d_SOS(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c, const ::std::array<double, 2> &p);
+int16_t in_circle_2d_filtered(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c, const ::std::array<double, 2> &p);
+int16_t in_circle_3d_SOS(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &p);
+int16_t in_circle_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &p);
+int16_t in_sphere_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d, const ::std::array<double, 3> &p);
 int16_t in_sphere_3d_SOS(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d, const ::std::array<double, 3> &p);
 void initialize();
 int16_t orient_2d(const ::std::array<double, 2> &a, const  ::std::array<double, 2> &b, const ::std::array<double, 2> &c);
+int16_t orient_2d_filtered(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c);
 int16_t orient_2dlifted_SOS(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c, const ::std::array<double, 2> &p, double h_a, double h_b, double h_c, double h_p);
+int16_t orient_2dlifted_filtered(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c, const ::std::array<double, 2> &p, double h_a, double h_b, double h_c, double h_p);
 int16_t orient_3d(const ::std::array<double, 3> &a, const  ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d);
+int16_t orient_3d_filtered(const ::std::array<double, 3> &a, const  ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d);
 int16_t orient_3d_inexact(const ::std::array<double, 3> &a, const  ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d);
 int16_t orient_3dlifted_SOS(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d, const ::std::array<double, 3> &p, double h_a, double h_b, double h_c, double h_d, double h_p);
+int16_t orient_3dlifted_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d, const ::std::array<double, 3> &p, double h_a, double h_b, double h_c, double h_d, double h_p);
 bool points_are_colinear_3d(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c);
 bool points_are_identical_2d(const ::std::array<double, 2> &p1, const ::std::array<double, 2> &p2);
 bool points_are_identical_3d(const ::std::array<double, 3> &p1, const ::std::array<double, 3> &p2);
+void set_filter_mode(FilterMode mode);
 void show_stats();
+int16_t side1_3d_SOS(const ::std::array<double, 3> &p0, const ::std::array<double, 3> &p1, const ::std::array<double, 3> &q0);
+int16_t side1_nd_SOS(rust::Slice<const double> p0, rust::Slice<const double> p1, rust::Slice<const double> q0);
+int16_t side2_3d_SOS(const ::std::array<double, 3> &p0, const ::std::array<double, 3> &p1, const ::std::array<double, 3> &p2, const ::std::array<double, 3> &q0, const ::std::array<double, 3> &q1);
 void terminate();
 } // namespace GEOGRAM
\ No newline at end of file
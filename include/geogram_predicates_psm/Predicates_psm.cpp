@@ -11946,7 +11946,27 @@ namespace {
 
     using namespace GEO;
     
-    GEO::PCK::SOSMode SOS_mode_ = GEO::PCK::SOS_ADDRESS; 
+    // Atomic, so that the mode can be changed while other threads
+    // evaluate predicates.
+    std::atomic<GEO::PCK::SOSMode> SOS_mode_(GEO::PCK::SOS_ADDRESS);
+
+    std::atomic<GEO::PCK::FilterMode> filter_mode_(
+	GEO::PCK::FILTER_AND_EXACT
+    );
+
+    // Counts the filters skipped by the current thread, so that the tests
+    // can check that every predicate honors EXACT_ONLY. Thread-local, so
+    // that it does not slow down concurrent predicates.
+    thread_local GEO::Numeric::uint64 filters_skipped_ = 0;
+
+    inline bool filter_enabled() {
+	if(filter_mode_.load(std::memory_order_relaxed) !=
+	   GEO::PCK::EXACT_ONLY) {
+	    return true;
+	}
+	++filters_skipped_;
+	return false;
+    }
 
     class LexicoCompare {
     public:
@@ -12147,7 +12167,7 @@ namespace {
     Sign side1_3d_SOS(
         const double* p0, const double* p1, const double* q0
     ) {
-        Sign result = Sign(side1_3d_filter(p0, p1, q0));
+        Sign result = !filter_enabled() ? ZERO : Sign(side1_3d_filter(p0, p1, q0));
         if(result == ZERO) {
             result = side1_exact_SOS(p0, p1, q0, 3);
         }
@@ -12157,7 +12177,7 @@ namespace {
     Sign side1_4d_SOS(
         const double* p0, const double* p1, const double* q0
     ) {
-        Sign result = Sign(side1_4d_filter(p0, p1, q0));
+        Sign result = !filter_enabled() ? ZERO : Sign(side1_4d_filter(p0, p1, q0));
         if(result == ZERO) {
             result = side1_exact_SOS(p0, p1, q0, 4);
         }
@@ -12167,7 +12187,7 @@ namespace {
     Sign side1_6d_SOS(
         const double* p0, const double* p1, const double* q0
     ) {
-        Sign result = Sign(side1_6d_filter(p0, p1, q0));
+        Sign result = !filter_enabled() ? ZERO : Sign(side1_6d_filter(p0, p1, q0));
         if(result == ZERO) {
             result = side1_exact_SOS(p0, p1, q0, 6);
         }
@@ -12177,7 +12197,7 @@ namespace {
     Sign side1_7d_SOS(
         const double* p0, const double* p1, const double* q0
     ) {
-        Sign result = Sign(side1_7d_filter(p0, p1, q0));
+        Sign result = !filter_enabled() ? ZERO : Sign(side1_7d_filter(p0, p1, q0));
         if(result == ZERO) {
             result = side1_exact_SOS(p0, p1, q0, 7);
         }
@@ -12187,7 +12207,7 @@ namespace {
     Sign side1_8d_SOS(
         const double* p0, const double* p1, const double* q0
     ) {
-        Sign result = Sign(side1_8d_filter(p0, p1, q0));
+        Sign result = !filter_enabled() ? ZERO : Sign(side1_8d_filter(p0, p1, q0));
         if(result == ZERO) {
             result = side1_exact_SOS(p0, p1, q0, 8);
         }
@@ -12275,7 +12295,7 @@ namespace {
         const double* p0, const double* p1, const double* p2,
         const double* q0, const double* q1
     ) {
-        Sign result = Sign(side2_3d_filter(p0, p1, p2, q0, q1));
+        Sign result = !filter_enabled() ? ZERO : Sign(side2_3d_filter(p0, p1, p2, q0, q1));
         if(result == ZERO) {
             result = side2_exact_SOS(p0, p1, p2, q0, q1, 3);
         }
@@ -12286,7 +12306,7 @@ namespace {
         const double* p0, const double* p1, const double* p2,
         const double* q0, const double* q1
     ) {
-        Sign result = Sign(side2_4d_filter(p0, p1, p2, q0, q1));
+        Sign result = !filter_enabled() ? ZERO : Sign(side2_4d_filter(p0, p1, p2, q0, q1));
         if(result == ZERO) {
             result = side2_exact_SOS(p0, p1, p2, q0, q1, 4);
         }
@@ -12297,7 +12317,7 @@ namespace {
         const double* p0, const double* p1, const double* p2,
         const double* q0, const double* q1
     ) {
-        Sign result = Sign(side2_6d_filter(p0, p1, p2, q0, q1));
+        Sign result = !filter_enabled() ? ZERO : Sign(side2_6d_filter(p0, p1, p2, q0, q1));
         if(result == ZERO) {
             result = side2_exact_SOS(p0, p1, p2, q0, q1, 6);
         }
@@ -12308,7 +12328,7 @@ namespace {
         const double* p0, const double* p1, const double* p2,
         const double* q0, const double* q1
     ) {
-        Sign result = Sign(side2_7d_filter(p0, p1, p2, q0, q1));
+        Sign result = !filter_enabled() ? ZERO : Sign(side2_7d_filter(p0, p1, p2, q0, q1));
         if(result == ZERO) {
             result = side2_exact_SOS(p0, p1, p2, q0, q1, 7);
         }
@@ -12319,7 +12339,7 @@ namespace {
         const double* p0, const double* p1, const double* p2,
         const double* q0, const double* q1
     ) {
-        Sign result = Sign(side2_8d_filter(p0, p1, p2, q0, q1));
+        Sign result = !filter_enabled() ? ZERO : Sign(side2_8d_filter(p0, p1, p2, q0, q1));
         if(result == ZERO) {
             result = side2_exact_SOS(p0, p1, p2, q0, q1, 8);
         }
@@ -12569,7 +12589,7 @@ namespace {
         const double* p0, const double* p1, const double* p2, const double* p3,
         const double* q0, const double* q1, const double* q2
     ) {
-        Sign result = Sign(side3_3d_filter(p0, p1, p2, p3, q0, q1, q2));
+        Sign result = !filter_enabled() ? ZERO : Sign(side3_3d_filter(p0, p1, p2, p3, q0, q1, q2));
         if(result == ZERO) {
             result = side3_exact_SOS(p0, p1, p2, p3, q0, q1, q2, 3);
         }
@@ -12581,7 +12601,7 @@ namespace {
         const double* p0, const double* p1, const double* p2, const double* p3,
         const double* q0, const double* q1, const double* q2
     ) {
-        Sign result = Sign(side3_4d_filter(p0, p1, p2, p3, q0, q1, q2));
+        Sign result = !filter_enabled() ? ZERO : Sign(side3_4d_filter(p0, p1, p2, p3, q0, q1, q2));
         if(result == ZERO) {
             result = side3_exact_SOS(p0, p1, p2, p3, q0, q1, q2, 4);
         }
@@ -12592,7 +12612,7 @@ namespace {
         const double* p0, const double* p1, const double* p2, const double* p3,
         const double* q0, const double* q1, const double* q2
     ) {
-        Sign result = Sign(side3_6d_filter(p0, p1, p2, p3, q0, q1, q2));
+        Sign result = !filter_enabled() ? ZERO : Sign(side3_6d_filter(p0, p1, p2, p3, q0, q1, q2));
         if(result == ZERO) {
             result = side3_exact_SOS(p0, p1, p2, p3, q0, q1, q2, 6);
         }
@@ -12603,7 +12623,7 @@ namespace {
         const double* p0, const double* p1, const double* p2, const double* p3,
         const double* q0, const double* q1, const double* q2
     ) {
-        Sign result = Sign(side3_7d_filter(p0, p1, p2, p3, q0, q1, q2));
+        Sign result = !filter_enabled() ? ZERO : Sign(side3_7d_filter(p0, p1, p2, p3, q0, q1, q2));
         if(result == ZERO) {
             result = side3_exact_SOS(p0, p1, p2, p3, q0, q1, q2, 7);
         }
@@ -12614,7 +12634,7 @@ namespace {
         const double* p0, const double* p1, const double* p2, const double* p3,
         const double* q0, const double* q1, const double* q2
     ) {
-        Sign result = Sign(side3_8d_filter(p0, p1, p2, p3, q0, q1, q2));
+        Sign result = !filter_enabled() ? ZERO : Sign(side3_8d_filter(p0, p1, p2, p3, q0, q1, q2));
         if(result == ZERO) {
             result = side3_exact_SOS(p0, p1, p2, p3, q0, q1, q2, 8);
         }
@@ -12936,7 +12956,7 @@ namespace {
         const double* p1, const double* p2, const double* p3, const double* p4,
         const double* q0, const double* q1, const double* q2, const double* q3
     ) {
-        Sign result = Sign(side4_4d_filter(p0, p1, p2, p3, p4, q0, q1, q2, q3));
+        Sign result = !filter_enabled() ? ZERO : Sign(side4_4d_filter(p0, p1, p2, p3, p4, q0, q1, q2, q3));
         if(result == ZERO) {
             result = side4_exact_SOS(p0, p1, p2, p3, p4, q0, q1, q2, q3, 4);
         }
@@ -12948,7 +12968,7 @@ namespace {
         const double* p1, const double* p2, const double* p3, const double* p4,
         const double* q0, const double* q1, const double* q2, const double* q3
     ) {
-        Sign result = Sign(side4_6d_filter(p0, p1, p2, p3, p4, q0, q1, q2, q3));
+        Sign result = !filter_enabled() ? ZERO : Sign(side4_6d_filter(p0, p1, p2, p3, p4, q0, q1, q2, q3));
         if(result == ZERO) {
             result = side4_exact_SOS(p0, p1, p2, p3, p4, q0, q1, q2, q3, 6);
         }
@@ -12960,7 +12980,7 @@ namespace {
         const double* p1, const double* p2, const double* p3, const double* p4,
         const double* q0, const double* q1, const double* q2, const double* q3
     ) {
-        Sign result = Sign(side4_7d_filter(p0, p1, p2, p3, p4, q0, q1, q2, q3));
+        Sign result = !filter_enabled() ? ZERO : Sign(side4_7d_filter(p0, p1, p2, p3, p4, q0, q1, q2, q3));
         if(result == ZERO) {
             result = side4_exact_SOS(p0, p1, p2, p3, p4, q0, q1, q2, q3, 7);
         }
@@ -12972,7 +12992,7 @@ namespace {
         const double* p1, const double* p2, const double* p3, const double* p4,
         const double* q0, const double* q1, const double* q2, const double* q3
     ) {
-        Sign result = Sign(side4_8d_filter(p0, p1, p2, p3, p4, q0, q1, q2, q3));
+        Sign result = !filter_enabled() ? ZERO : Sign(side4_8d_filter(p0, p1, p2, p3, p4, q0, q1, q2, q3));
         if(result == ZERO) {
             result = side4_exact_SOS(p0, p1, p2, p3, p4, q0, q1, q2, q3, 8);
         }
@@ -13185,9 +13205,12 @@ namespace {
             p_sort[3] = p3;
             SOS_sort(p_sort, p_sort + 4, 2);
             for(index_t i = 0; i < 4; ++i) {
+                // The heights are perturbed downwards, as in
+                // side4h_3d_exact_SOS(). Perturbing h0 moves all
+                // the a_i3 terms, hence the -Delta3.
                 if(p_sort[i] == p0) {
                     const expansion& z1 = expansion_diff(Delta2, Delta1);
-                    const expansion& z = expansion_sum(z1, Delta3);
+                    const expansion& z = expansion_diff(z1, Delta3);
                     Sign z_sign = z.sign();
                     if(z_sign != ZERO) {
                         return Sign(Delta3_sign * z_sign);
@@ -13203,7 +13226,7 @@ namespace {
                         return Sign(-Delta3_sign * Delta2_sign);
                     }
                 } else if(p_sort[i] == p3) {
-		    return NEGATIVE;
+		    return POSITIVE;
                 } 
             }
         }
@@ -13317,6 +13340,18 @@ namespace GEO {
 	    return SOS_mode_;
 	}
 
+	void set_filter_mode(FilterMode m) {
+	    filter_mode_ = m;
+	}
+
+	FilterMode get_filter_mode() {
+	    return filter_mode_;
+	}
+
+	Numeric::uint64 filters_skipped() {
+	    return filters_skipped_;
+	}
+
 	
         Sign side1_SOS(
             const double* p0, const double* p1,
@@ -13390,7 +13425,7 @@ namespace GEO {
             const double* q0, const double* q1, const double* q2,
 	    bool SOS
         ) {
-            Sign result = Sign(
+            Sign result = !filter_enabled() ? ZERO : Sign(
 		side3h_3d_filter(p0, p1, p2, p3, h0, h1, h2, h3, q0, q1, q2)
 	    );
             if(SOS && result == ZERO) {
@@ -13440,7 +13475,7 @@ namespace GEO {
 	    const double* p3, const double* p4
         ) {
             stats_side4.log_invoke();
-            Sign result = Sign(side4_3d_filter(p0, p1, p2, p3, p4));
+            Sign result = !filter_enabled() ? ZERO : Sign(side4_3d_filter(p0, p1, p2, p3, p4));
             if(result == 0) {
                 // last argument is false: do not apply symbolic perturbation
                 result = side4_3d_exact_SOS(p0, p1, p2, p3, p4, false);
@@ -13454,7 +13489,7 @@ namespace GEO {
             const double* p4
         ) {
             stats_side4.log_invoke();
-            Sign result = Sign(side4_3d_filter(p0, p1, p2, p3, p4));
+            Sign result = !filter_enabled() ? ZERO : Sign(side4_3d_filter(p0, p1, p2, p3, p4));
             if(result == 0) {
                 result = side4_3d_exact_SOS(p0, p1, p2, p3, p4);
             }
@@ -13484,7 +13519,7 @@ namespace GEO {
             
             // This specialized filter supposes that orient_3d(p0,p1,p2,p3) > 0
 
-            Sign result = Sign(in_sphere_3d_filter_optim(p0, p1, p2, p3, p4));
+            Sign result = !filter_enabled() ? ZERO : Sign(in_sphere_3d_filter_optim(p0, p1, p2, p3, p4));
 
             if(result == 0) {
                 result = side4_3d_exact_SOS(p0, p1, p2, p3, p4);
@@ -13492,6 +13527,15 @@ namespace GEO {
             return Sign(-result);
         }
 
+        Sign in_sphere_3d_filtered(
+            const double* p0, const double* p1,
+            const double* p2, const double* p3,
+            const double* p4
+        ) {
+            // Supposes that orient_3d(p0,p1,p2,p3) > 0, like in_sphere_3d_SOS()
+            return Sign(-in_sphere_3d_filter_optim(p0, p1, p2, p3, p4));
+        }
+
         Sign GEOGRAM_API in_circle_2d_SOS(
             const double* p0, const double* p1, const double* p2,
             const double* p3
@@ -13511,13 +13555,21 @@ namespace GEO {
 
 	    // TODO: implement specialized filter like the one used
 	    // by "in-sphere".
-	    Sign s = Sign(-side3_2d_filter(p0, p1, p2, p3, p0, p1, p2));
+	    Sign s = !filter_enabled() ? ZERO :
+		Sign(-side3_2d_filter(p0, p1, p2, p3, p0, p1, p2));
 	    if(s != ZERO) {
 		return s;
 	    }
 	    return Sign(-side3_exact_SOS(p0, p1, p2, p3, p0, p1, p2, 2));
         }
 
+        Sign in_circle_2d_filtered(
+            const double* p0, const double* p1, const double* p2,
+            const double* p3
+        ) {
+            return Sign(-side3_2d_filter(p0, p1, p2, p3, p0, p1, p2));
+        }
+
         Sign GEOGRAM_API in_circle_3d_SOS(
             const double* p0, const double* p1, const double* p2,
             const double* p3
@@ -13537,6 +13589,13 @@ namespace GEO {
             return Sign(-side3_3d_SOS(p0,p1,p2,p3,p0,p1,p2));
         }
 
+        Sign in_circle_3d_filtered(
+            const double* p0, const double* p1, const double* p2,
+            const double* p3
+        ) {
+            return Sign(-side3_3d_filter(p0, p1, p2, p3, p0, p1, p2));
+        }
+
         Sign GEOGRAM_API in_circle_3dlifted_SOS(
             const double* p0, const double* p1, const double* p2,
             const double* p3,
@@ -13557,19 +13616,25 @@ namespace GEO {
             const double* p0, const double* p1, const double* p2
         ) {
             stats_orient2d.log_invoke();
-            Sign result = Sign(orient_2d_filter(p0, p1, p2));
+            Sign result = !filter_enabled() ? ZERO : Sign(orient_2d_filter(p0, p1, p2));
             if(result == 0) {
                 result = orient_2d_exact(p0, p1, p2);
             }
             return result;
         }
 
+        Sign orient_2d_filtered(
+            const double* p0, const double* p1, const double* p2
+        ) {
+            return Sign(orient_2d_filter(p0, p1, p2));
+        }
+
         Sign orient_2dlifted_SOS(
             const double* p0, const double* p1,
             const double* p2, const double* p3, 
             double h0, double h1, double h2, double h3
 	) {
-            Sign result = Sign(
+            Sign result = !filter_enabled() ? ZERO : Sign(
                 side3_2dlifted_2d_filter(
                     p0, p1, p2, p3, h0, h1, h2, h3
                     )
@@ -13584,19 +13649,36 @@ namespace GEO {
 	    return result;
 	}
 
+        Sign orient_2dlifted_filtered(
+            const double* p0, const double* p1,
+            const double* p2, const double* p3,
+            double h0, double h1, double h2, double h3
+        ) {
+            return Sign(
+                side3_2dlifted_2d_filter(p0, p1, p2, p3, h0, h1, h2, h3)
+            );
+        }
+
 	
         Sign orient_3d(
             const double* p0, const double* p1,
             const double* p2, const double* p3
         ) {
             stats_orient3d.log_invoke();
-            Sign result = Sign(orient_3d_filter(p0, p1, p2, p3));
+            Sign result = !filter_enabled() ? ZERO : Sign(orient_3d_filter(p0, p1, p2, p3));
             if(result == 0) {
                 result = orient_3d_exact(p0, p1, p2, p3);
             }
             return result;
         }
 
+        Sign orient_3d_filtered(
+            const double* p0, const double* p1,
+            const double* p2, const double* p3
+        ) {
+            return Sign(orient_3d_filter(p0, p1, p2, p3));
+        }
+
 
         Sign orient_3dlifted(
             const double* p0, const double* p1,
@@ -13604,7 +13686,7 @@ namespace GEO {
             double h0, double h1, double h2, double h3, double h4
         ) {
             stats_orient3dh.log_invoke();
-            Sign result = Sign(
+            Sign result = !filter_enabled() ? ZERO : Sign(
                 side4h_3d_filter(
                     p0, p1, p2, p3, p4, h0, h1, h2, h3, h4
                     )
@@ -13626,7 +13708,7 @@ namespace GEO {
             double h0, double h1, double h2, double h3, double h4
         ) {
             stats_orient3dh.log_invoke();
-            Sign result = Sign(
+            Sign result = !filter_enabled() ? ZERO : Sign(
                 side4h_3d_filter(
                     p0, p1, p2, p3, p4, h0, h1, h2, h3, h4
                     )
@@ -13641,11 +13723,21 @@ namespace GEO {
             return Sign(-result);
         }
 
+        Sign orient_3dlifted_filtered(
+            const double* p0, const double* p1,
+            const double* p2, const double* p3, const double* p4,
+            double h0, double h1, double h2, double h3, double h4
+        ) {
+            return Sign(
+                -side4h_3d_filter(p0, p1, p2, p3, p4, h0, h1, h2, h3, h4)
+            );
+        }
+
 	Sign det_3d(
 	    const double* p0, const double* p1, const double* p2
 	) {
             stats_det3d.log_invoke();
-	    Sign result = Sign(
+	    Sign result = !filter_enabled() ? ZERO : Sign(
 		det_3d_filter(p0, p1, p2)
 	    );
 	    if(result == 0) {
@@ -13654,13 +13746,19 @@ namespace GEO {
 	    return result;
 	}
 
+	Sign det_3d_filtered(
+	    const double* p0, const double* p1, const double* p2
+	) {
+	    return Sign(det_3d_filter(p0, p1, p2));
+	}
+
 
 	Sign det_4d(
 	    const double* p0, const double* p1,
 	    const double* p2, const double* p3
 	) {
             stats_det4d.log_invoke();
-	    Sign result = Sign(
+	    Sign result = !filter_enabled() ? ZERO : Sign(
 		det_4d_filter(p0, p1, p2, p3)
 	    );
 
@@ -13703,7 +13801,7 @@ namespace GEO {
 	    const double* p2, const double* p3,
 	    const double* p4
 	) {
-	    Sign result = Sign(
+	    Sign result = !filter_enabled() ? ZERO : Sign(
 		det_compare_4d_filter(p0, p1, p2, p3, p4)
 	    );
 	    if(result == 0) {
@@ -13736,6 +13834,13 @@ namespace GEO {
 	    }
 	    return result;
 	}
+
+	Sign det_4d_filtered(
+	    const double* p0, const double* p1,
+	    const double* p2, const double* p3
+	) {
+	    return Sign(det_4d_filter(p0, p1, p2, p3));
+	}
 	
 	
 	bool aligned_3d(
@@ -13755,17 +13860,23 @@ namespace GEO {
 	Sign dot_3d(
 	    const double* p0, const double* p1, const double* p2
 	) {
-	    Sign result = Sign(det_3d_filter(p0, p1, p2));
+	    Sign result = !filter_enabled() ? ZERO : Sign(dot_3d_filter(p0, p1, p2));
 	    if(result == 0) {
                 result = dot_3d_exact(p0, p1, p2);
             }
 	    return result;
 	}
 
+	Sign dot_3d_filtered(
+	    const double* p0, const double* p1, const double* p2
+	) {
+	    return Sign(dot_3d_filter(p0, p1, p2));
+	}
+
 	Sign dot_compare_3d(
 	    const double* v0, const double* v1, const double* v2
 	) {
-	    Sign result = Sign(dot_compare_3d_filter(v0, v1, v2));
+	    Sign result = !filter_enabled() ? ZERO : Sign(dot_compare_3d_filter(v0, v1, v2));
 	    if(result == 0) {
 		result = dot_compare_3d_exact(v0, v1, v2);
 	    }
@@ -618,23 +618,24 @@ namespace GEO {
 
 
 #ifndef GEOGRAM_BASIC_THREAD_SYNC
-#define GEOGRAM_SPINLOCK_INIT 0
+#include <atomic>
+
+#define GEOGRAM_SPINLOCK_INIT ATOMIC_FLAG_INIT
 
 namespace GEO {
     namespace Process {
     
-        typedef int spinlock;
+        // Minimal spinlock for the PSM, guards the expansion pools
+        // that are shared by all threads.
+        typedef std::atomic_flag spinlock;
         
         inline void acquire_spinlock(spinlock& x) {
-            // Not implemented yet for PSMs
-            geo_argused(x);
-            geo_assert_not_reached;
+            while(x.test_and_set(std::memory_order_acquire)) {
+            }
         }
     
         inline void release_spinlock(spinlock& x) {
-            // Not implemented yet for PSMs
-            geo_argused(x); 
-            geo_assert_not_reached;       
+            x.clear(std::memory_order_release);
         }
     }
 }
@@ -728,13 +729,13 @@ namespace GEO {
         public:
             PredicateStats(const char* name);
             void log_invoke() {
-                ++invoke_count_;
+                invoke_count_.fetch_add(1, std::memory_order_relaxed);
             }
             void log_exact() {
-                ++exact_count_;
+                exact_count_.fetch_add(1, std::memory_order_relaxed);
             }
             void log_SOS() {
-                ++SOS_count_;
+                SOS_count_.fetch_add(1, std::memory_order_relaxed);
             }
             void show_stats();
             static void show_all_stats();
@@ -838,6 +839,27 @@ namespace GEO {
 	void GEOGRAM_API set_SOS_mode(SOSMode m);
 
 	SOSMode GEOGRAM_API get_SOS_mode();
+
+	enum FilterMode { FILTER_AND_EXACT, EXACT_ONLY };
+
+	/**
+	 * \brief Selects the arithmetic used by the predicates.
+	 * \details FILTER_AND_EXACT (the default) uses the arithmetic
+	 *  filters, and exact arithmetic when they are inconclusive.
+	 *  EXACT_ONLY skips the filters. Both modes are exact, hence the
+	 *  mode only changes the cost of the predicates. Meant for
+	 *  benchmarking and verifying the filters.
+	 */
+	void GEOGRAM_API set_filter_mode(FilterMode m);
+
+	FilterMode GEOGRAM_API get_filter_mode();
+
+	/**
+	 * \brief Gets the number of filters that the calling thread skipped
+	 *  in EXACT_ONLY mode.
+	 * \details Meant for testing that the predicates honor the mode.
+	 */
+	Numeric::uint64 GEOGRAM_API filters_skipped();
 	
         Sign GEOGRAM_API side1_SOS(
             const double* p0, const double* p1,
@@ -893,18 +915,50 @@ namespace GEO {
             const double* p4
          );
 
+        /**
+         * \brief Evaluates the arithmetic filter of in_sphere_3d_SOS() only.
+         * \pre orient_3d(p0,p1,p2,p3) > 0
+         * \return the sign of in_sphere_3d_SOS() if the filter is certain,
+         *  ZERO otherwise
+         */
+        Sign GEOGRAM_API in_sphere_3d_filtered(
+            const double* p0, const double* p1,
+            const double* p2, const double* p3,
+            const double* p4
+        );
+
 
          Sign GEOGRAM_API in_circle_2d_SOS(
              const double* p0, const double* p1, const double* p2,
              const double* p3
          );
 
+        /**
+         * \brief Evaluates the arithmetic filter of in_circle_2d_SOS() only.
+         * \return the sign of in_circle_2d_SOS() if the filter is certain,
+         *  ZERO otherwise
+         */
+        Sign GEOGRAM_API in_circle_2d_filtered(
+            const double* p0, const double* p1, const double* p2,
+            const double* p3
+        );
+
 	 
          Sign GEOGRAM_API in_circle_3d_SOS(
              const double* p0, const double* p1, const double* p2,
              const double* p3
          );
 
+        /**
+         * \brief Evaluates the arithmetic filter of in_circle_3d_SOS() only.
+         * \return the sign of in_circle_3d_SOS() if the filter is certain,
+         *  ZERO otherwise
+         */
+        Sign GEOGRAM_API in_circle_3d_filtered(
+            const double* p0, const double* p1, const double* p2,
+            const double* p3
+        );
+
 
         Sign GEOGRAM_API in_circle_3dlifted_SOS(
             const double* p0, const double* p1, const double* p2,
@@ -917,6 +971,15 @@ namespace GEO {
             const double* p0, const double* p1, const double* p2
         );
 
+        /**
+         * \brief Evaluates the arithmetic filter of orient_2d() only.
+         * \return the sign of orient_2d() if the filter is certain,
+         *  ZERO otherwise
+         */
+        Sign GEOGRAM_API orient_2d_filtered(
+            const double* p0, const double* p1, const double* p2
+        );
+
 
 #ifndef GEOGRAM_PSM        
         inline Sign orient_2d(
@@ -931,6 +994,18 @@ namespace GEO {
             const double* p2, const double* p3, 
             double h0, double h1, double h2, double h3
         );
+
+        /**
+         * \brief Evaluates the arithmetic filter of orient_2dlifted_SOS()
+         *  only.
+         * \return the sign of orient_2dlifted_SOS() if the filter is
+         *  certain, ZERO otherwise
+         */
+        Sign GEOGRAM_API orient_2dlifted_filtered(
+            const double* p0, const double* p1,
+            const double* p2, const double* p3,
+            double h0, double h1, double h2, double h3
+        );
 	
         
         Sign GEOGRAM_API orient_3d(
@@ -938,6 +1013,16 @@ namespace GEO {
             const double* p2, const double* p3
         );
 
+        /**
+         * \brief Evaluates the arithmetic filter of orient_3d() only.
+         * \return the sign of orient_3d() if the filter is certain,
+         *  ZERO otherwise
+         */
+        Sign GEOGRAM_API orient_3d_filtered(
+            const double* p0, const double* p1,
+            const double* p2, const double* p3
+        );
+
 
 #ifndef GEOGRAM_PSM
         inline Sign orient_3d(
@@ -961,16 +1046,47 @@ namespace GEO {
             double h0, double h1, double h2, double h3, double h4
         );
 
+        /**
+         * \brief Evaluates the arithmetic filter of orient_3dlifted_SOS()
+         *  only.
+         * \return the sign of orient_3dlifted_SOS() if the filter is
+         *  certain, ZERO otherwise
+         */
+        Sign GEOGRAM_API orient_3dlifted_filtered(
+            const double* p0, const double* p1,
+            const double* p2, const double* p3, const double* p4,
+            double h0, double h1, double h2, double h3, double h4
+        );
+
 
 	Sign GEOGRAM_API det_3d(
 	    const double* p0, const double* p1, const double* p2
 	);
 
+	/**
+	 * \brief Evaluates the arithmetic filter of det_3d() only.
+	 * \return the sign of det_3d() if the filter is certain,
+	 *  ZERO otherwise
+	 */
+	Sign GEOGRAM_API det_3d_filtered(
+	    const double* p0, const double* p1, const double* p2
+	);
+
 	Sign GEOGRAM_API det_4d(
 	    const double* p0, const double* p1,
 	    const double* p2, const double* p3
 	);
 
+	/**
+	 * \brief Evaluates the arithmetic filter of det_4d() only.
+	 * \return the sign of det_4d() if the filter is certain,
+	 *  ZERO otherwise
+	 */
+	Sign GEOGRAM_API det_4d_filtered(
+	    const double* p0, const double* p1,
+	    const double* p2, const double* p3
+	);
+
 	Sign GEOGRAM_API det_compare_4d(
 	    const double* p0, const double* p1,
 	    const double* p2, const double* p3,
@@ -985,6 +1101,15 @@ namespace GEO {
 	    const double* p0, const double* p1, const double* p2
 	);
 
+	/**
+	 * \brief Evaluates the arithmetic filter of dot_3d() only.
+	 * \return the sign of dot_3d() if the filter is certain,
+	 *  ZERO otherwise
+	 */
+	Sign GEOGRAM_API dot_3d_filtered(
+	    const double* p0, const double* p1, const double* p2
+	);
+
 #ifndef GEOGRAM_PSM
 
 	inline bool aligned_3d(
@@ -0,0 +1,182 @@
+//! Exact collinearity, coplanarity and affine rank of point sets.
+
+use crate::determinant::det;
+use crate::expansion::Expansion;
+use crate::interval::{Interval, Ring};
+use crate::predicates_nd::rows;
+
+/// The subsets of `k` of the columns `0..n`, as increasing indices.
+fn column_subsets(n: usize, k: usize) -> impl Iterator<Item = Vec<usize>> {
+    let mut next = (k <= n).then(|| (0..k).collect::<Vec<_>>());
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        // Increments the last index that can still be incremented, and resets the ones after it.
+        if let Some(i) = (0..k).rev().find(|&i| current[i] < n - k + i) {
+            let mut following = current.clone();
+            following[i] += 1;
+            for j in i + 1..k {
+                following[j] = following[j - 1] + 1;
+            }
+            next = Some(following);
+        }
+        Some(current)
+    })
+}
+
+fn minor<T: Ring>(rows: &[Vec<T>], columns: &[usize]) -> T {
+    let minor: Vec<Vec<T>> = rows
+        .iter()
+        .map(|row| columns.iter().map(|&j| row[j].clone()).collect())
+        .collect();
+    det(&minor)
+}
+
+/// Tests whether the differences of `points` to the first one are linearly independent, i.e. whether one of their
+/// maximal minors is not zero.
+///
+/// The minors are first evaluated with [`Interval`]s, and only the ones they cannot decide with exact [`Expansion`]s.
+fn affinely_independent<const D: usize>(points: &[&[f64; D]]) -> bool {
+    let k = points.len().saturating_sub(1);
+    let intervals = rows::<Interval, D>(points);
+    let mut undecided = Vec::new();
+    for columns in column_subsets(D, k) {
+        match minor(&intervals, &columns).sign() {
+            Some(0) => {}
+            Some(_) => return true,
+            None => undecided.push(columns),
+        }
+    }
+    if undecided.is_empty() {
+        return false;
+    }
+    let expansions = rows::<Expansion, D>(points);
+    undecided
+        .iter()
+        .any(|columns| minor(&expansions, columns).sign() != 0)
+}
+
+/// Tests whether three 2d points are collinear.
+///
+/// See [`collinear_nd`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c` three 2d points
+///
+/// ### Return values
+/// - `true` - if `a`, `b` and `c` lie on a common line
+/// - `false` - otherwise
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// assert!(gp::collinear_2d(&[0.0, 0.0], &[1.0, 1.0], &[3.0, 3.0]));
+/// // 0.1 + 0.2 rounds to the double above 0.3
+/// assert!(!gp::collinear_2d(&[0.1, 0.1], &[0.2, 0.2], &[0.3, 0.1 + 0.2]));
+/// ```
+pub fn collinear_2d(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> bool {
+    collinear_nd(a, b, c)
+}
+
+/// Tests whether three points are collinear, in any dimension.
+///
+/// The points are collinear if all 2x2 minors of `b - a`, `c - a` vanish. The minors are evaluated with
+/// [`Interval`]s, and only if they cannot decide with exact [`Expansion`]s. Unlike [`orient_2d`](crate::orient_2d),
+/// the result does not depend on the [`FilterMode`](crate::FilterMode), and it is exact for coordinates in
+/// [`range::COLLINEAR`](crate::range::COLLINEAR). Identical points count as collinear.
+///
+/// ### Parameters
+/// - `a`, `b`, `c` three points
+///
+/// ### Return values
+/// - `true` - if `a`, `b` and `c` lie on a common line
+/// - `false` - otherwise
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 1.0, 2.0, 3.0];
+/// let b = [1.0, 2.0, 3.0, 4.0];
+///
+/// assert!(gp::collinear_nd(&a, &b, &[-2.0, -1.0, 0.0, 1.0]));
+/// assert!(gp::collinear_nd(&a, &a, &b));
+/// assert!(!gp::collinear_nd(&a, &b, &[-2.0, -1.0, 0.0, 1.5]));
+/// ```
+pub fn collinear_nd<const D: usize>(a: &[f64; D], b: &[f64; D], c: &[f64; D]) -> bool {
+    !affinely_independent(&[a, b, c])
+}
+
+/// Tests whether four 3d points are coplanar.
+///
+/// This is the case if [`orient_3d`](crate::orient_3d) is `0`, but the result does not depend on the
+/// [`FilterMode`](crate::FilterMode). It is exact for coordinates in [`range::ORIENT_3D`](crate::range::ORIENT_3D).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` four 3d points
+///
+/// ### Return values
+/// - `true` - if `a`, `b`, `c` and `d` lie on a common plane
+/// - `false` - otherwise
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 1.0];
+/// let b = [1.0, 0.0, 1.0];
+/// let c = [0.0, 1.0, 1.0];
+///
+/// assert!(gp::coplanar_3d(&a, &b, &c, &[0.25, 0.5, 1.0]));
+/// assert!(!gp::coplanar_3d(&a, &b, &c, &[0.25, 0.5, 1.0 + f64::EPSILON]));
+/// ```
+pub fn coplanar_3d(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], d: &[f64; 3]) -> bool {
+    !affinely_independent(&[a, b, c, d])
+}
+
+/// Computes the dimension of the affine hull of a set of points.
+///
+/// This is `0` if all points are identical, `1` if they are collinear, `2` if they are coplanar, and so on up to
+/// `D`. The points are added one by one to a set of affinely independent ones, which grows if the new point is
+/// independent of them, as decided by the same minors as in [`collinear_nd`]. The result is exact for coordinates in
+/// [`SafeRange::of_degree(D)`](crate::range::SafeRange::of_degree).
+///
+/// ### Parameters
+/// - `points` a non-empty set of points
+///
+/// ### Returns
+/// - the dimension of the smallest affine subspace that contains all `points`
+///
+/// ### Panics
+/// - if `points` is empty
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let mut points = vec![[0.0, 0.0, 0.0], [1.0, 2.0, 3.0], [2.0, 4.0, 6.0]];
+/// assert_eq!(1, gp::affine_rank(&points));
+///
+/// points.push([1.0, 0.0, 0.0]);
+/// points.push([3.0, 4.0, 6.0]);
+/// assert_eq!(2, gp::affine_rank(&points));
+///
+/// points.push([0.0, 0.0, 1.0]);
+/// assert_eq!(3, gp::affine_rank(&points));
+/// ```
+pub fn affine_rank<const D: usize>(points: &[[f64; D]]) -> usize {
+    let [first, rest @ ..] = points else {
+        panic!("the affine hull of no points has no dimension");
+    };
+    let mut basis = vec![first];
+    for p in rest {
+        if basis.len() > D {
+            break;
+        }
+        basis.push(p);
+        if !affinely_independent(&basis) {
+            basis.pop();
+        }
+    }
+    basis.len() - 1
+}
@@ -0,0 +1,473 @@
+//! Planar arrangements of segments in 2d.
+//!
+//! The arrangement subdivides the plane along a set of segments into vertices, edges and faces, and stores them as a
+//! doubly-connected edge list. Intersections of segments are kept as [`ImplicitPoint2d`]s, such that all decisions,
+//! i.e. the order of the vertices along the segments, the identification of coinciding intersections and the order of
+//! the edges around the vertices, are made by exact predicates. The result is hence a valid planar subdivision, with
+//! no rounding errors in its combinatorics.
+//!
+//! Exact predicates require [`initialize`](crate::initialize) to be called first.
+
+use crate::implicit::{self, coordinate_cmp_2d, ImplicitPoint2d};
+use crate::{lexico_cmp_2d, orient_2d, points_are_identical_2d};
+use std::cmp::Ordering;
+
+/// A face of the arrangement, given by its boundary cycles.
+#[derive(Debug, Clone, Default)]
+struct Face {
+    /// A half-edge of the counter-clockwise outer boundary, `None` for the unbounded face.
+    outer: Option<usize>,
+    /// A half-edge of each clockwise inner boundary.
+    holes: Vec<usize>,
+}
+
+/// The planar arrangement of a set of segments in 2d, as a doubly-connected edge list.
+///
+/// The vertices are the endpoints of the segments and their intersections, sorted lexicographically. Each edge between
+/// two consecutive vertices along a segment is split into two opposite half-edges, the half-edges `2 * e` and
+/// `2 * e + 1` being twins. Each half-edge has its face on its left side: the outer boundaries of bounded faces are
+/// counter-clockwise, and their inner boundaries, i.e. the boundaries of the connected components they contain, are
+/// clockwise. The unbounded face [`Arrangement2d::UNBOUNDED`] has no outer boundary.
+///
+/// Overlapping segments share their common edges, and segments of zero length are ignored. Vertices that coincide with
+/// an endpoint of a segment keep its exact coordinates.
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::arrangement::Arrangement2d;
+///
+/// gp::initialize();
+///
+/// // A square with a diagonal, and a segment from its left side across the diagonal and the right side
+/// let arrangement = Arrangement2d::new(&[
+///     [[0.0, 0.0], [4.0, 0.0]],
+///     [[4.0, 0.0], [4.0, 4.0]],
+///     [[4.0, 4.0], [0.0, 4.0]],
+///     [[0.0, 4.0], [0.0, 0.0]],
+///     [[0.0, 0.0], [4.0, 4.0]],
+///     [[0.0, 3.0], [6.0, 0.0]],
+/// ]);
+/// assert_eq!(8, arrangement.nb_vertices());
+/// assert_eq!(11, arrangement.nb_half_edges() / 2);
+/// assert_eq!(5, arrangement.nb_faces());
+///
+/// let along: Vec<_> = arrangement.segment_vertices(5).iter().map(|&v| arrangement.point(v)).collect();
+/// assert_eq!(vec![[0.0, 3.0], [2.0, 2.0], [4.0, 1.0], [6.0, 0.0]], along);
+///
+/// // The bounded faces are the four pieces of the square, and the tail of the segment sticks out of it
+/// let outside = arrangement.holes(Arrangement2d::UNBOUNDED);
+/// assert_eq!(1, outside.len());
+/// assert_eq!(8, arrangement.cycle(outside[0]).len());
+/// for f in 1..arrangement.nb_faces() {
+///     assert!(arrangement.holes(f).is_empty());
+///     let h = arrangement.outer_boundary(f).unwrap();
+///     assert!(arrangement.cycle(h).iter().all(|&e| arrangement.face(e) == f));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Arrangement2d {
+    points: Vec<ImplicitPoint2d>,
+    /// The vertices along each segment, from its first endpoint to its second one.
+    segment_vertices: Vec<Vec<usize>>,
+    /// The segments that contain each edge, i.e. each pair of twin half-edges.
+    edge_segments: Vec<Vec<usize>>,
+    origins: Vec<usize>,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+    half_edge_faces: Vec<usize>,
+    /// The half-edges leaving each vertex, in counter-clockwise order starting below the negative x-axis.
+    outgoing: Vec<Vec<usize>>,
+    faces: Vec<Face>,
+}
+
+impl Arrangement2d {
+    /// The index of the unbounded face.
+    pub const UNBOUNDED: usize = 0;
+
+    /// Computes the arrangement of a set of segments.
+    ///
+    /// All pairs of segments are intersected, so this takes quadratic time in the number of segments. The predicates
+    /// are exact for coordinates in [`range::ORIENT_2D_IMPLICIT`](crate::range::ORIENT_2D_IMPLICIT).
+    ///
+    /// ### Parameters
+    /// - `segments` the segments, given by their endpoints
+    ///
+    /// ### Returns
+    /// - the arrangement of the segments
+    pub fn new(segments: &[[[f64; 2]; 2]]) -> Self {
+        let along = points_along(segments);
+
+        let mut points: Vec<ImplicitPoint2d> = along.iter().flatten().copied().collect();
+        sort_and_dedup(&mut points);
+        let index = |p: &ImplicitPoint2d| {
+            points
+                .binary_search_by(|q| implicit::lexico_cmp_2d(q, p))
+                .expect("all points along segments are vertices")
+        };
+        let segment_vertices: Vec<Vec<usize>> = along
+            .iter()
+            .map(|along| along.iter().map(index).collect())
+            .collect();
+
+        // Edges between consecutive vertices along the segments, merged where segments overlap.
+        let mut pieces: Vec<([usize; 2], usize)> = segment_vertices
+            .iter()
+            .enumerate()
+            .flat_map(|(s, vertices)| {
+                vertices
+                    .windows(2)
+                    .map(move |w| ([w[0].min(w[1]), w[0].max(w[1])], s))
+            })
+            .collect();
+        pieces.sort_unstable();
+        let mut origins = Vec::new();
+        let mut edge_segments: Vec<Vec<usize>> = Vec::new();
+        for (i, &(edge, s)) in pieces.iter().enumerate() {
+            if i > 0 && pieces[i - 1].0 == edge {
+                edge_segments.last_mut().unwrap().push(s);
+            } else {
+                origins.extend(edge);
+                edge_segments.push(vec![s]);
+            }
+        }
+
+        let mut arrangement = Arrangement2d {
+            points,
+            segment_vertices,
+            edge_segments,
+            origins,
+            next: Vec::new(),
+            prev: Vec::new(),
+            half_edge_faces: Vec::new(),
+            outgoing: Vec::new(),
+            faces: Vec::new(),
+        };
+        arrangement.link();
+        arrangement.build_faces();
+        arrangement
+    }
+
+    /// Gets the number of vertices, including the intersections of segments.
+    pub fn nb_vertices(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Gets the exact position of a vertex.
+    pub fn vertex(&self, v: usize) -> &ImplicitPoint2d {
+        &self.points[v]
+    }
+
+    /// Gets the coordinates of a vertex, rounded with [`ImplicitPoint2d::approximate`].
+    pub fn point(&self, v: usize) -> [f64; 2] {
+        self.points[v].approximate()
+    }
+
+    /// Gets the vertices along a segment, in order from its first endpoint to its second one.
+    ///
+    /// The list is empty if the segment has zero length.
+    pub fn segment_vertices(&self, s: usize) -> &[usize] {
+        &self.segment_vertices[s]
+    }
+
+    /// Gets the half-edges leaving a vertex, in counter-clockwise order.
+    pub fn outgoing(&self, v: usize) -> &[usize] {
+        &self.outgoing[v]
+    }
+
+    /// Gets the number of half-edges, which is twice the number of edges.
+    pub fn nb_half_edges(&self) -> usize {
+        self.origins.len()
+    }
+
+    /// Gets the vertex a half-edge starts from.
+    pub fn origin(&self, h: usize) -> usize {
+        self.origins[h]
+    }
+
+    /// Gets the vertex a half-edge ends at.
+    pub fn destination(&self, h: usize) -> usize {
+        self.origins[self.twin(h)]
+    }
+
+    /// Gets the opposite half-edge, with the same edge and the face on the other side.
+    pub fn twin(&self, h: usize) -> usize {
+        h ^ 1
+    }
+
+    /// Gets the half-edge that follows a half-edge along the boundary of its face.
+    pub fn next(&self, h: usize) -> usize {
+        self.next[h]
+    }
+
+    /// Gets the half-edge that precedes a half-edge along the boundary of its face.
+    pub fn prev(&self, h: usize) -> usize {
+        self.prev[h]
+    }
+
+    /// Gets the face on the left side of a half-edge.
+    pub fn face(&self, h: usize) -> usize {
+        self.half_edge_faces[h]
+    }
+
+    /// Gets the segments that contain the edge of a half-edge, in increasing order.
+    ///
+    /// There are several of them where segments overlap.
+    pub fn segments(&self, h: usize) -> &[usize] {
+        &self.edge_segments[h / 2]
+    }
+
+    /// Gets the half-edges of the boundary cycle of a half-edge, following [`Arrangement2d::next`] from it.
+    pub fn cycle(&self, h: usize) -> Vec<usize> {
+        let mut cycle = vec![h];
+        let mut e = self.next[h];
+        while e != h {
+            cycle.push(e);
+            e = self.next[e];
+        }
+        cycle
+    }
+
+    /// Gets the number of faces, including the unbounded one.
+    pub fn nb_faces(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Gets a half-edge of the counter-clockwise outer boundary of a face, or `None` for the unbounded face.
+    pub fn outer_boundary(&self, f: usize) -> Option<usize> {
+        self.faces[f].outer
+    }
+
+    /// Gets a half-edge of each clockwise inner boundary of a face.
+    ///
+    /// The inner boundaries are the outer sides of the connected components of the arrangement inside the face.
+    pub fn holes(&self, f: usize) -> &[usize] {
+        &self.faces[f].holes
+    }
+
+    /// Sorts the half-edges around the vertices, and links each half-edge to the next one around its face.
+    fn link(&mut self) {
+        self.outgoing = vec![Vec::new(); self.points.len()];
+        for (h, &v) in self.origins.iter().enumerate() {
+            self.outgoing[v].push(h);
+        }
+        let mut outgoing = std::mem::take(&mut self.outgoing);
+        for (v, around) in outgoing.iter_mut().enumerate() {
+            around.sort_by(|&g, &h| self.counter_clockwise_cmp(v, g, h));
+        }
+        self.outgoing = outgoing;
+
+        // The face on the left of `h` continues with the half-edge clockwise from its twin around its destination.
+        let n = self.nb_half_edges();
+        self.next = vec![0; n];
+        self.prev = vec![0; n];
+        for around in &self.outgoing {
+            for (i, &h) in around.iter().enumerate() {
+                let e = self.twin(h);
+                let next = around[(i + around.len() - 1) % around.len()];
+                self.next[e] = next;
+                self.prev[next] = e;
+            }
+        }
+    }
+
+    /// Compares the directions of two half-edges leaving `v` by angle, counter-clockwise from just below the negative
+    /// x-axis.
+    fn counter_clockwise_cmp(&self, v: usize, g: usize, h: usize) -> Ordering {
+        // The directions in `(-pi, 0]` come first, the ones in `(0, pi]` second, and each half spans less than `pi`.
+        let half = |h: usize| {
+            let w = &self.points[self.destination(h)];
+            match coordinate_cmp_2d(w, &self.points[v], 1) {
+                Ordering::Less => 0,
+                Ordering::Equal => usize::from(coordinate_cmp_2d(w, &self.points[v], 0).is_lt()),
+                Ordering::Greater => 1,
+            }
+        };
+        half(g).cmp(&half(h)).then_with(|| {
+            let [p, q, r] = [v, self.destination(g), self.destination(h)].map(|v| &self.points[v]);
+            0.cmp(&implicit::orient_2d(p, q, r))
+        })
+    }
+
+    /// Finds the boundary cycles, creates a face for each counter-clockwise one, and assigns each clockwise one to the
+    /// face that contains it as a hole.
+    fn build_faces(&mut self) {
+        let n = self.nb_half_edges();
+        let mut visited = vec![false; n];
+        let mut cycles = Vec::new();
+        for h in 0..n {
+            if !visited[h] {
+                for e in self.cycle(h) {
+                    visited[e] = true;
+                }
+                cycles.push(h);
+            }
+        }
+        let component = self.components();
+
+        let (holes, outers): (Vec<usize>, Vec<usize>) =
+            cycles.into_iter().partition(|&h| self.is_hole(h));
+        let mut faces = vec![Face::default()];
+        faces.extend(outers.iter().map(|&h| Face {
+            outer: Some(h),
+            holes: Vec::new(),
+        }));
+        for hole in holes {
+            // The innermost enclosing boundary has the greatest lowest vertex, since enclosed regions are nested.
+            let v = self.lowest_vertex(hole);
+            let face = outers
+                .iter()
+                .enumerate()
+                .filter(|&(_, &h)| component[self.origins[h]] != component[v])
+                .filter(|&(_, &h)| self.winding_number(h, v) != 0)
+                .max_by_key(|&(_, &h)| self.lowest_vertex(h))
+                .map_or(Self::UNBOUNDED, |(f, _)| f + 1);
+            faces[face].holes.push(hole);
+        }
+
+        self.half_edge_faces = vec![Self::UNBOUNDED; n];
+        for (f, face) in faces.iter().enumerate() {
+            for &h in face.outer.iter().chain(&face.holes) {
+                for e in self.cycle(h) {
+                    self.half_edge_faces[e] = f;
+                }
+            }
+        }
+        self.faces = faces;
+    }
+
+    /// Gets the lexicographically smallest vertex of the cycle of `h`.
+    fn lowest_vertex(&self, h: usize) -> usize {
+        self.cycle(h)
+            .into_iter()
+            .map(|e| self.origins[e])
+            .min()
+            .unwrap()
+    }
+
+    /// Tests whether the cycle of `h` bounds its face from the outside, i.e. is a hole of its face or the boundary of
+    /// the unbounded face.
+    ///
+    /// This is the case iff the face extends beyond the lowest vertex of the cycle, i.e. iff the cycle uses the angle
+    /// around this vertex that contains the directions just below the negative x-axis. This angle is between the last
+    /// and the first outgoing half-edges.
+    fn is_hole(&self, h: usize) -> bool {
+        let v = self.lowest_vertex(h);
+        self.cycle(h)
+            .into_iter()
+            .any(|e| self.destination(e) == v && self.twin(e) == self.outgoing[v][0])
+    }
+
+    /// Labels the vertices with the index of their connected component.
+    fn components(&self) -> Vec<usize> {
+        let mut component = vec![usize::MAX; self.points.len()];
+        for start in 0..self.points.len() {
+            if component[start] != usize::MAX {
+                continue;
+            }
+            component[start] = start;
+            let mut stack = vec![start];
+            while let Some(v) = stack.pop() {
+                for &h in &self.outgoing[v] {
+                    let w = self.destination(h);
+                    if component[w] == usize::MAX {
+                        component[w] = start;
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+        component
+    }
+
+    /// Computes the winding number of the cycle of `h` around the vertex `v`, which must not be on the cycle.
+    fn winding_number(&self, h: usize, v: usize) -> i32 {
+        let p = &self.points[v];
+        let mut winding = 0;
+        for e in self.cycle(h) {
+            let [a, b] = [self.origin(e), self.destination(e)].map(|v| &self.points[v]);
+            if coordinate_cmp_2d(a, p, 1).is_le() {
+                if coordinate_cmp_2d(b, p, 1).is_gt() && implicit::orient_2d(a, b, p) > 0 {
+                    winding += 1;
+                }
+            } else if coordinate_cmp_2d(b, p, 1).is_le() && implicit::orient_2d(a, b, p) < 0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+}
+
+/// Computes the points on each segment where it meets the other ones, including its endpoints, in order from its first
+/// endpoint to its second one.
+fn points_along(segments: &[[[f64; 2]; 2]]) -> Vec<Vec<ImplicitPoint2d>> {
+    let mut along: Vec<Vec<ImplicitPoint2d>> = segments
+        .iter()
+        .map(|[u, v]| {
+            if points_are_identical_2d(u, v) {
+                Vec::new()
+            } else {
+                vec![(*u).into(), (*v).into()]
+            }
+        })
+        .collect();
+    for i in 0..segments.len() {
+        for j in i + 1..segments.len() {
+            if along[i].is_empty() || along[j].is_empty() {
+                continue;
+            }
+            let ([u, v], [a, b]) = (&segments[i], &segments[j]);
+            let [o_a, o_b] = [a, b].map(|p| orient_2d(u, v, p));
+            let [o_u, o_v] = [u, v].map(|p| orient_2d(a, b, p));
+            if o_a == 0 && o_b == 0 {
+                // The segments are collinear, and each one is split at the endpoints of the other one.
+                for p in [a, b].into_iter().filter(|p| between(u, p, v)) {
+                    along[i].push((*p).into());
+                }
+                for p in [u, v].into_iter().filter(|p| between(a, p, b)) {
+                    along[j].push((*p).into());
+                }
+            } else if o_a * o_b <= 0 && o_u * o_v <= 0 {
+                // The lines meet in a single point, which is an endpoint if one of the orientations is zero.
+                if o_a == 0 {
+                    along[i].push((*a).into());
+                } else if o_b == 0 {
+                    along[i].push((*b).into());
+                } else if o_u == 0 {
+                    along[j].push((*u).into());
+                } else if o_v == 0 {
+                    along[j].push((*v).into());
+                } else {
+                    let x = ImplicitPoint2d::segment_intersection(u, v, a, b)
+                        .expect("crossing segments are not parallel");
+                    along[i].push(x);
+                    along[j].push(x);
+                }
+            }
+        }
+    }
+    for (points, [u, v]) in along.iter_mut().zip(segments) {
+        sort_and_dedup(points);
+        if lexico_cmp_2d(u, v).is_gt() {
+            points.reverse();
+        }
+    }
+    along
+}
+
+/// Sorts points lexicographically and removes duplicates, keeping explicit points over identical implicit ones.
+fn sort_and_dedup(points: &mut Vec<ImplicitPoint2d>) {
+    points.sort_by(|p, q| {
+        implicit::lexico_cmp_2d(p, q).then_with(|| q.is_explicit().cmp(&p.is_explicit()))
+    });
+    points.dedup_by(|p, q| implicit::lexico_cmp_2d(p, q) == Ordering::Equal);
+}
+
+/// Tests whether `p` is between `u` and `v`, which are collinear with it.
+fn between(u: &[f64; 2], p: &[f64; 2], v: &[f64; 2]) -> bool {
+    let (low, high) = match lexico_cmp_2d(u, v) {
+        Ordering::Greater => (v, u),
+        _ => (u, v),
+    };
+    lexico_cmp_2d(low, p).is_le() && lexico_cmp_2d(p, high).is_le()
+}
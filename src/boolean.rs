@@ -0,0 +1,905 @@
+//! Boolean operations on polygons with holes.
+//!
+//! The operands are overlaid in an [`Arrangement2d`], so that their edges are split at exact intersection points, and
+//! each face of the arrangement is classified as inside or outside of each operand by counting the edges crossed from
+//! the unbounded face. The result is the boundary between the faces selected by the operation. As all decisions are
+//! taken by exact predicates, the result is always a valid polygon: its boundaries do not cross each other, and they
+//! only touch at vertices.
+//!
+//! Boolean operations on solids bounded by triangle meshes work the same way in 3d: the faces that meet are split along
+//! their exact intersection, and the pieces are classified as inside or outside of the other solid, see
+//! [`mesh_boolean`].
+//!
+//! Exact predicates require [`initialize`](crate::initialize) to be called first.
+
+use crate::arrangement::Arrangement2d;
+use crate::bvh::{bounding_box, Bvh};
+use crate::implicit::{self, Construction3d, ImplicitPoint2d, ImplicitPoint3d};
+use crate::intersection::{on_one_side, orient, projection_axis};
+use crate::remesh;
+use crate::{lexico_cmp_3d, orient_2d, points_are_colinear_3d, Sign};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A boolean operation on two regions `a` and `b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    /// The points in `a` or in `b`.
+    Union,
+    /// The points in `a` and in `b`.
+    Intersection,
+    /// The points in `a` and not in `b`.
+    Difference,
+    /// The points in exactly one of `a` and `b`.
+    Xor,
+}
+
+impl BooleanOp {
+    /// Tests whether a point is in the result, given whether it is in `a` and in `b`.
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            BooleanOp::Union => a || b,
+            BooleanOp::Intersection => a && b,
+            BooleanOp::Difference => a && !b,
+            BooleanOp::Xor => a != b,
+        }
+    }
+}
+
+/// A connected polygon with holes, whose vertices may be intersections of the input edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolygonWithHoles {
+    outer: Vec<ImplicitPoint2d>,
+    holes: Vec<Vec<ImplicitPoint2d>>,
+}
+
+impl PolygonWithHoles {
+    /// Gets the vertices of the outer boundary, in counter-clockwise order.
+    pub fn outer(&self) -> &[ImplicitPoint2d] {
+        &self.outer
+    }
+
+    /// Gets the vertices of each hole, in clockwise order.
+    pub fn holes(&self) -> &[Vec<ImplicitPoint2d>] {
+        &self.holes
+    }
+
+    /// Rounds the boundaries to `f64` coordinates, with [`ImplicitPoint2d::approximate`].
+    ///
+    /// ### Returns
+    /// - the outer boundary, followed by the holes, in the format of the operands of [`polygon_boolean`]
+    pub fn approximate(&self) -> Vec<Vec<[f64; 2]>> {
+        std::iter::once(&self.outer)
+            .chain(&self.holes)
+            .map(|ring| ring.iter().map(ImplicitPoint2d::approximate).collect())
+            .collect()
+    }
+}
+
+/// Computes a boolean operation on two polygons with holes.
+///
+/// Each operand is a set of closed rings, where edge `i` of a ring joins vertex `i` and vertex `(i + 1) % n`. The
+/// region of an operand is defined by the even-odd rule: a point is inside, if a ray from it crosses the rings an odd
+/// number of times. Hence the orientation of the rings does not matter, and the rings may intersect each other or
+/// themselves. The result is exact for coordinates in
+/// [`range::ORIENT_2D_IMPLICIT`](crate::range::ORIENT_2D_IMPLICIT).
+///
+/// ### Parameters
+/// - `a`, `b` the rings of the operands
+/// - `op` the operation
+///
+/// ### Returns
+/// - the connected components of the result, which are polygons whose boundaries only touch at vertices, without
+///   vertices in the middle of straight edges. A ring may touch itself at vertices: a hole that touches the outer
+///   boundary or another hole at a vertex is not a separate ring, but merged with it into one weakly simple ring
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::boolean::BooleanOp;
+///
+/// gp::initialize();
+///
+/// let a = vec![vec![[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]];
+/// let b = vec![vec![[2.0, 2.0], [6.0, 2.0], [6.0, 6.0], [2.0, 6.0]]];
+///
+/// let union = gp::boolean::polygon_boolean(&a, &b, BooleanOp::Union);
+/// assert_eq!(1, union.len());
+/// assert_eq!(8, union[0].outer().len());
+///
+/// let intersection = gp::boolean::polygon_boolean(&a, &b, BooleanOp::Intersection);
+/// assert_eq!(vec![vec![[2.0, 2.0], [4.0, 2.0], [4.0, 4.0], [2.0, 4.0]]], intersection[0].approximate());
+///
+/// // A square with a square hole, minus a bar through it, leaves two U shapes
+/// let frame = vec![a[0].clone(), vec![[1.0, 1.0], [3.0, 1.0], [3.0, 3.0], [1.0, 3.0]]];
+/// let bar = vec![vec![[-1.0, 1.5], [5.0, 1.5], [5.0, 2.5], [-1.0, 2.5]]];
+/// let difference = gp::boolean::polygon_boolean(&frame, &bar, BooleanOp::Difference);
+/// assert_eq!(2, difference.len());
+/// assert!(difference.iter().all(|polygon| polygon.outer().len() == 8 && polygon.holes().is_empty()));
+/// ```
+pub fn polygon_boolean(
+    a: &[Vec<[f64; 2]>],
+    b: &[Vec<[f64; 2]>],
+    op: BooleanOp,
+) -> Vec<PolygonWithHoles> {
+    let edges = |rings: &[Vec<[f64; 2]>]| -> Vec<[[f64; 2]; 2]> {
+        rings
+            .iter()
+            .flat_map(|ring| (0..ring.len()).map(|i| [ring[i], ring[(i + 1) % ring.len()]]))
+            .collect()
+    };
+    let mut segments = edges(a);
+    let nb_a = segments.len();
+    segments.extend(edges(b));
+    let arrangement = Arrangement2d::new(&segments);
+
+    // Crossing an edge flips the inclusion in an operand, iff the edge is on an odd number of its rings.
+    let crossing = |h: usize| {
+        let from_a = arrangement
+            .segments(h)
+            .iter()
+            .filter(|&&s| s < nb_a)
+            .count();
+        let from_b = arrangement.segments(h).len() - from_a;
+        [from_a % 2 == 1, from_b % 2 == 1]
+    };
+    let mut inside: Vec<Option<[bool; 2]>> = vec![None; arrangement.nb_faces()];
+    inside[Arrangement2d::UNBOUNDED] = Some([false, false]);
+    let mut queue = VecDeque::from([Arrangement2d::UNBOUNDED]);
+    let boundaries = |f: usize| {
+        let face_boundaries = arrangement.outer_boundary(f).into_iter();
+        face_boundaries.chain(arrangement.holes(f).iter().copied())
+    };
+    while let Some(f) = queue.pop_front() {
+        let [in_a, in_b] = inside[f].unwrap();
+        for h in boundaries(f).flat_map(|h| arrangement.cycle(h)) {
+            let g = arrangement.face(arrangement.twin(h));
+            if inside[g].is_none() {
+                let [flip_a, flip_b] = crossing(h);
+                inside[g] = Some([in_a != flip_a, in_b != flip_b]);
+                queue.push_back(g);
+            }
+        }
+    }
+    let selected: Vec<bool> = inside
+        .iter()
+        .map(|inside| inside.is_some_and(|[in_a, in_b]| op.apply(in_a, in_b)))
+        .collect();
+    let in_result = |h: usize| selected[arrangement.face(h)];
+    let on_boundary = |h: usize| in_result(h) && !in_result(arrangement.twin(h));
+
+    // The connected components of the result, through the edges between selected faces.
+    let mut component = vec![usize::MAX; arrangement.nb_faces()];
+    let mut nb_components = 0;
+    for start in (0..arrangement.nb_faces()).filter(|&f| selected[f]) {
+        if component[start] != usize::MAX {
+            continue;
+        }
+        component[start] = nb_components;
+        let mut stack = vec![start];
+        while let Some(f) = stack.pop() {
+            for h in boundaries(f).flat_map(|h| arrangement.cycle(h)) {
+                let g = arrangement.face(arrangement.twin(h));
+                if selected[g] && component[g] == usize::MAX {
+                    component[g] = nb_components;
+                    stack.push(g);
+                }
+            }
+        }
+        nb_components += 1;
+    }
+
+    // Follows the boundary of the result, turning around the vertices through selected faces, such that a ring stays
+    // in one wedge of the result at a vertex where several wedges meet. The boundaries of different components are
+    // not merged, but a hole that touches the outer boundary or another hole at a vertex is part of the same ring,
+    // which is weakly simple and touches itself there.
+    let mut polygons: Vec<PolygonWithHoles> = Vec::new();
+    let mut holes: Vec<Vec<Vec<ImplicitPoint2d>>> = vec![Vec::new(); nb_components];
+    let mut outers: Vec<Option<usize>> = vec![None; nb_components];
+    let mut visited = vec![false; arrangement.nb_half_edges()];
+    for start in 0..arrangement.nb_half_edges() {
+        if visited[start] || !on_boundary(start) {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut h = start;
+        loop {
+            visited[h] = true;
+            ring.push(arrangement.origin(h));
+            h = arrangement.next(h);
+            while !on_boundary(h) {
+                h = arrangement.next(arrangement.twin(h));
+            }
+            if h == start {
+                break;
+            }
+        }
+        let ring = simplify(&arrangement, &ring);
+        let c = component[arrangement.face(start)];
+        if is_counter_clockwise(&ring) {
+            outers[c] = Some(polygons.len());
+            polygons.push(PolygonWithHoles {
+                outer: ring,
+                holes: Vec::new(),
+            });
+        } else {
+            holes[c].push(ring);
+        }
+    }
+    for (c, holes) in holes.into_iter().enumerate() {
+        let p = outers[c].expect("each component of the result has an outer boundary");
+        polygons[p].holes = holes;
+    }
+    polygons
+}
+
+/// Gets the points of a ring of vertices, without the ones in the middle of straight edges, starting from the lowest
+/// one.
+fn simplify(arrangement: &Arrangement2d, ring: &[usize]) -> Vec<ImplicitPoint2d> {
+    let n = ring.len();
+    // The vertices of the arrangement are sorted lexicographically.
+    let lowest = (0..n).min_by_key(|&i| ring[i]).unwrap();
+    (lowest..lowest + n)
+        .filter(|&i| {
+            // Consecutive edges of a boundary never overlap, so collinear edges continue straight.
+            let [p, q, r] = [i + n - 1, i, i + 1].map(|j| arrangement.vertex(ring[j % n]));
+            implicit::orient_2d(p, q, r) != 0
+        })
+        .map(|i| *arrangement.vertex(ring[i % n]))
+        .collect()
+}
+
+/// Tests whether a ring that does not cross itself is counter-clockwise, from the turn at its lowest vertex.
+fn is_counter_clockwise(ring: &[ImplicitPoint2d]) -> bool {
+    let n = ring.len();
+    let lowest = (0..n)
+        .min_by(|&i, &j| implicit::lexico_cmp_2d(&ring[i], &ring[j]))
+        .unwrap();
+    let [p, q, r] = [lowest + n - 1, lowest, lowest + 1].map(|j| &ring[j % n]);
+    implicit::orient_2d(p, q, r) > 0
+}
+
+/// A triangle mesh, given by its vertices and its faces.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TriangleMesh {
+    /// The vertices.
+    pub points: Vec<[f64; 3]>,
+    /// The faces, as triples of vertex indices.
+    pub triangles: Vec<[usize; 3]>,
+}
+
+/// A triangle mesh computed by [`mesh_boolean`], whose vertices may be intersections of the input faces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BooleanMesh {
+    vertices: Vec<ImplicitPoint3d>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl BooleanMesh {
+    /// Gets the exact vertices.
+    pub fn vertices(&self) -> &[ImplicitPoint3d] {
+        &self.vertices
+    }
+
+    /// Gets the faces, as triples of vertex indices, oriented outwards.
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Rounds the vertices to `f64` coordinates, with [`ImplicitPoint3d::approximate`].
+    pub fn approximate(&self) -> TriangleMesh {
+        TriangleMesh {
+            points: self
+                .vertices
+                .iter()
+                .map(ImplicitPoint3d::approximate)
+                .collect(),
+            triangles: self.triangles.clone(),
+        }
+    }
+}
+
+/// A non-degenerate face of one of the operands of [`mesh_boolean`].
+struct Face {
+    /// `0` for the first operand, `1` for the second one.
+    mesh: usize,
+    corners: [[f64; 3]; 3],
+    /// The indices of the corners in the vertices of the result.
+    vertices: [usize; 3],
+    axis: usize,
+}
+
+/// Where a piece of a face is w.r.t. the other operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Location {
+    Outside,
+    Inside,
+    /// On a face of the other operand, with the same orientation or not.
+    On {
+        same: bool,
+    },
+}
+
+/// Computes a boolean operation on two solids bounded by triangle meshes.
+///
+/// The operands must be closed and oriented outwards, and their faces must not intersect each other, apart from
+/// neighbors sharing a vertex or an edge, see
+/// [`mesh_self_intersections`](crate::intersection::mesh_self_intersections). Faces of one operand may touch or cross
+/// the faces of the other one in arbitrary ways, including coplanar overlaps.
+///
+/// The faces that meet are split along their intersection, whose vertices are kept as [`ImplicitPoint3d`]s, by an
+/// exact constrained triangulation of each face. The pieces are classified as inside or outside of the other operand
+/// with [`orient_3d`](crate::orient_3d) at the intersection curve, where the other operand is locally a plane or a
+/// dihedral wedge. Pieces that do not touch the curve in such a way are classified by counting the crossings of a
+/// segment from the interior of a piece to the outside, whose endpoints are perturbed symbolically so that it never
+/// meets an edge of the other operand. Pieces on a face of the other operand are kept once if the operation needs them.
+/// Hence the result is watertight: each edge is shared by faces on both sides, with opposite orientations. It is exact
+/// for coordinates in [`range::ORIENT_3D_IMPLICIT`](crate::range::ORIENT_3D_IMPLICIT).
+///
+/// ### Parameters
+/// - `a`, `b` the boundaries of the operands
+/// - `op` the operation
+///
+/// ### Returns
+/// - the boundary of the result, whose vertices are the vertices of the operands that it uses and the intersections
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::boolean::{BooleanOp, TriangleMesh};
+///
+/// gp::initialize();
+///
+/// // A tetrahedron, and a copy that is shifted such that one vertex pokes through a face
+/// let points = vec![[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]];
+/// let triangles = vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+/// let a = TriangleMesh { points: points.clone(), triangles: triangles.clone() };
+/// let shifted = points.iter().map(|p| [p[0] + 1.0, p[1] + 1.0, p[2] + 1.0]).collect();
+/// let b = TriangleMesh { points: shifted, triangles };
+///
+/// // The intersection is a tetrahedron with a corner at `[1, 1, 1]`, whose other corners are on the faces of `b`
+/// let intersection = gp::boolean::mesh_boolean(&a, &b, BooleanOp::Intersection);
+/// assert_eq!(4, intersection.vertices().len());
+/// assert_eq!(4, intersection.triangles().len());
+/// let mut points = intersection.approximate().points;
+/// points.sort_by(|p, q| gp::lexico_cmp_3d(p, q));
+/// assert_eq!(vec![[1.0, 1.0, 1.0], [1.0, 1.0, 2.0], [1.0, 2.0, 1.0], [2.0, 1.0, 1.0]], points);
+///
+/// // The union has the six vertices of `a` and `b` outside of each other, and the three crossings of their edges
+/// let union = gp::boolean::mesh_boolean(&a, &b, BooleanOp::Union);
+/// assert_eq!(4 + 3 + 3, union.vertices().len());
+/// ```
+pub fn mesh_boolean(a: &TriangleMesh, b: &TriangleMesh, op: BooleanOp) -> BooleanMesh {
+    let explicit: Vec<ImplicitPoint3d> = a
+        .points
+        .iter()
+        .chain(&b.points)
+        .map(|&p| p.into())
+        .collect();
+    let mut faces: Vec<Face> = Vec::new();
+    for (mesh, operand) in [a, b].into_iter().enumerate() {
+        for t in &operand.triangles {
+            let corners = t.map(|v| operand.points[v]);
+            if !points_are_colinear_3d(&corners[0], &corners[1], &corners[2]) {
+                faces.push(Face {
+                    mesh,
+                    corners,
+                    vertices: [0; 3],
+                    axis: projection_axis(&corners),
+                });
+            }
+        }
+    }
+
+    // The points and segments where the faces meet the faces of the other operand.
+    let mut on_face: Vec<Vec<ImplicitPoint3d>> = faces
+        .iter()
+        .map(|f| f.corners.map(ImplicitPoint3d::from).to_vec())
+        .collect();
+    let mut traces: Vec<Vec<[ImplicitPoint3d; 2]>> = vec![Vec::new(); faces.len()];
+    let mut partners: Vec<Vec<usize>> = vec![Vec::new(); faces.len()];
+    let mut coplanar: Vec<Vec<usize>> = vec![Vec::new(); faces.len()];
+    let boxes = faces
+        .iter()
+        .map(|f| bounding_box(f.corners.iter()))
+        .collect();
+    Bvh::new(boxes).self_overlaps(|i, j| {
+        if faces[i].mesh == faces[j].mesh {
+            return;
+        }
+        let Some(contact) = contact(&faces[i], &faces[j]) else {
+            return;
+        };
+        for (f, g, segments) in [(i, j, contact.on_first), (j, i, contact.on_second)] {
+            partners[f].push(g);
+            if contact.coplanar {
+                coplanar[f].push(g);
+            }
+            for [p, q] in segments {
+                on_face[f].extend([p, q]);
+                traces[f].push([p, q]);
+            }
+        }
+    });
+
+    // Most intersections are found once per contact and per face. The copies are merged by their construction, so that
+    // only points that coincide with a different construction are compared exactly for equality.
+    let mut constructions: HashMap<Construction3d, usize> = HashMap::new();
+    let mut distinct: Vec<ImplicitPoint3d> = Vec::new();
+    for p in explicit.iter().chain(on_face.iter().flatten()) {
+        constructions.entry(p.construction()).or_insert_with(|| {
+            distinct.push(*p);
+            distinct.len() - 1
+        });
+    }
+    let mut order: Vec<usize> = (0..distinct.len()).collect();
+    order.sort_by(|&i, &j| cmp_explicit_first(&distinct[i], &distinct[j]));
+    let mut vertices: Vec<ImplicitPoint3d> = Vec::new();
+    let mut slots = vec![0; distinct.len()];
+    for i in order {
+        let p = &distinct[i];
+        if vertices
+            .last()
+            .is_none_or(|q| implicit::lexico_cmp_3d(q, p) != Ordering::Equal)
+        {
+            vertices.push(*p);
+        }
+        slots[i] = vertices.len() - 1;
+    }
+    let index = |p: &ImplicitPoint3d| slots[constructions[&p.construction()]];
+    for f in &mut faces {
+        f.vertices = f.corners.map(|p| index(&p.into()));
+    }
+    let mut on_face: Vec<Vec<usize>> = on_face
+        .iter()
+        .map(|points| {
+            let mut points: Vec<usize> = points.iter().map(index).collect();
+            points.sort_unstable();
+            points.dedup();
+            points
+        })
+        .collect();
+
+    // Neighbors must split their common edge at the same points, even if only one of them meets the other operand
+    // there.
+    let mut on_edges: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+    for (f, face) in faces.iter().enumerate() {
+        for &p in &on_face[f] {
+            for i in 0..3 {
+                let [u, v] = [face.vertices[i], face.vertices[(i + 1) % 3]];
+                if p != u
+                    && p != v
+                    && implicit::orient_2d_projected(
+                        &vertices[u],
+                        &vertices[v],
+                        &vertices[p],
+                        face.axis,
+                    ) == 0
+                {
+                    on_edges.entry([u.min(v), u.max(v)]).or_default().push(p);
+                }
+            }
+        }
+    }
+    for (f, face) in faces.iter().enumerate() {
+        for i in 0..3 {
+            let [u, v] = [face.vertices[i], face.vertices[(i + 1) % 3]];
+            if let Some(points) = on_edges.get(&[u.min(v), u.max(v)]) {
+                on_face[f].extend(points);
+            }
+        }
+        on_face[f].sort_unstable();
+        on_face[f].dedup();
+    }
+
+    // Splits the faces into pieces along the traces.
+    let mut pieces: Vec<(usize, [usize; 3])> = Vec::new();
+    let mut curve: HashSet<[usize; 2]> = HashSet::new();
+    for (f, face) in faces.iter().enumerate() {
+        let mut local: Vec<usize> = face.vertices.to_vec();
+        local.extend(on_face[f].iter().filter(|p| !face.vertices.contains(p)));
+        if local.len() == 3 && traces[f].is_empty() {
+            pieces.push((f, face.vertices));
+            continue;
+        }
+        let position = |p: usize| local.iter().position(|&q| q == p).unwrap();
+        let edges: Vec<[usize; 2]> = traces[f]
+            .iter()
+            .map(|[p, q]| [index(p), index(q)])
+            .filter(|[p, q]| p != q)
+            .map(|[p, q]| [position(p), position(q)])
+            .collect();
+        let points: Vec<ImplicitPoint3d> = local.iter().map(|&p| vertices[p]).collect();
+        let (triangles, constrained) = remesh::triangulate(&points, face.axis, &edges);
+        pieces.extend(triangles.into_iter().map(|t| (f, t.map(|i| local[i]))));
+        curve.extend(constrained.into_iter().map(|[p, q]| {
+            let [p, q] = [local[p], local[q]];
+            [p.min(q), p.max(q)]
+        }));
+    }
+
+    let locations = classify(&faces, &vertices, &pieces, &curve, &partners, &coplanar);
+    let mut triangles = Vec::new();
+    for (&(f, t), &location) in pieces.iter().zip(&locations) {
+        let keep = match (faces[f].mesh, location, op) {
+            (_, Location::Outside, BooleanOp::Union | BooleanOp::Xor) => Some(false),
+            (0, Location::Outside, BooleanOp::Difference) => Some(false),
+            (_, Location::Inside, BooleanOp::Intersection) => Some(false),
+            (_, Location::Inside, BooleanOp::Xor) => Some(true),
+            (1, Location::Inside, BooleanOp::Difference) => Some(true),
+            (0, Location::On { same: true }, BooleanOp::Union | BooleanOp::Intersection) => {
+                Some(false)
+            }
+            (0, Location::On { same: false }, BooleanOp::Difference) => Some(false),
+            _ => None,
+        };
+        if let Some(flip) = keep {
+            triangles.push(if flip { [t[0], t[2], t[1]] } else { t });
+        }
+    }
+
+    // Drops the vertices that are not used.
+    let mut used = vec![usize::MAX; vertices.len()];
+    let mut kept = Vec::new();
+    for v in triangles.iter_mut().flatten() {
+        if used[*v] == usize::MAX {
+            used[*v] = kept.len();
+            kept.push(vertices[*v]);
+        }
+        *v = used[*v];
+    }
+    BooleanMesh {
+        vertices: kept,
+        triangles,
+    }
+}
+
+/// The intersection of two faces of different operands.
+struct Contact {
+    /// The segments, or points, where the second face meets the first one.
+    on_first: Vec<[ImplicitPoint3d; 2]>,
+    /// The segments, or points, where the first face meets the second one.
+    on_second: Vec<[ImplicitPoint3d; 2]>,
+    coplanar: bool,
+}
+
+/// Computes the intersection of two non-degenerate faces.
+fn contact(p: &Face, q: &Face) -> Option<Contact> {
+    let [a, b, c] = &q.corners;
+    let sides_p = p.corners.each_ref().map(|v| orient(a, b, c, v));
+    let [a, b, c] = &p.corners;
+    let sides_q = q.corners.each_ref().map(|v| orient(a, b, c, v));
+    if on_one_side(&sides_p) || on_one_side(&sides_q) {
+        return None;
+    }
+    if sides_p == [0; 3] {
+        // Each face is split along the edges of the other one.
+        let clip = |p: &Face, q: &Face| -> Vec<[ImplicitPoint3d; 2]> {
+            (0..3)
+                .filter_map(|i| clip_coplanar(&q.corners[i], &q.corners[(i + 1) % 3], p))
+                .collect()
+        };
+        let (on_first, on_second) = (clip(p, q), clip(q, p));
+        if on_first.is_empty() && on_second.is_empty() {
+            return None;
+        }
+        return Some(Contact {
+            on_first,
+            on_second,
+            coplanar: true,
+        });
+    }
+
+    // The faces meet in a segment on the line where their planes intersect, whose endpoints are on their edges.
+    let mut points = edge_points(p, &sides_p, q);
+    points.extend(edge_points(q, &sides_q, p));
+    sort_and_dedup_3d(&mut points);
+    let segment = [*points.first()?, *points.last()?];
+    Some(Contact {
+        on_first: vec![segment],
+        on_second: vec![segment],
+        coplanar: false,
+    })
+}
+
+/// Computes the points where the edges of `p` meet `q`, given the sides of the corners of `p` w.r.t. the plane of
+/// `q`, which are not all zero.
+fn edge_points(p: &Face, sides: &[i16; 3], q: &Face) -> Vec<ImplicitPoint3d> {
+    let mut points = Vec::new();
+    for i in 0..3 {
+        let (u, v) = (&p.corners[i], &p.corners[(i + 1) % 3]);
+        if sides[i] == 0 && in_triangle(u, q) {
+            points.push((*u).into());
+        }
+        if sides[i] * sides[(i + 1) % 3] < 0 {
+            let crossings = [0, 1, 2].map(|k| orient(u, v, &q.corners[k], &q.corners[(k + 1) % 3]));
+            if crossings.contains(&1) && crossings.contains(&-1) {
+                continue;
+            }
+            // The edge passes through a corner of `q` if it is on the lines through two of its edges.
+            match crossings.iter().filter(|&&s| s == 0).count() {
+                0 | 1 => points.extend(ImplicitPoint3d::segment_triangle_intersection(
+                    u, v, &q.corners,
+                )),
+                _ => {
+                    let k = (0..3)
+                        .find(|&k| crossings[k] == 0 && crossings[(k + 1) % 3] == 0)
+                        .unwrap();
+                    points.push(q.corners[(k + 1) % 3].into());
+                }
+            }
+        }
+    }
+    points
+}
+
+/// Tests whether a point in the plane of a face is in the closed face.
+fn in_triangle(p: &[f64; 3], face: &Face) -> bool {
+    let project = |p: &[f64; 3]| [p[(face.axis + 1) % 3], p[(face.axis + 2) % 3]];
+    let [a, b, c] = face.corners.each_ref().map(project);
+    let p = project(p);
+    let sides = [
+        orient_2d(&a, &b, &p),
+        orient_2d(&b, &c, &p),
+        orient_2d(&c, &a, &p),
+    ];
+    !(sides.contains(&1) && sides.contains(&-1))
+}
+
+/// Computes the part of the segment `uv` in a coplanar face, or `None` if they do not meet.
+fn clip_coplanar(u: &[f64; 3], v: &[f64; 3], face: &Face) -> Option<[ImplicitPoint3d; 2]> {
+    let project = |p: &[f64; 3]| [p[(face.axis + 1) % 3], p[(face.axis + 2) % 3]];
+    let (pu, pv) = (project(u), project(v));
+    let mut points: Vec<ImplicitPoint3d> = [u, v]
+        .into_iter()
+        .filter(|p| in_triangle(p, face))
+        .map(|&p| p.into())
+        .collect();
+    for i in 0..3 {
+        let (x, y) = (&face.corners[i], &face.corners[(i + 1) % 3]);
+        let (px, py) = (project(x), project(y));
+        let [sx, sy] = [&px, &py].map(|p| orient_2d(&pu, &pv, p));
+        if sx == 0 && lexico_cmp_3d(u, x) == lexico_cmp_3d(x, v) {
+            points.push((*x).into());
+        }
+        let [su, sv] = [&pu, &pv].map(|p| orient_2d(&px, &py, p));
+        if sx * sy < 0 && su * sv < 0 {
+            // The line through `uv` meets a plane through `xy`, off the plane of the face, where it crosses `xy`.
+            let mut off = *x;
+            off[face.axis] += 1.0 + off[face.axis].abs();
+            points.extend(ImplicitPoint3d::segment_triangle_intersection(
+                u,
+                v,
+                &[*x, *y, off],
+            ));
+        }
+    }
+    sort_and_dedup_3d(&mut points);
+    Some([*points.first()?, *points.last()?])
+}
+
+/// Sorts points lexicographically and removes duplicates, keeping explicit points over identical implicit ones.
+fn sort_and_dedup_3d(points: &mut Vec<ImplicitPoint3d>) {
+    points.sort_by(cmp_explicit_first);
+    points.dedup_by(|p, q| implicit::lexico_cmp_3d(p, q) == Ordering::Equal);
+}
+
+/// Compares implicit points lexicographically, and puts explicit points first among equal ones.
+fn cmp_explicit_first(p: &ImplicitPoint3d, q: &ImplicitPoint3d) -> Ordering {
+    implicit::lexico_cmp_3d(p, q).then_with(|| q.is_explicit().cmp(&p.is_explicit()))
+}
+
+/// Classifies the pieces of the faces w.r.t. the other operand.
+///
+/// The pieces that are not on the other operand are grouped into patches, which are connected through edges that are
+/// not on the intersection curve, and are on the same side of the other operand.
+fn classify(
+    faces: &[Face],
+    vertices: &[ImplicitPoint3d],
+    pieces: &[(usize, [usize; 3])],
+    curve: &HashSet<[usize; 2]>,
+    partners: &[Vec<usize>],
+    coplanar: &[Vec<usize>],
+) -> Vec<Location> {
+    let contains = |g: usize, p: usize| on_face(&faces[g], &vertices[p]);
+    let mut locations: Vec<Option<Location>> = pieces
+        .iter()
+        .map(|&(f, t)| {
+            let g = *coplanar[f]
+                .iter()
+                .find(|&&g| t.iter().all(|&p| contains(g, p)))?;
+            let axis = faces[f].axis;
+            let same =
+                projected_orientation(&faces[f], axis) == projected_orientation(&faces[g], axis);
+            Some(Location::On { same })
+        })
+        .collect();
+
+    // The patches, through the edges off the curve, between pieces of the same operand.
+    let mut by_edge: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+    for (i, (_, t)) in pieces.iter().enumerate() {
+        for k in 0..3 {
+            let [u, v] = [t[k], t[(k + 1) % 3]];
+            by_edge.entry([u.min(v), u.max(v)]).or_default().push(i);
+        }
+    }
+    let mut patch = vec![usize::MAX; pieces.len()];
+    let mut patches: Vec<Vec<usize>> = Vec::new();
+    for start in 0..pieces.len() {
+        if patch[start] != usize::MAX || locations[start].is_some() {
+            continue;
+        }
+        patch[start] = patches.len();
+        let mut members = vec![start];
+        let mut stack = vec![start];
+        while let Some(i) = stack.pop() {
+            let (f, t) = pieces[i];
+            for k in 0..3 {
+                let [u, v] = [t[k], t[(k + 1) % 3]];
+                let edge = [u.min(v), u.max(v)];
+                if curve.contains(&edge) {
+                    continue;
+                }
+                for &j in &by_edge[&edge] {
+                    let g = pieces[j].0;
+                    if patch[j] == usize::MAX
+                        && locations[j].is_none()
+                        && faces[g].mesh == faces[f].mesh
+                    {
+                        patch[j] = patches.len();
+                        members.push(j);
+                        stack.push(j);
+                    }
+                }
+            }
+        }
+        patches.push(members);
+    }
+
+    for members in patches {
+        let inside = members
+            .iter()
+            .find_map(|&i| {
+                let (f, t) = pieces[i];
+                (0..3).find_map(|k| {
+                    let [u, v, w] = [0, 1, 2].map(|j| t[(k + j) % 3]);
+                    if !curve.contains(&[u.min(v), u.max(v)]) {
+                        return None;
+                    }
+                    let around: Vec<usize> = partners[f]
+                        .iter()
+                        .copied()
+                        .filter(|&g| contains(g, u) && contains(g, v))
+                        .collect();
+                    locally_inside(faces, &around, &vertices[w])
+                })
+            })
+            .unwrap_or_else(|| {
+                // The vertices of the patch may be on the other operand, but the interior of its pieces is not.
+                let (f, t) = pieces[members[0]];
+                let others: Vec<&Face> = faces.iter().filter(|g| g.mesh != faces[f].mesh).collect();
+                ray_inside(t.map(|p| &vertices[p]), &others)
+            });
+        for i in members {
+            locations[i] = Some(if inside {
+                Location::Inside
+            } else {
+                Location::Outside
+            });
+        }
+    }
+    locations
+        .into_iter()
+        .map(|l| l.expect("all pieces are in a patch"))
+        .collect()
+}
+
+/// Computes the orientation of the projection of a face along a coordinate axis.
+fn projected_orientation(face: &Face, axis: usize) -> i16 {
+    let [a, b, c] = face.corners.map(|p| [p[(axis + 1) % 3], p[(axis + 2) % 3]]);
+    orient_2d(&a, &b, &c)
+}
+
+/// Tests whether a piece of a face is inside of the other operand, from the faces of the other operand around an edge
+/// of the piece on the intersection curve.
+///
+/// ### Parameters
+/// - `around` the faces of the other operand that contain the edge
+/// - `w` the vertex of the piece opposite to the edge
+///
+/// ### Returns
+/// - whether the piece is inside, or `None` if it cannot be decided from these faces
+fn locally_inside(faces: &[Face], around: &[usize], w: &ImplicitPoint3d) -> Option<bool> {
+    let side = |g: usize| {
+        let [a, b, c] = faces[g].corners.map(ImplicitPoint3d::from);
+        implicit::orient_3d(&a, &b, &c, w)
+    };
+    let inside = |s: i16| match s {
+        0 => None,
+        s => Some(s < 0),
+    };
+    match *around {
+        [g] => inside(side(g)),
+        [g, h] => {
+            // The edge is on the common edge of two faces, whose half-spaces are intersected if the operand is convex
+            // there, and united if it is reflex.
+            let apex = faces[h]
+                .corners
+                .iter()
+                .find(|p| !faces[g].corners.contains(p))?;
+            let [a, b, c] = &faces[g].corners;
+            let (s, t) = (side(g), side(h));
+            match orient(a, b, c, apex) {
+                0 => inside(s),
+                c if c < 0 && (s > 0 || t > 0) => Some(false),
+                c if c < 0 && s < 0 && t < 0 => Some(true),
+                c if c > 0 && (s < 0 || t < 0) => Some(true),
+                c if c > 0 && s > 0 && t > 0 => Some(false),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Tests whether a point is on a closed face.
+fn on_face(face: &Face, p: &ImplicitPoint3d) -> bool {
+    let [a, b, c] = &face.corners.map(ImplicitPoint3d::from);
+    let sides =
+        [(a, b), (b, c), (c, a)].map(|(x, y)| implicit::orient_2d_projected(x, y, p, face.axis));
+    implicit::orient_3d(a, b, c, p) == 0 && !(sides.contains(&1) && sides.contains(&-1))
+}
+
+/// Tests whether the interior of a piece is inside of a closed mesh, by counting the faces crossed by a segment to the
+/// outside.
+///
+/// The segment starts next to the first vertex of the piece, moved towards the second one, and then towards the third
+/// one, by infinitesimal amounts. It ends next to a far point `q`, moved along the coordinate axes by even smaller
+/// amounts. Like in the `_SOS` predicates, the sign of a predicate on the perturbed endpoints is the first non-zero
+/// sign on the unperturbed points, in the order of decreasing weight. The interior of the piece is off the faces, so
+/// the segment never starts on a face, and as `q` is moved in three independent directions, it never meets an edge.
+fn ray_inside(piece: [&ImplicitPoint3d; 3], faces: &[&Face]) -> bool {
+    let origin = piece[0].approximate();
+    let corners = faces.iter().flat_map(|f| &f.corners);
+    let [lo, hi] = bounding_box(corners.chain([&origin]));
+    let length = 2.0 * (0..3).map(|k| hi[k] - lo[k]).fold(1.0, f64::max);
+    // The far end is outside of the bounding box, hence outside of the mesh.
+    let q: [f64; 3] = std::array::from_fn(|k| origin[k] + length * DIRECTION[k]);
+    let far: Vec<ImplicitPoint3d> = std::iter::once(q)
+        .chain((0..3).map(|k| {
+            let mut p = q;
+            p[k] += length + q[k].abs();
+            p
+        }))
+        .map(ImplicitPoint3d::from)
+        .collect();
+    let mut crossings = 0;
+    for f in faces {
+        let [a, b, c] = f.corners.map(ImplicitPoint3d::from);
+        let side = |x: &ImplicitPoint3d| implicit::orient_3d(&a, &b, &c, x);
+        let s = first_non_zero(piece.iter().map(|x| side(x)));
+        // The segment starts on the plane of the face if the piece is in that plane, but then it is off the face.
+        if s == 0 || s == first_non_zero(far.iter().map(side)) {
+            continue;
+        }
+        let edges = [(&a, &b), (&b, &c), (&c, &a)].map(|(x, y)| {
+            first_non_zero(
+                far.iter()
+                    .flat_map(|q| piece.iter().map(move |p| implicit::orient_3d(p, q, x, y))),
+            )
+        });
+        if !(edges.contains(&1) && edges.contains(&-1)) {
+            crossings += 1;
+        }
+    }
+    crossings % 2 == 1
+}
+
+/// Gets the first non-zero sign, or `0` if all are zero.
+fn first_non_zero(signs: impl IntoIterator<Item = Sign>) -> Sign {
+    signs.into_iter().find(|&s| s != 0).unwrap_or(0)
+}
+
+/// The direction of the segments in [`ray_inside`], which is unlikely to be parallel to the faces of a mesh, so that
+/// the perturbation of the far end is rarely needed.
+const DIRECTION: [f64; 3] = [0.5385, 0.6574, 0.5271];
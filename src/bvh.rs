@@ -0,0 +1,152 @@
+//! A bounding volume hierarchy of axis aligned boxes, to find the pairs of overlapping boxes.
+
+/// An axis aligned box, as its lower and upper corner.
+pub(crate) type Aabb = [[f64; 3]; 2];
+
+/// The maximum number of items in a leaf.
+const LEAF_SIZE: usize = 4;
+
+struct Node {
+    bbox: Aabb,
+    /// The children of an inner node, or the range of a leaf in `Bvh::items`.
+    range: [usize; 2],
+    leaf: bool,
+}
+
+/// A bounding volume hierarchy, built by recursive median splits along the longest axis.
+pub(crate) struct Bvh {
+    nodes: Vec<Node>,
+    items: Vec<usize>,
+    boxes: Vec<Aabb>,
+}
+
+impl Bvh {
+    /// Builds the hierarchy of the given boxes, which are referred to by their index.
+    pub(crate) fn new(boxes: Vec<Aabb>) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            items: (0..boxes.len()).collect(),
+            boxes,
+        };
+        if !bvh.items.is_empty() {
+            bvh.build(0, bvh.items.len());
+        }
+        bvh
+    }
+
+    /// Calls `f(i, j)` once for each pair of overlapping boxes `i < j`. Boxes that only touch count as overlapping.
+    pub(crate) fn self_overlaps(&self, mut f: impl FnMut(usize, usize)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![(0, 0)];
+        while let Some((a, b)) = stack.pop() {
+            let (na, nb) = (&self.nodes[a], &self.nodes[b]);
+            if a == b {
+                if na.leaf {
+                    let items = &self.items[na.range[0]..na.range[1]];
+                    for (k, &i) in items.iter().enumerate() {
+                        for &j in &items[k + 1..] {
+                            self.report(i, j, &mut f);
+                        }
+                    }
+                } else {
+                    let [l, r] = na.range;
+                    stack.extend([(l, l), (r, r), (l, r)]);
+                }
+            } else if overlap(&na.bbox, &nb.bbox) {
+                self.descend(a, b, &mut stack, |i, j| self.report(i, j, &mut f));
+            }
+        }
+    }
+
+    /// Pushes the children of the node pair `(a, b)`, or calls `leaves` for the item pairs if both are leaves.
+    fn descend(
+        &self,
+        a: usize,
+        b: usize,
+        stack: &mut Vec<(usize, usize)>,
+        mut leaves: impl FnMut(usize, usize),
+    ) {
+        let (na, nb) = (&self.nodes[a], &self.nodes[b]);
+        match (na.leaf, nb.leaf) {
+            (true, true) => {
+                for &i in &self.items[na.range[0]..na.range[1]] {
+                    for &j in &self.items[nb.range[0]..nb.range[1]] {
+                        leaves(i, j);
+                    }
+                }
+            }
+            (false, true) => stack.extend(na.range.map(|c| (c, b))),
+            (true, false) => stack.extend(nb.range.map(|c| (a, c))),
+            (false, false) => {
+                let [l, r] = na.range;
+                stack.extend([(l, b), (r, b)]);
+            }
+        }
+    }
+
+    fn report(&self, i: usize, j: usize, f: &mut impl FnMut(usize, usize)) {
+        if overlap(&self.boxes[i], &self.boxes[j]) {
+            f(i.min(j), i.max(j));
+        }
+    }
+
+    /// Builds the subtree over `items[start..end]`, and returns the index of its root.
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let bbox = self.items[start..end]
+            .iter()
+            .map(|&i| self.boxes[i])
+            .reduce(|a, b| union(&a, &b))
+            .unwrap();
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            bbox,
+            range: [start, end],
+            leaf: true,
+        });
+        if end - start <= LEAF_SIZE {
+            return node;
+        }
+
+        let axis = (0..3)
+            .max_by(|&i, &j| (bbox[1][i] - bbox[0][i]).total_cmp(&(bbox[1][j] - bbox[0][j])))
+            .unwrap();
+        let center = |i: usize| self.boxes[i][0][axis] + self.boxes[i][1][axis];
+        let mid = start + (end - start) / 2;
+        let mut items = std::mem::take(&mut self.items);
+        items[start..end]
+            .select_nth_unstable_by(mid - start, |&i, &j| center(i).total_cmp(&center(j)));
+        self.items = items;
+
+        let left = self.build(start, mid);
+        let right = self.build(mid, end);
+        self.nodes[node].range = [left, right];
+        self.nodes[node].leaf = false;
+        node
+    }
+}
+
+/// Computes the bounding box of some points.
+pub(crate) fn bounding_box<'a>(points: impl IntoIterator<Item = &'a [f64; 3]>) -> Aabb {
+    points.into_iter().fold(
+        [[f64::INFINITY; 3], [f64::NEG_INFINITY; 3]],
+        |[lo, hi], p| {
+            [
+                [0, 1, 2].map(|k| lo[k].min(p[k])),
+                [0, 1, 2].map(|k| hi[k].max(p[k])),
+            ]
+        },
+    )
+}
+
+fn union(a: &Aabb, b: &Aabb) -> Aabb {
+    [
+        [0, 1, 2].map(|k| a[0][k].min(b[0][k])),
+        [0, 1, 2].map(|k| a[1][k].max(b[1][k])),
+    ]
+}
+
+fn overlap(a: &Aabb, b: &Aabb) -> bool {
+    (0..3).all(|k| a[0][k] <= b[1][k] && b[0][k] <= a[1][k])
+}
@@ -0,0 +1,669 @@
+//! Constrained Delaunay triangulation in 2d.
+//!
+//! The triangulation is built incrementally with [`orient_2d`] for point location and [`in_circle_2d_SOS`] for the
+//! Delaunay criterion, so all combinatorial decisions are exact and cocircular points are handled consistently by
+//! symbolic perturbation. Crossing constraints are split at their intersection point, which is approximated within a
+//! few ulps from exact [`Expansion`] arithmetic, and clamped to the bounding boxes of both constraints.
+//!
+//! Exact predicates require [`initialize`](crate::initialize) to be called first.
+
+use crate::expansion::Expansion;
+use crate::{in_circle_2d_SOS, lexico_cmp_2d, orient_2d, points_are_identical_2d, PredicateError};
+use std::collections::{HashMap, VecDeque};
+
+/// The vertex at infinity, shared by all ghost triangles on the outer side of the convex hull.
+const INFINITE: usize = usize::MAX;
+
+/// Marks a triangle index that is not set.
+const NONE: usize = usize::MAX;
+
+/// The region of a triangle w.r.t. the boundaries inserted with [`Cdt2d::insert_boundary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    /// The triangle is enclosed by an odd number of boundaries.
+    Inside,
+    /// The triangle is enclosed by an even number of boundaries.
+    Outside,
+}
+
+/// A constrained Delaunay triangulation in 2d.
+///
+/// Vertices are inserted with [`Cdt2d::insert`], constraints with [`Cdt2d::insert_constraint`] and closed polygon
+/// boundaries with [`Cdt2d::insert_boundary`]. When a new constraint crosses an existing one, both are split at their
+/// intersection, which is inserted as a new vertex.
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::cdt::{Cdt2d, Region};
+///
+/// gp::initialize();
+///
+/// let mut cdt = Cdt2d::new();
+/// let square = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]].map(|p| cdt.insert(p).unwrap());
+/// cdt.insert_boundary(&square);
+/// assert_eq!(2, cdt.triangles().len());
+///
+/// // The diagonals of the square cross in the center, which becomes a vertex
+/// cdt.insert_constraint(square[0], square[2]);
+/// cdt.insert_constraint(square[1], square[3]);
+/// assert_eq!(5, cdt.nb_vertices());
+/// assert_eq!([2.0, 2.0], cdt.point(4));
+/// assert_eq!(4, cdt.triangles_in(Region::Inside).len());
+/// ```
+#[derive(Debug, Default)]
+pub struct Cdt2d {
+    /// Boxed, such that the addresses seen by the symbolic perturbation of `in_circle_2d_SOS` never change.
+    #[allow(clippy::vec_box)]
+    points: Vec<Box<[f64; 2]>>,
+    /// Counter-clockwise triangles. Ghost triangles have the infinite vertex at index 2.
+    triangles: Vec<[usize; 3]>,
+    /// The neighbor opposite to each vertex of each triangle.
+    neighbors: Vec<[usize; 3]>,
+    /// Some triangle incident to each vertex.
+    vertex_triangle: Vec<usize>,
+    /// The constrained edges, with the number of boundaries they are part of.
+    constraints: HashMap<[usize; 2], u32>,
+    /// Constraints inserted before the points spanned a triangle.
+    pending: Vec<(usize, usize, u32)>,
+    /// A finite triangle where point location starts.
+    hint: usize,
+    /// State of the random generator used by the point location.
+    seed: u64,
+}
+
+enum Location {
+    Vertex(usize),
+    Triangle(usize),
+    Edge(usize, usize),
+    Outside(usize),
+}
+
+enum Trace {
+    Reached(Vec<[usize; 2]>),
+    Vertex(usize, Vec<[usize; 2]>),
+    Constraint(usize, usize),
+}
+
+impl Cdt2d {
+    /// Creates an empty triangulation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the number of vertices, including the intersections of constraints.
+    pub fn nb_vertices(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Gets the coordinates of a vertex.
+    pub fn point(&self, v: usize) -> [f64; 2] {
+        *self.points[v]
+    }
+
+    /// Gets the triangles, as counter-clockwise triples of vertex indices.
+    ///
+    /// The triangulation has no triangles as long as all vertices are colinear.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        (0..self.triangles.len())
+            .filter(|&t| !self.is_ghost(t))
+            .map(|t| self.triangles[t])
+            .collect()
+    }
+
+    /// Gets the triangles in a region, as counter-clockwise triples of vertex indices.
+    ///
+    /// The regions follow the even-odd rule: a triangle is inside, if it is enclosed by an odd number of the boundaries
+    /// inserted with [`Cdt2d::insert_boundary`].
+    pub fn triangles_in(&self, region: Region) -> Vec<[usize; 3]> {
+        let inside = self.classify();
+        (0..self.triangles.len())
+            .filter(|&t| !self.is_ghost(t))
+            .filter(|&t| (region == Region::Inside) == inside[t])
+            .map(|t| self.triangles[t])
+            .collect()
+    }
+
+    /// Gets the constrained edges, as sorted pairs of vertex indices.
+    ///
+    /// A constraint that passes through other vertices is reported as its sub-edges.
+    pub fn constraints(&self) -> Vec<[usize; 2]> {
+        let mut constraints: Vec<_> = self.constraints.keys().copied().collect();
+        constraints.sort_unstable();
+        constraints
+    }
+
+    /// Inserts a vertex.
+    ///
+    /// ### Parameters
+    /// - `p` the point to insert
+    ///
+    /// ### Returns
+    /// - the index of the vertex, which is an existing one if a vertex with identical coordinates was inserted before
+    /// - [`PredicateError::NonFinite`] if a coordinate of `p` is NaN or infinite, as the predicates cannot locate it
+    pub fn insert(&mut self, p: [f64; 2]) -> Result<usize, PredicateError> {
+        if !p.iter().all(|x| x.is_finite()) {
+            return Err(PredicateError::NonFinite { arg_index: 0 });
+        }
+        Ok(self.insert_point(p))
+    }
+
+    /// Inserts a finite vertex.
+    fn insert_point(&mut self, p: [f64; 2]) -> usize {
+        if self.triangles.is_empty() {
+            return self.insert_without_triangles(p);
+        }
+        match self.locate(&p) {
+            Location::Vertex(v) => v,
+            location => {
+                let v = self.push_point(p);
+                self.insert_at(v, location);
+                v
+            }
+        }
+    }
+
+    /// Inserts a constraint between two vertices.
+    ///
+    /// The constraint is split at the vertices it passes through, and at its intersections with other constraints.
+    ///
+    /// ### Parameters
+    /// - `i`, `j` the indices of the endpoints of the constraint
+    pub fn insert_constraint(&mut self, i: usize, j: usize) {
+        self.insert_segment(i, j, 0);
+    }
+
+    /// Inserts a closed polygon boundary, as constraints between consecutive vertices.
+    ///
+    /// Boundaries determine the [`Region`] of the triangles.
+    ///
+    /// ### Parameters
+    /// - `polygon` the indices of the vertices of the boundary
+    pub fn insert_boundary(&mut self, polygon: &[usize]) {
+        for (k, &i) in polygon.iter().enumerate() {
+            self.insert_segment(i, polygon[(k + 1) % polygon.len()], 1);
+        }
+    }
+
+    fn is_ghost(&self, t: usize) -> bool {
+        self.triangles[t][2] == INFINITE
+    }
+
+    fn pt(&self, v: usize) -> &[f64; 2] {
+        &self.points[v]
+    }
+
+    fn push_point(&mut self, p: [f64; 2]) -> usize {
+        self.points.push(Box::new(p));
+        self.vertex_triangle.push(NONE);
+        self.points.len() - 1
+    }
+
+    fn random(&mut self) -> usize {
+        self.seed = self
+            .seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.seed >> 33) as usize
+    }
+
+    // ================= triangle storage ==============================
+
+    fn new_triangle(&mut self) -> usize {
+        self.triangles.push([NONE; 3]);
+        self.neighbors.push([NONE; 3]);
+        self.triangles.len() - 1
+    }
+
+    /// Writes a triangle, rotating ghost triangles such that the infinite vertex is at index 2.
+    fn set_triangle(&mut self, t: usize, mut v: [usize; 3], mut n: [usize; 3]) {
+        if let Some(k) = v.iter().position(|&x| x == INFINITE) {
+            v.rotate_left((k + 1) % 3);
+            n.rotate_left((k + 1) % 3);
+        } else {
+            self.hint = t;
+        }
+        for &x in &v {
+            if x != INFINITE {
+                self.vertex_triangle[x] = t;
+            }
+        }
+        self.triangles[t] = v;
+        self.neighbors[t] = n;
+    }
+
+    fn replace_neighbor(&mut self, t: usize, old: usize, new: usize) {
+        let k = self.neighbors[t].iter().position(|&n| n == old).unwrap();
+        self.neighbors[t][k] = new;
+    }
+
+    fn index_of(&self, t: usize, v: usize) -> usize {
+        self.triangles[t].iter().position(|&x| x == v).unwrap()
+    }
+
+    /// Finds a triangle `t` and the index `i` of the vertex of `t` opposite to the edge `ab`.
+    fn find_edge(&self, a: usize, b: usize) -> Option<(usize, usize)> {
+        let start = self.vertex_triangle[a];
+        let mut t = start;
+        loop {
+            let j = self.index_of(t, a);
+            if self.triangles[t][(j + 1) % 3] == b {
+                return Some((t, (j + 2) % 3));
+            }
+            // Turn counter-clockwise around `a`.
+            t = self.neighbors[t][(j + 1) % 3];
+            if t == start {
+                return None;
+            }
+        }
+    }
+
+    /// Flips the edge opposite to vertex `i` of `t`.
+    ///
+    /// Afterwards `t` is `[a, b, d]` and its former neighbor `u` is `[a, d, c]`, where `t` was `[a, b, c]` and `d` is
+    /// the vertex of `u` opposite to `bc`.
+    fn flip(&mut self, t: usize, i: usize) {
+        let [a, b, c] = [0, 1, 2].map(|k| self.triangles[t][(i + k) % 3]);
+        let [u, nb, nc] = [0, 1, 2].map(|k| self.neighbors[t][(i + k) % 3]);
+        let j = (0..3)
+            .find(|&k| self.triangles[u][k] != b && self.triangles[u][k] != c)
+            .unwrap();
+        let d = self.triangles[u][j];
+        let u_bd = self.neighbors[u][(j + 1) % 3];
+        let u_dc = self.neighbors[u][(j + 2) % 3];
+
+        self.set_triangle(t, [a, b, d], [u_bd, u, nc]);
+        self.set_triangle(u, [a, d, c], [u_dc, nb, t]);
+        self.replace_neighbor(u_bd, u, t);
+        self.replace_neighbor(nb, t, u);
+    }
+
+    // ================= constraints ===================================
+
+    fn key(a: usize, b: usize) -> [usize; 2] {
+        [a.min(b), a.max(b)]
+    }
+
+    fn is_constrained(&self, a: usize, b: usize) -> bool {
+        self.constraints.contains_key(&Self::key(a, b))
+    }
+
+    fn add_constraint(&mut self, a: usize, b: usize, count: u32) {
+        *self.constraints.entry(Self::key(a, b)).or_insert(0) += count;
+    }
+
+    // ================= point insertion ===============================
+
+    fn insert_without_triangles(&mut self, p: [f64; 2]) -> usize {
+        if let Some(v) = (0..self.points.len()).find(|&v| points_are_identical_2d(self.pt(v), &p)) {
+            return v;
+        }
+        let v = self.push_point(p);
+        if v >= 2 && orient_2d(self.pt(0), self.pt(1), self.pt(v)) != 0 {
+            self.create_first_triangle(0, 1, v);
+            for w in 2..v {
+                let location = self.locate(&self.point(w));
+                self.insert_at(w, location);
+            }
+            for (i, j, count) in std::mem::take(&mut self.pending) {
+                self.insert_segment(i, j, count);
+            }
+        }
+        v
+    }
+
+    fn create_first_triangle(&mut self, a: usize, b: usize, c: usize) {
+        let (a, b) = if orient_2d(self.pt(a), self.pt(b), self.pt(c)) > 0 {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let [t, g_ab, g_bc, g_ca] = [(); 4].map(|_| self.new_triangle());
+        self.set_triangle(t, [a, b, c], [g_bc, g_ca, g_ab]);
+        self.set_triangle(g_ab, [b, a, INFINITE], [g_ca, g_bc, t]);
+        self.set_triangle(g_bc, [c, b, INFINITE], [g_ab, g_ca, t]);
+        self.set_triangle(g_ca, [a, c, INFINITE], [g_bc, g_ab, t]);
+    }
+
+    /// Locates a point by a randomized visibility walk, starting from the last modified triangle.
+    fn locate(&mut self, p: &[f64; 2]) -> Location {
+        let mut t = self.hint;
+        loop {
+            let [a, b, c] = self.triangles[t].map(|v| self.pt(v));
+            let o = [orient_2d(b, c, p), orient_2d(c, a, p), orient_2d(a, b, p)];
+            let r = self.random();
+            if let Some(i) = (0..3).map(|k| (r + k) % 3).find(|&i| o[i] < 0) {
+                t = self.neighbors[t][i];
+                if self.is_ghost(t) {
+                    return Location::Outside(t);
+                }
+                continue;
+            }
+            return match o.iter().filter(|&&s| s == 0).count() {
+                0 => Location::Triangle(t),
+                1 => Location::Edge(t, o.iter().position(|&s| s == 0).unwrap()),
+                _ => Location::Vertex(self.triangles[t][o.iter().position(|&s| s != 0).unwrap()]),
+            };
+        }
+    }
+
+    fn insert_at(&mut self, v: usize, location: Location) {
+        let created = match location {
+            Location::Vertex(_) => unreachable!("identical vertices are merged"),
+            Location::Triangle(t) => self.insert_in_triangle(t, v),
+            Location::Edge(t, i) => self.insert_on_edge(t, i, v),
+            Location::Outside(g) => self.insert_outside(g, v),
+        };
+        self.legalize_around(v, created);
+    }
+
+    fn insert_in_triangle(&mut self, t: usize, p: usize) -> Vec<usize> {
+        let [a, b, c] = self.triangles[t];
+        let [na, nb, nc] = self.neighbors[t];
+        let t1 = self.new_triangle();
+        let t2 = self.new_triangle();
+        self.set_triangle(t, [a, b, p], [t1, t2, nc]);
+        self.set_triangle(t1, [b, c, p], [t2, t, na]);
+        self.set_triangle(t2, [c, a, p], [t, t1, nb]);
+        self.replace_neighbor(na, t, t1);
+        self.replace_neighbor(nb, t, t2);
+        vec![t, t1, t2]
+    }
+
+    fn insert_on_edge(&mut self, t: usize, i: usize, p: usize) -> Vec<usize> {
+        let [a, b, c] = [0, 1, 2].map(|k| self.triangles[t][(i + k) % 3]);
+        let [u, nb, nc] = [0, 1, 2].map(|k| self.neighbors[t][(i + k) % 3]);
+        let j = (0..3)
+            .find(|&k| self.triangles[u][k] != b && self.triangles[u][k] != c)
+            .unwrap();
+        let d = self.triangles[u][j];
+        let u_bd = self.neighbors[u][(j + 1) % 3];
+        let u_dc = self.neighbors[u][(j + 2) % 3];
+        let t1 = self.new_triangle();
+        let u1 = self.new_triangle();
+
+        self.set_triangle(t, [a, b, p], [u1, t1, nc]);
+        self.set_triangle(t1, [a, p, c], [u, nb, t]);
+        self.set_triangle(u, [d, c, p], [t1, u1, u_dc]);
+        self.set_triangle(u1, [d, p, b], [t, u_bd, u]);
+        self.replace_neighbor(nb, t, t1);
+        self.replace_neighbor(u_bd, u, u1);
+
+        if let Some(count) = self.constraints.remove(&Self::key(b, c)) {
+            self.add_constraint(b, p, count);
+            self.add_constraint(p, c, count);
+        }
+        vec![t, t1, u, u1]
+    }
+
+    fn insert_outside(&mut self, g: usize, p: usize) -> Vec<usize> {
+        let visible = |cdt: &Self, g: usize| {
+            let [a, b, _] = cdt.triangles[g];
+            orient_2d(cdt.pt(a), cdt.pt(b), cdt.pt(p)) > 0
+        };
+        // Collect the chain of ghost triangles whose hull edge is visible from `p`.
+        let mut first = g;
+        while visible(self, self.neighbors[first][1]) {
+            first = self.neighbors[first][1];
+        }
+        let mut chain = vec![first];
+        while visible(self, self.neighbors[*chain.last().unwrap()][0]) {
+            chain.push(self.neighbors[*chain.last().unwrap()][0]);
+        }
+        let prev = self.neighbors[first][1];
+        let next = self.neighbors[*chain.last().unwrap()][0];
+
+        let g_start = self.new_triangle();
+        let g_end = self.new_triangle();
+        for (k, &t) in chain.iter().enumerate() {
+            let [a, b, _] = self.triangles[t];
+            let across = self.neighbors[t][2];
+            let after = chain.get(k + 1).copied().unwrap_or(g_end);
+            let before = if k == 0 { g_start } else { chain[k - 1] };
+            self.set_triangle(t, [a, b, p], [after, before, across]);
+        }
+        let x_first = self.triangles[first][0];
+        let x_last = self.triangles[*chain.last().unwrap()][1];
+        self.set_triangle(g_start, [x_first, p, INFINITE], [g_end, prev, first]);
+        self.set_triangle(
+            g_end,
+            [p, x_last, INFINITE],
+            [next, g_start, *chain.last().unwrap()],
+        );
+        self.neighbors[prev][0] = g_start;
+        self.neighbors[next][1] = g_end;
+        chain
+    }
+
+    /// Restores the Delaunay property by flipping the edges opposite to `p` in the triangles `created`.
+    fn legalize_around(&mut self, p: usize, mut stack: Vec<usize>) {
+        while let Some(t) = stack.pop() {
+            if self.is_ghost(t) {
+                continue;
+            }
+            let i = self.index_of(t, p);
+            if self.is_legal(t, i) {
+                continue;
+            }
+            let u = self.neighbors[t][i];
+            self.flip(t, i);
+            stack.push(t);
+            stack.push(u);
+        }
+    }
+
+    /// Restores the Delaunay property by flipping the given edges and their surroundings.
+    fn legalize_edges(&mut self, mut stack: Vec<[usize; 2]>) {
+        while let Some([x, y]) = stack.pop() {
+            let Some((t, i)) = self.find_edge(x, y) else {
+                continue;
+            };
+            if self.is_ghost(t) || self.is_legal(t, i) {
+                continue;
+            }
+            let a = self.triangles[t][i];
+            self.flip(t, i);
+            let [_, b, d] = self.triangles[t];
+            let c = self.triangles[self.neighbors[t][1]][2];
+            stack.extend([[a, b], [b, d], [d, c], [c, a]]);
+        }
+    }
+
+    /// Tests whether the edge opposite to vertex `i` of the finite triangle `t` is constrained or locally Delaunay.
+    fn is_legal(&self, t: usize, i: usize) -> bool {
+        let [a, b, c] = self.triangles[t];
+        let [e0, e1] = [
+            self.triangles[t][(i + 1) % 3],
+            self.triangles[t][(i + 2) % 3],
+        ];
+        let u = self.neighbors[t][i];
+        if self.is_ghost(u) || self.is_constrained(e0, e1) {
+            return true;
+        }
+        let d = self.triangles[u]
+            .into_iter()
+            .find(|&x| x != e0 && x != e1)
+            .unwrap();
+        in_circle_2d_SOS(self.pt(a), self.pt(b), self.pt(c), self.pt(d)) <= 0
+    }
+
+    // ================= constraint insertion ==========================
+
+    fn insert_segment(&mut self, i: usize, j: usize, count: u32) {
+        if self.triangles.is_empty() {
+            self.pending.push((i, j, count));
+            return;
+        }
+        let mut work = vec![(i, j, count)];
+        while let Some((u, v, count)) = work.pop() {
+            if u == v {
+                continue;
+            }
+            if self.find_edge(u, v).is_some() {
+                self.add_constraint(u, v, count);
+                continue;
+            }
+            match self.trace_segment(u, v) {
+                Trace::Reached(crossed) => self.force_edge(u, v, crossed, count),
+                Trace::Vertex(w, crossed) => {
+                    self.force_edge(u, w, crossed, count);
+                    work.push((w, v, count));
+                }
+                Trace::Constraint(a, b) => {
+                    let q = segment_intersection(self.pt(u), self.pt(v), self.pt(a), self.pt(b));
+                    let w = self.insert_point(q);
+                    // Unless the rounded intersection is exactly on `ab`, the old constraint is rerouted through it.
+                    if self.find_edge(a, b).is_some() {
+                        let old = self.constraints.remove(&Self::key(a, b)).unwrap();
+                        self.legalize_edges(vec![[a, b]]);
+                        work.push((a, w, old));
+                        work.push((w, b, old));
+                    }
+                    work.push((u, w, count));
+                    work.push((w, v, count));
+                }
+            }
+        }
+    }
+
+    /// Walks from `u` towards `v`, collecting the crossed edges, until reaching `v`, a vertex on the segment `uv` or a
+    /// constrained edge.
+    fn trace_segment(&self, u: usize, v: usize) -> Trace {
+        let (pu, pv) = (self.pt(u), self.pt(v));
+        let side = |x: usize| orient_2d(pu, pv, self.pt(x));
+
+        // Find the triangle around `u` through which the segment leaves. The triangles around `u`, ghosts included,
+        // cover all directions, and the finite ones cover the directions into the convex hull, which contains `v`.
+        // So the segment either leaves along an edge `ux`, where `x` is strictly between `u` and `v` as edges contain
+        // no vertices and `uv` is not an edge, or through the interior of a finite triangle: the turn always ends
+        // before getting back to the start.
+        let start = self.vertex_triangle[u];
+        let mut t = start;
+        let (mut right, mut left) = loop {
+            let j = self.index_of(t, u);
+            let x = self.triangles[t][(j + 1) % 3];
+            let y = self.triangles[t][(j + 2) % 3];
+            if x != INFINITE
+                && side(x) == 0
+                && lexico_cmp_2d(self.pt(x), pu) == lexico_cmp_2d(pv, pu)
+            {
+                return Trace::Vertex(x, Vec::new());
+            }
+            if !self.is_ghost(t) && side(x) < 0 && side(y) > 0 {
+                break (x, y);
+            }
+            t = self.neighbors[t][(j + 1) % 3];
+            debug_assert!(t != start, "no triangle around vertex {u} faces vertex {v}");
+        };
+
+        let mut crossed = Vec::new();
+        loop {
+            if self.is_constrained(right, left) {
+                return Trace::Constraint(right, left);
+            }
+            crossed.push([right, left]);
+            t = self.neighbors[t][self.index_of_third(t, right, left)];
+            let z = self.triangles[t][self.index_of_third(t, right, left)];
+            if z == v {
+                return Trace::Reached(crossed);
+            }
+            match side(z) {
+                0 => return Trace::Vertex(z, crossed),
+                s if s < 0 => right = z,
+                _ => left = z,
+            }
+        }
+    }
+
+    fn index_of_third(&self, t: usize, a: usize, b: usize) -> usize {
+        (0..3)
+            .find(|&k| self.triangles[t][k] != a && self.triangles[t][k] != b)
+            .unwrap()
+    }
+
+    /// Makes `uw` an edge by flipping the edges that cross it (Sloan), marks it constrained and restores the Delaunay
+    /// property.
+    fn force_edge(&mut self, u: usize, w: usize, crossed: Vec<[usize; 2]>, count: u32) {
+        let (pu, pw) = (*self.pt(u), *self.pt(w));
+        let mut queue = VecDeque::from(crossed);
+        let mut created = Vec::new();
+        while let Some([x, y]) = queue.pop_front() {
+            let (t, i) = self.find_edge(x, y).unwrap();
+            let a = self.triangles[t][i];
+            let u2 = self.neighbors[t][i];
+            let d = self.triangles[u2][self.index_of_third(u2, x, y)];
+            let (pa, pd) = (*self.pt(a), *self.pt(d));
+            if orient_2d(&pa, &pd, self.pt(x)) * orient_2d(&pa, &pd, self.pt(y)) >= 0 {
+                // The quadrilateral is not strictly convex, retry once other edges have been flipped.
+                queue.push_back([x, y]);
+                continue;
+            }
+            self.flip(t, i);
+            let crosses = ![u, w].contains(&a)
+                && ![u, w].contains(&d)
+                && orient_2d(&pu, &pw, &pa) * orient_2d(&pu, &pw, &pd) < 0;
+            if crosses {
+                queue.push_back([a, d]);
+            } else {
+                created.push([a, d]);
+            }
+        }
+        self.add_constraint(u, w, count);
+        self.legalize_edges(created);
+    }
+
+    // ================= regions =======================================
+
+    /// Flags the triangles enclosed by an odd number of boundaries, by a flood fill from the ghost triangles.
+    fn classify(&self) -> Vec<bool> {
+        let mut inside = vec![false; self.triangles.len()];
+        let mut visited = vec![false; self.triangles.len()];
+        let mut stack: Vec<usize> = (0..self.triangles.len())
+            .filter(|&t| self.is_ghost(t))
+            .collect();
+        stack.iter().for_each(|&t| visited[t] = true);
+        while let Some(t) = stack.pop() {
+            for i in 0..3 {
+                let n = self.neighbors[t][i];
+                if visited[n] {
+                    continue;
+                }
+                let [a, b] = [
+                    self.triangles[t][(i + 1) % 3],
+                    self.triangles[t][(i + 2) % 3],
+                ];
+                let count = self.constraints.get(&Self::key(a, b)).copied().unwrap_or(0);
+                inside[n] = inside[t] ^ (count % 2 == 1);
+                visited[n] = true;
+                stack.push(n);
+            }
+        }
+        inside
+    }
+}
+
+/// Computes the intersection of the segments `uv` and `ab`, which are known to cross properly.
+///
+/// The intersection is `(du * v - dv * u) / (du - dv)`, where `du` and `dv` are the signed areas of `abu` and `abv`.
+/// The numerators and the denominator are evaluated exactly and rounded, so the quotient is within a few ulps of the
+/// exact intersection, but not necessarily the nearest representable point. It is clamped to the bounding boxes of both
+/// segments.
+fn segment_intersection(u: &[f64; 2], v: &[f64; 2], a: &[f64; 2], b: &[f64; 2]) -> [f64; 2] {
+    let signed_area = |p: &[f64; 2]| {
+        Expansion::det2x2(
+            &Expansion::diff(b[0], a[0]),
+            &Expansion::diff(b[1], a[1]),
+            &Expansion::diff(p[0], a[0]),
+            &Expansion::diff(p[1], a[1]),
+        )
+    };
+    let du = signed_area(u);
+    let dv = signed_area(v);
+    let denominator = (&du - &dv).estimate();
+    [0, 1].map(|k| {
+        let numerator = &du.scale(v[k]) - &dv.scale(u[k]);
+        let lo = u[k].min(v[k]).max(a[k].min(b[k]));
+        let hi = u[k].max(v[k]).min(a[k].max(b[k]));
+        (numerator.estimate() / denominator).clamp(lo, hi)
+    })
+}
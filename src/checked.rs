@@ -0,0 +1,477 @@
+//! Predicates that validate their input instead of aborting.
+//!
+//! The bridged predicates pass any `f64` to geogram. On NaN or infinite coordinates they return an unspecified sign,
+//! and the predicates with Simulation of Simplicity fail a C++ assertion, which aborts the process, when their simplex
+//! is flat, or looks flat because the expansion arithmetic overflowed or underflowed. The `checked_*` variants test
+//! these conditions first and only call geogram on inputs where its result is exact.
+
+use crate::{
+    det_3d, det_4d, dot_3d, in_circle_2d_SOS, in_circle_3d_SOS, in_sphere_3d_SOS, orient_2d,
+    orient_2dlifted_SOS, orient_3d, orient_3dlifted_SOS, points_are_colinear_3d,
+    range::{self, Magnitude},
+    Sign,
+};
+use std::fmt;
+
+/// A reason why a checked predicate could not be evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PredicateError {
+    /// The argument at position `arg_index` in the parameter list of the predicate has a NaN or infinite coordinate.
+    NonFinite { arg_index: usize },
+    /// The magnitudes of the coordinates are too large or too small, so that the exact arithmetic could overflow or
+    /// underflow to subnormal numbers. See the [`range`](crate::range) module for the input ranges that always work.
+    Overflow,
+    /// The simplex of a predicate with Simulation of Simplicity is flat, which violates its precondition.
+    Degenerate,
+}
+
+impl fmt::Display for PredicateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PredicateError::NonFinite { arg_index } => {
+                write!(f, "argument {arg_index} is not finite")
+            }
+            PredicateError::Overflow => {
+                write!(f, "coordinates are out of the range of exact arithmetic")
+            }
+            PredicateError::Degenerate => write!(f, "the simplex is flat"),
+        }
+    }
+}
+
+impl std::error::Error for PredicateError {}
+
+/// Returns the position of the first argument with a non-finite coordinate.
+fn finite(args: &[&[f64]]) -> Result<(), PredicateError> {
+    match args.iter().position(|a| !a.iter().all(|x| x.is_finite())) {
+        Some(arg_index) => Err(PredicateError::NonFinite { arg_index }),
+        None => Ok(()),
+    }
+}
+
+/// Tests whether products of the given degrees in values of the given magnitudes cannot overflow or underflow.
+fn in_range(terms: &[(Magnitude, i32)]) -> Result<(), PredicateError> {
+    if range::exact(terms) {
+        Ok(())
+    } else {
+        Err(PredicateError::Overflow)
+    }
+}
+
+/// Validates the points of a predicate whose exact evaluation is a polynomial of `degree` in their coordinates.
+fn check<const N: usize>(points: &[&[f64; N]], degree: i32) -> Result<(), PredicateError> {
+    let args: Vec<&[f64]> = points.iter().map(|p| p.as_slice()).collect();
+    finite(&args)?;
+    in_range(&[(
+        Magnitude::of(points.iter().flat_map(|p| p.iter().copied())),
+        degree,
+    )])
+}
+
+/// Validates lifted points, whose exact evaluation is a determinant with a row per point and a column per coordinate
+/// and for the height.
+fn check_lifted<const N: usize>(
+    points: &[&[f64; N]],
+    heights: &[f64],
+) -> Result<(), PredicateError> {
+    let mut args: Vec<&[f64]> = points.iter().map(|p| p.as_slice()).collect();
+    args.extend(heights.iter().map(std::slice::from_ref));
+    finite(&args)?;
+    in_range(&[
+        (
+            Magnitude::of(points.iter().flat_map(|p| p.iter().copied())),
+            N as i32,
+        ),
+        (Magnitude::of(heights.iter().copied()), 1),
+    ])
+}
+
+/// Checked variant of [`det_3d`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c` the three points that form the matrix
+///
+/// ### Returns
+/// - the sign of the determinant of the matrix
+/// - [`PredicateError::NonFinite`] if a coordinate is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates are out of the range of exact arithmetic
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [1.0, 2.0, 3.0];
+/// let b = [4.0, 5.0, 6.0];
+/// let c = [7.0, 8.0, f64::NAN];
+///
+/// assert_eq!(Ok(0), gp::checked_det_3d(&a, &b, &[7.0, 8.0, 9.0]));
+/// assert_eq!(Err(gp::PredicateError::NonFinite { arg_index: 2 }), gp::checked_det_3d(&a, &b, &c));
+/// ```
+pub fn checked_det_3d(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> Result<Sign, PredicateError> {
+    check(&[a, b, c], 3)?;
+    Ok(det_3d(a, b, c))
+}
+
+/// Checked variant of [`det_4d`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` the four points that form the matrix
+///
+/// ### Returns
+/// - the sign of the determinant of the matrix
+/// - [`PredicateError::NonFinite`] if a coordinate is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates are out of the range of exact arithmetic
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [1.0, 0.0, 0.0, 0.0];
+/// let b = [0.0, 1.0, 0.0, 0.0];
+/// let c = [0.0, 0.0, 1.0, 0.0];
+/// let d = [0.0, 0.0, 0.0, 1e300];
+///
+/// assert_eq!(Ok(1), gp::checked_det_4d(&a, &b, &c, &[0.0, 0.0, 0.0, 1.0]));
+/// assert_eq!(Err(gp::PredicateError::Overflow), gp::checked_det_4d(&a, &b, &c, &d));
+/// ```
+pub fn checked_det_4d(
+    a: &[f64; 4],
+    b: &[f64; 4],
+    c: &[f64; 4],
+    d: &[f64; 4],
+) -> Result<Sign, PredicateError> {
+    check(&[a, b, c, d], 4)?;
+    Ok(det_4d(a, b, c, d))
+}
+
+/// Checked variant of [`dot_3d`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, three 3d points
+///
+/// ### Returns
+/// - the sign of the dot product between the vectors `ab` and `ac`
+/// - [`PredicateError::NonFinite`] if a coordinate is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates are out of the range of exact arithmetic
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0];
+///
+/// assert_eq!(Ok(0), gp::checked_dot_3d(&a, &b, &[0.0, 1.0, 0.0]));
+/// assert_eq!(
+///     Err(gp::PredicateError::NonFinite { arg_index: 1 }),
+///     gp::checked_dot_3d(&a, &[f64::INFINITY, 0.0, 0.0], &[0.0, 1.0, 0.0])
+/// );
+/// ```
+pub fn checked_dot_3d(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> Result<Sign, PredicateError> {
+    check(&[a, b, c], 2)?;
+    Ok(dot_3d(a, b, c))
+}
+
+/// Checked variant of [`orient_2d`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+///
+/// ### Returns
+/// - the orientation of the triangle, as for [`orient_2d`]
+/// - [`PredicateError::NonFinite`] if a coordinate is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates are out of the range of exact arithmetic
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0];
+/// let b = [2.0, 0.0];
+///
+/// assert_eq!(Ok(1), gp::checked_orient_2d(&a, &b, &[1.0, 1.0]));
+/// assert_eq!(
+///     Err(gp::PredicateError::NonFinite { arg_index: 2 }),
+///     gp::checked_orient_2d(&a, &b, &[1.0, f64::NAN])
+/// );
+/// ```
+pub fn checked_orient_2d(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> Result<Sign, PredicateError> {
+    check(&[a, b, c], 2)?;
+    Ok(orient_2d(a, b, c))
+}
+
+/// Checked variant of [`orient_3d`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron
+///
+/// ### Returns
+/// - the orientation of the tetrahedron, as for [`orient_3d`]
+/// - [`PredicateError::NonFinite`] if a coordinate is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates are out of the range of exact arithmetic
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0];
+/// let c = [0.0, 1.0, 0.0];
+///
+/// assert_eq!(Ok(1), gp::checked_orient_3d(&a, &b, &c, &[0.0, 0.0, 1.0]));
+/// assert_eq!(
+///     Err(gp::PredicateError::Overflow),
+///     gp::checked_orient_3d(&a, &b, &c, &[0.0, 0.0, 1e-300])
+/// );
+/// ```
+pub fn checked_orient_3d(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    d: &[f64; 3],
+) -> Result<Sign, PredicateError> {
+    check(&[a, b, c, d], 3)?;
+    Ok(orient_3d(a, b, c, d))
+}
+
+/// Checked variant of [`in_circle_2d_SOS`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+/// - `p` point to test
+///
+/// ### Returns
+/// - the position of `p` relative to the circum-circle, as for [`in_circle_2d_SOS`]
+/// - [`PredicateError::NonFinite`] if a coordinate is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates are out of the range of exact arithmetic
+/// - [`PredicateError::Degenerate`] if the triangle `a`, `b`, `c` is flat
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0];
+/// let b = [2.0, 0.0];
+/// let p = [1.0, -0.4];
+///
+/// assert_eq!(Ok(1), gp::checked_in_circle_2d_SOS(&a, &b, &[1.0, 1.0], &p));
+/// assert_eq!(
+///     Err(gp::PredicateError::Degenerate),
+///     gp::checked_in_circle_2d_SOS(&a, &b, &[4.0, 0.0], &p)
+/// );
+/// ```
+#[allow(non_snake_case)]
+pub fn checked_in_circle_2d_SOS(
+    a: &[f64; 2],
+    b: &[f64; 2],
+    c: &[f64; 2],
+    p: &[f64; 2],
+) -> Result<Sign, PredicateError> {
+    check(&[a, b, c, p], 8)?;
+    if orient_2d(a, b, c) == 0 {
+        return Err(PredicateError::Degenerate);
+    }
+    Ok(in_circle_2d_SOS(a, b, c, p))
+}
+
+/// Checked variant of [`in_circle_3d_SOS`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+/// - `p` point to test
+///
+/// ### Returns
+/// - the position of `p` relative to the circum-circle, as for [`in_circle_3d_SOS`]
+/// - [`PredicateError::NonFinite`] if a coordinate is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates are out of the range of exact arithmetic
+/// - [`PredicateError::Degenerate`] if the points `a`, `b`, `c` are colinear
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0];
+/// let p = [1.0, 1.0, 1.0];
+///
+/// assert_eq!(Ok(1), gp::checked_in_circle_3d_SOS(&a, &b, &[0.0, 2.0, 0.0], &p));
+/// assert_eq!(
+///     Err(gp::PredicateError::Degenerate),
+///     gp::checked_in_circle_3d_SOS(&a, &b, &[1.0, 0.0, 0.0], &p)
+/// );
+/// ```
+#[allow(non_snake_case)]
+pub fn checked_in_circle_3d_SOS(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    p: &[f64; 3],
+) -> Result<Sign, PredicateError> {
+    check(&[a, b, c, p], 8)?;
+    if points_are_colinear_3d(a, b, c) {
+        return Err(PredicateError::Degenerate);
+    }
+    Ok(in_circle_3d_SOS(a, b, c, p))
+}
+
+/// Checked variant of [`in_sphere_3d_SOS`].
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron
+/// - `p` point to test
+///
+/// ### Returns
+/// - the position of `p` relative to the circum-sphere, as for [`in_sphere_3d_SOS`]
+/// - [`PredicateError::NonFinite`] if a coordinate is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates are out of the range of exact arithmetic
+/// - [`PredicateError::Degenerate`] if the tetrahedron `a`, `b`, `c`, `d` is flat
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0];
+/// let c = [0.0, 2.0, 0.0];
+/// let p = [0.75, 0.75, 0.5];
+///
+/// assert_eq!(Ok(1), gp::checked_in_sphere_3d_SOS(&a, &b, &c, &[0.75, 0.75, 1.0], &p));
+/// assert_eq!(
+///     Err(gp::PredicateError::Degenerate),
+///     gp::checked_in_sphere_3d_SOS(&a, &b, &c, &[0.75, 0.75, 0.0], &p)
+/// );
+/// ```
+#[allow(non_snake_case)]
+pub fn checked_in_sphere_3d_SOS(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    d: &[f64; 3],
+    p: &[f64; 3],
+) -> Result<Sign, PredicateError> {
+    check(&[a, b, c, d, p], 5)?;
+    if orient_3d(a, b, c, d) == 0 {
+        return Err(PredicateError::Degenerate);
+    }
+    Ok(in_sphere_3d_SOS(a, b, c, d, p))
+}
+
+/// Checked variant of [`orient_2dlifted_SOS`].
+///
+/// ### Parameters
+/// - `a` ,`b`, `c` vertices of the triangle
+/// - `p` point to test
+/// - `h_a` ,`h_b` ,`h_c` the heights of the lifted points
+/// - `h_p` the height of the lifted point `p`
+///
+/// ### Returns
+/// - the position of `p'` relative to the plane, as for [`orient_2dlifted_SOS`]
+/// - [`PredicateError::NonFinite`] if a coordinate or height is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates or heights are out of the range of exact arithmetic
+/// - [`PredicateError::Degenerate`] if the triangle `a`, `b`, `c` is flat
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0];
+/// let b = [1.0, 0.0];
+/// let c = [0.0, 1.0];
+/// let p = [0.25, 0.25];
+///
+/// assert_eq!(Ok(1), gp::checked_orient_2dlifted_SOS(&a, &b, &c, &p, 0.0, 0.0, 0.0, -1.0));
+/// assert_eq!(
+///     Err(gp::PredicateError::NonFinite { arg_index: 7 }),
+///     gp::checked_orient_2dlifted_SOS(&a, &b, &c, &p, 0.0, 0.0, 0.0, f64::NAN)
+/// );
+/// ```
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub fn checked_orient_2dlifted_SOS(
+    a: &[f64; 2],
+    b: &[f64; 2],
+    c: &[f64; 2],
+    p: &[f64; 2],
+    h_a: f64,
+    h_b: f64,
+    h_c: f64,
+    h_p: f64,
+) -> Result<Sign, PredicateError> {
+    check_lifted(&[a, b, c, p], &[h_a, h_b, h_c, h_p])?;
+    if orient_2d(a, b, c) == 0 {
+        return Err(PredicateError::Degenerate);
+    }
+    Ok(orient_2dlifted_SOS(a, b, c, p, h_a, h_b, h_c, h_p))
+}
+
+/// Checked variant of [`orient_3dlifted_SOS`].
+///
+/// ### Parameters
+/// - `a` ,`b`, `c`, `d` vertices of the tetrahedron
+/// - `p` point to test
+/// - `h_a` ,`h_b` ,`h_c`, `h_d` the heights of the lifted points
+/// - `h_p` the height of the lifted point `p`
+///
+/// ### Returns
+/// - the position of `p'` relative to the hyperplane, as for [`orient_3dlifted_SOS`]
+/// - [`PredicateError::NonFinite`] if a coordinate or height is NaN or infinite
+/// - [`PredicateError::Overflow`] if the coordinates or heights are out of the range of exact arithmetic
+/// - [`PredicateError::Degenerate`] if the tetrahedron `a`, `b`, `c`, `d` is flat
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0];
+/// let c = [0.0, 1.0, 0.0];
+/// let d = [0.0, 0.0, 1.0];
+/// let p = [0.25, 0.25, 0.25];
+///
+/// assert_eq!(
+///     Ok(1),
+///     gp::checked_orient_3dlifted_SOS(&a, &b, &c, &d, &p, 0.0, 0.0, 0.0, 0.0, -1.0)
+/// );
+/// assert_eq!(
+///     Err(gp::PredicateError::Degenerate),
+///     gp::checked_orient_3dlifted_SOS(&a, &b, &c, &[1.0, 1.0, 0.0], &p, 0.0, 0.0, 0.0, 0.0, -1.0)
+/// );
+/// ```
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub fn checked_orient_3dlifted_SOS(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    d: &[f64; 3],
+    p: &[f64; 3],
+    h_a: f64,
+    h_b: f64,
+    h_c: f64,
+    h_d: f64,
+    h_p: f64,
+) -> Result<Sign, PredicateError> {
+    check_lifted(&[a, b, c, d, p], &[h_a, h_b, h_c, h_d, h_p])?;
+    if orient_3d(a, b, c, d) == 0 {
+        return Err(PredicateError::Degenerate);
+    }
+    Ok(orient_3dlifted_SOS(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p))
+}
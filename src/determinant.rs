@@ -0,0 +1,98 @@
+//! Determinants of small matrices over any [`Ring`].
+
+use crate::expansion::Expansion;
+use crate::interval::{Interval, Ring};
+use crate::{det_3d, det_4d, Sign};
+
+/// Computes the determinant of a square matrix, given by its rows.
+///
+/// The minors of the bottom rows are computed once for each subset of columns, and expanded along the row above them,
+/// which takes `O(2^n n)` products instead of the `O(n!)` of a plain Laplace expansion, and no divisions.
+pub(crate) fn det<T: Ring>(rows: &[Vec<T>]) -> T {
+    let n = rows.len();
+    debug_assert!(rows.iter().all(|row| row.len() == n));
+    if n == 0 {
+        return T::from(1.0);
+    }
+    // `minors[mask]` is the minor of the last `mask.count_ones()` rows and the columns in `mask`.
+    let mut minors: Vec<Option<T>> = vec![None; 1 << n];
+    for (j, x) in rows[n - 1].iter().enumerate() {
+        minors[1 << j] = Some(x.clone());
+    }
+    for k in 2..=n {
+        let row = &rows[n - k];
+        for mask in 1usize..1 << n {
+            if mask.count_ones() as usize != k {
+                continue;
+            }
+            let mut minor: Option<T> = None;
+            for (position, j) in (0..n).filter(|&j| mask & (1 << j) != 0).enumerate() {
+                let term = row[j].clone() * minors[mask & !(1 << j)].clone().unwrap();
+                minor = Some(match (minor, position % 2 == 0) {
+                    (None, true) => term,
+                    (None, false) => -term,
+                    (Some(m), true) => m + term,
+                    (Some(m), false) => m - term,
+                });
+            }
+            minors[mask] = minor;
+        }
+    }
+    minors[(1 << n) - 1].take().unwrap()
+}
+
+/// Computes the sign of the determinant of a square matrix of any size.
+///
+/// For `N = 3` and `N = 4`, geogram's filtered kernels [`det_3d`] and [`det_4d`] are called. For other sizes, the
+/// determinant is first evaluated with [`Interval`]s, and only if they cannot decide with exact [`Expansion`]s, by an
+/// expansion into minors that needs no division and `O(2^N N)` products. The result is exact for entries in
+/// [`SafeRange::of_degree(N)`](crate::range::SafeRange::of_degree).
+///
+/// Hence the [`FilterMode`](crate::FilterMode) set with [`set_filter_mode`](crate::set_filter_mode) applies for
+/// `N = 3` and `N = 4` only. For other sizes, the result is always exact.
+///
+/// Exact predicates require [`initialize`](crate::initialize) to be called first.
+///
+/// ### Parameters
+/// - `m` the rows of the matrix
+///
+/// ### Returns
+/// - the sign of the determinant of the matrix
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let mut m = [[0.0; 6]; 6];
+/// for i in 0..6 {
+///     m[i][i] = 1.0;
+///     m[i][(i + 1) % 6] = 0.5;
+/// }
+/// assert_eq!(1, gp::det_sign(&m));
+///
+/// // The last row is a combination of the first two.
+/// m[5] = [1.0, 1.5, 0.5, 0.0, 0.0, 0.0];
+/// assert_eq!(0, gp::det_sign(&m));
+/// m[5][0] += f64::EPSILON;
+/// assert_eq!(-1, gp::det_sign(&m));
+/// ```
+pub fn det_sign<const N: usize>(m: &[[f64; N]; N]) -> Sign {
+    fn row<const M: usize>(r: &[f64]) -> &[f64; M] {
+        r.try_into().unwrap()
+    }
+    fn rows<T: Ring, const N: usize>(m: &[[f64; N]; N]) -> Vec<Vec<T>> {
+        m.iter()
+            .map(|r| r.iter().map(|&x| T::from(x)).collect())
+            .collect()
+    }
+    match N {
+        3 => det_3d(row(&m[0]), row(&m[1]), row(&m[2])),
+        4 => det_4d(row(&m[0]), row(&m[1]), row(&m[2]), row(&m[3])),
+        _ => match det(&rows::<Interval, N>(m)).sign() {
+            Some(sign) => sign,
+            None => det(&rows::<Expansion, N>(m)).sign(),
+        },
+    }
+}
@@ -0,0 +1,364 @@
+//! Exact comparison of distances and power distances between points, and sides of bisectors.
+
+use crate::expansion::Expansion;
+use crate::interval::{Interval, Ring};
+use crate::Sign;
+use std::cmp::Ordering;
+
+#[cxx::bridge(namespace = "GEOGRAM")]
+mod ffi {
+    unsafe extern "C++" {
+        include!("geogram_predicates/include/geogram_ffi.h");
+
+        fn side1_nd_SOS(p0: &[f64], p1: &[f64], q0: &[f64]) -> i16;
+
+        fn side2_3d_SOS(
+            p0: &[f64; 3],
+            p1: &[f64; 3],
+            p2: &[f64; 3],
+            q0: &[f64; 3],
+            q1: &[f64; 3],
+        ) -> i16;
+    }
+}
+
+/// Computes `|p - q|^2 - |p - r|^2`.
+fn sq_dist_diff<T: Ring>(p: &[f64], q: &[f64], r: &[f64]) -> T {
+    let mut diff = T::from(0.0);
+    for ((&p, &q), &r) in p.iter().zip(q).zip(r) {
+        let pq = T::from(p) - T::from(q);
+        let pr = T::from(p) - T::from(r);
+        diff = diff + pq.clone() * pq - pr.clone() * pr;
+    }
+    diff
+}
+
+/// Computes `|p - q|^2 - w_q - (|p - r|^2 - w_r)`.
+fn power_diff<T: Ring>(p: &[f64], q: &[f64], w_q: f64, r: &[f64], w_r: f64) -> T {
+    sq_dist_diff::<T>(p, q, r) - T::from(w_q) + T::from(w_r)
+}
+
+/// Compares the distances from a point to two other points.
+///
+/// See [`compare_distance_nd`].
+///
+/// ### Parameters
+/// - `p` the reference point
+/// - `q`, `r` the points whose distances to `p` are compared
+///
+/// ### Return values
+/// * `Less` - if `q` is closer to `p` than `r`
+/// * `Equal` - if `q` and `r` are at the same distance from `p`
+/// * `Greater` - if `r` is closer to `p` than `q`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [0.0, 0.0];
+///
+/// assert_eq!(Ordering::Less, gp::compare_distance_2d(&p, &[1.0, 0.0], &[1.0, 1.0]));
+/// assert_eq!(Ordering::Equal, gp::compare_distance_2d(&p, &[3.0, 4.0], &[0.0, -5.0]));
+/// // the doubles closest to 0.1 and 0.3 are not at the same distance from the one closest to 0.2
+/// assert_eq!(Ordering::Greater, gp::compare_distance_2d(&[0.2, 0.0], &[0.1, 0.0], &[0.3, 0.0]));
+/// ```
+pub fn compare_distance_2d(p: &[f64; 2], q: &[f64; 2], r: &[f64; 2]) -> Ordering {
+    compare_distance_nd(p, q, r)
+}
+
+/// Compares the distances from a point to two other points in 3d.
+///
+/// See [`compare_distance_nd`].
+///
+/// ### Parameters
+/// - `p` the reference point
+/// - `q`, `r` the points whose distances to `p` are compared
+///
+/// ### Return values
+/// * `Less` - if `q` is closer to `p` than `r`
+/// * `Equal` - if `q` and `r` are at the same distance from `p`
+/// * `Greater` - if `r` is closer to `p` than `q`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [1.0, 1.0, 1.0];
+///
+/// assert_eq!(Ordering::Greater, gp::compare_distance_3d(&p, &[3.0, 1.0, 1.0], &[1.0, 2.0, 2.0]));
+/// assert_eq!(Ordering::Equal, gp::compare_distance_3d(&p, &[3.0, 1.0, 1.0], &[1.0, 1.0, -1.0]));
+/// ```
+pub fn compare_distance_3d(p: &[f64; 3], q: &[f64; 3], r: &[f64; 3]) -> Ordering {
+    compare_distance_nd(p, q, r)
+}
+
+/// Compares the distances from a point to two other points, in any dimension.
+///
+/// This is the sign of `|p - q|^2 - |p - r|^2`. It is first evaluated with [`Interval`]s, and only if they cannot
+/// decide with exact [`Expansion`]s, see [`interval::sign_of`](crate::interval::sign_of). The result is exact for
+/// coordinates in [`range::COMPARE_DISTANCE`](crate::range::COMPARE_DISTANCE).
+///
+/// ### Parameters
+/// - `p` the reference point
+/// - `q`, `r` the points whose distances to `p` are compared
+///
+/// ### Return values
+/// * `Less` - if `q` is closer to `p` than `r`
+/// * `Equal` - if `q` and `r` are at the same distance from `p`
+/// * `Greater` - if `r` is closer to `p` than `q`
+///
+/// ### Panics
+/// - if the points have different dimensions
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [0.0, 0.0, 0.0, 0.0];
+/// let q = [1.0, 1.0, 1.0, 1.0];
+/// let r = [2.0, 0.0, 0.0, 0.0];
+///
+/// assert_eq!(Ordering::Equal, gp::compare_distance_nd(&p, &q, &r));
+/// assert_eq!(Ordering::Less, gp::compare_distance_nd(&[0.0, 0.0, 0.0, f64::EPSILON], &q, &r));
+/// ```
+pub fn compare_distance_nd(p: &[f64], q: &[f64], r: &[f64]) -> Ordering {
+    assert!(
+        p.len() == q.len() && p.len() == r.len(),
+        "points of different dimensions"
+    );
+    let sign = match sq_dist_diff::<Interval>(p, q, r).sign() {
+        Some(sign) => sign,
+        None => sq_dist_diff::<Expansion>(p, q, r).sign(),
+    };
+    sign.cmp(&0)
+}
+
+/// Compares the power distances from a point to two weighted points in 2d.
+///
+/// See [`compare_power_distance_nd`].
+///
+/// ### Parameters
+/// - `p` the reference point
+/// - `q`, `r` the weighted points whose power distances to `p` are compared
+/// - `w_q`, `w_r` the weights of `q` and `r`
+///
+/// ### Return values
+/// * `Less` - if the power distance from `p` to `q` is smaller than the one to `r`
+/// * `Equal` - if both power distances are equal
+/// * `Greater` - if the power distance from `p` to `r` is smaller than the one to `q`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [0.0, 0.0];
+///
+/// assert_eq!(Ordering::Greater, gp::compare_power_distance_2d(&p, &[1.0, 0.0], 0.0, &[2.0, 0.0], 4.0));
+/// assert_eq!(Ordering::Equal, gp::compare_power_distance_2d(&p, &[1.0, 0.0], 1.0, &[2.0, 0.0], 4.0));
+/// ```
+pub fn compare_power_distance_2d(
+    p: &[f64; 2],
+    q: &[f64; 2],
+    w_q: f64,
+    r: &[f64; 2],
+    w_r: f64,
+) -> Ordering {
+    compare_power_distance_nd(p, q, w_q, r, w_r)
+}
+
+/// Compares the power distances from a point to two weighted points in 3d.
+///
+/// See [`compare_power_distance_nd`].
+///
+/// ### Parameters
+/// - `p` the reference point
+/// - `q`, `r` the weighted points whose power distances to `p` are compared
+/// - `w_q`, `w_r` the weights of `q` and `r`
+///
+/// ### Return values
+/// * `Less` - if the power distance from `p` to `q` is smaller than the one to `r`
+/// * `Equal` - if both power distances are equal
+/// * `Greater` - if the power distance from `p` to `r` is smaller than the one to `q`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [1.0, 1.0, 1.0];
+///
+/// assert_eq!(Ordering::Less, gp::compare_power_distance_3d(&p, &[3.0, 1.0, 1.0], 3.5, &[1.0, 1.0, 2.0], 0.0));
+/// ```
+pub fn compare_power_distance_3d(
+    p: &[f64; 3],
+    q: &[f64; 3],
+    w_q: f64,
+    r: &[f64; 3],
+    w_r: f64,
+) -> Ordering {
+    compare_power_distance_nd(p, q, w_q, r, w_r)
+}
+
+/// Compares the power distances from a point to two weighted points, in any dimension.
+///
+/// The power distance from `p` to a point `q` with weight `w_q` is `|p - q|^2 - w_q`, and the weighted points closest
+/// to `p` in this sense are the ones of the power diagram cell that contains `p`. This is the sign of
+/// `|p - q|^2 - w_q - (|p - r|^2 - w_r)`, evaluated like in [`compare_distance_nd`], to which it reduces for equal
+/// weights. Unlike [`side_of_bisector_nd`], it does not call geogram's `side1_SOS`, even in 3d: that predicate compares
+/// squared distances without weights, which geogram adds by lifting the points to 4d with a square root of the weight,
+/// hence inexactly, and it perturbs equal distances instead of reporting them. The result is exact for coordinates in
+/// [`range::COMPARE_DISTANCE`](crate::range::COMPARE_DISTANCE) and weights in
+/// [`range::COMPARE_POWER_DISTANCE`](crate::range::COMPARE_POWER_DISTANCE).
+///
+/// ### Parameters
+/// - `p` the reference point
+/// - `q`, `r` the weighted points whose power distances to `p` are compared
+/// - `w_q`, `w_r` the weights of `q` and `r`
+///
+/// ### Return values
+/// * `Less` - if the power distance from `p` to `q` is smaller than the one to `r`
+/// * `Equal` - if both power distances are equal
+/// * `Greater` - if the power distance from `p` to `r` is smaller than the one to `q`
+///
+/// ### Panics
+/// - if the points have different dimensions
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [0.0, 0.0, 0.0, 0.0];
+/// let q = [1.0, 1.0, 1.0, 1.0];
+/// let r = [2.0, 0.0, 0.0, 0.0];
+///
+/// assert_eq!(Ordering::Equal, gp::compare_power_distance_nd(&p, &q, 0.5, &r, 0.5));
+/// assert_eq!(Ordering::Less, gp::compare_power_distance_nd(&p, &q, 0.5, &r, 0.5 - f64::EPSILON));
+/// ```
+pub fn compare_power_distance_nd(p: &[f64], q: &[f64], w_q: f64, r: &[f64], w_r: f64) -> Ordering {
+    assert!(
+        p.len() == q.len() && p.len() == r.len(),
+        "points of different dimensions"
+    );
+    let sign = match power_diff::<Interval>(p, q, w_q, r, w_r).sign() {
+        Some(sign) => sign,
+        None => power_diff::<Expansion>(p, q, w_q, r, w_r).sign(),
+    };
+    sign.cmp(&0)
+}
+
+/// Computes the side of a point relative to a bisector, in any dimension.
+///
+/// This is the sign of `|q - p1|^2 - |q - p0|^2`. In the dimensions 3, 4, 6, 7 and 8 that geogram's `side1_SOS`
+/// supports, e.g. in 3d as [`side1_3d_SOS`](crate::side1_3d_SOS), it is called. In other dimensions, the sign is
+/// evaluated like in [`compare_distance_nd`]. If `q` is exactly on the bisector, the result is perturbed like in
+/// geogram: it is `+1` if `p0` has a lower address than `p1`, so that the perturbation is consistent with the other SOS
+/// predicates. The result is exact for coordinates in [`range::COMPARE_DISTANCE`](crate::range::COMPARE_DISTANCE).
+///
+/// ### Parameters
+/// - `p0`, `p1` the points that define the bisector
+/// - `q` point to test
+///
+/// ### Return values
+/// * `+1` - if `q` is on the side of `p0`, i.e. closer to `p0` than to `p1`
+/// * `-1` - if `q` is on the side of `p1`
+/// * `perturb()` - if `q` is exactly on the bisector, where `perturb()` denotes a globally consistent perturbation,
+///   that returns either `+1` or `-1`
+///
+/// ### Panics
+/// - if the points have different dimensions
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let points = [[0.0, 0.0], [2.0, 2.0]];
+///
+/// assert_eq!(1, gp::side_of_bisector_nd(&points[0], &points[1], &[2.0, -0.1]));
+/// assert_eq!(-1, gp::side_of_bisector_nd(&points[0], &points[1], &[2.0, 0.1]));
+/// // on the bisector, the point with the lower address wins
+/// assert_eq!(1, gp::side_of_bisector_nd(&points[0], &points[1], &[2.0, 0.0]));
+/// assert_eq!(-1, gp::side_of_bisector_nd(&points[1], &points[0], &[2.0, 0.0]));
+/// ```
+pub fn side_of_bisector_nd(p0: &[f64], p1: &[f64], q: &[f64]) -> Sign {
+    assert!(
+        p0.len() == p1.len() && p0.len() == q.len(),
+        "points of different dimensions"
+    );
+    if matches!(p0.len(), 3 | 4 | 6 | 7 | 8) {
+        return ffi::side1_nd_SOS(p0, p1, q);
+    }
+    let sign = match sq_dist_diff::<Interval>(q, p1, p0).sign() {
+        Some(sign) => sign,
+        None => sq_dist_diff::<Expansion>(q, p1, p0).sign(),
+    };
+    match sign {
+        0 if p0.as_ptr() < p1.as_ptr() => 1,
+        0 => -1,
+        sign => sign,
+    }
+}
+
+/// Computes `(p1 - p0) . (q1 - q0)`, which is zero iff the line through `q0` and `q1` is parallel to the bisector of
+/// `p0` and `p1`, or `p0 = p1`.
+fn direction_dot<T: Ring>(p0: &[f64; 3], p1: &[f64; 3], q0: &[f64; 3], q1: &[f64; 3]) -> T {
+    (0..3).fold(T::from(0.0), |dot, k| {
+        dot + (T::from(p1[k]) - T::from(p0[k])) * (T::from(q1[k]) - T::from(q0[k]))
+    })
+}
+
+/// Computes the side of the intersection of a segment and a bisector relative to another bisector.
+///
+/// Let `q` be the intersection of the line through `q0` and `q1` with the bisector of `p0` and `p1`. Tests on which
+/// side of the bisector of `p0` and `p2` the point `q` lies. For `q0 = p0` and `q1 = p1`, `q` is the circumcenter of
+/// the edge `p0`, `p1`, i.e. its midpoint.
+///
+/// The intersection only exists if the line is not parallel to the bisector, which is tested exactly, with an
+/// [`Interval`] filter and an [`Expansion`] fallback, before geogram's `side2_SOS` is called.
+///
+/// ### Parameters
+/// - `p0`, `p1`, `p2` the points that define the bisectors
+/// - `q0`, `q1` the points that define the line
+///
+/// ### Return values
+/// * `Some(+1)` - if `q` is on the side of `p0`, i.e. closer to `p0` than to `p2`
+/// * `Some(-1)` - if `q` is on the side of `p2`
+/// * `Some(perturb())` - if `q` is exactly on the bisector of `p0` and `p2`, where `perturb()` denotes a globally
+///   consistent perturbation, that returns either `+1` or `-1`
+/// * `None` - if the line is parallel to the bisector of `p0` and `p1`, or `p0 = p1`, so that `q` does not exist
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let p0 = [0.0, 0.0, 0.0];
+/// let p1 = [2.0, 0.0, 0.0];
+///
+/// // The midpoint of p0 and p1 is at distance 1 of p0.
+/// assert_eq!(Some(1), gp::side2_3d_SOS(&p0, &p1, &[1.0, 1.5, 0.0], &p0, &p1));
+/// assert_eq!(Some(-1), gp::side2_3d_SOS(&p0, &p1, &[1.0, 0.5, 0.0], &p0, &p1));
+/// // The line along the y axis lies in the bisector of p0 and p1.
+/// assert_eq!(None, gp::side2_3d_SOS(&p0, &p1, &[0.0, 2.0, 0.0], &p0, &[0.0, 1.0, 0.0]));
+/// ```
+#[allow(non_snake_case)]
+pub fn side2_3d_SOS(
+    p0: &[f64; 3],
+    p1: &[f64; 3],
+    p2: &[f64; 3],
+    q0: &[f64; 3],
+    q1: &[f64; 3],
+) -> Option<Sign> {
+    let parallel = match direction_dot::<Interval>(p0, p1, q0, q1).sign() {
+        Some(sign) => sign == 0,
+        None => direction_dot::<Expansion>(p0, p1, q0, q1).sign() == 0,
+    };
+    (!parallel).then(|| ffi::side2_3d_SOS(p0, p1, p2, q0, q1))
+}
@@ -0,0 +1,352 @@
+//! Exact arithmetic with expansions.
+//!
+//! An expansion represents a real number exactly, as the sum of non-overlapping `f64` components sorted by increasing
+//! magnitude (Shewchuk). This is a port of geogram's `expansion` class, which is internal to the `Predicates_psm.cpp`
+//! module and hence cannot be bridged. It is used for exact constructions, e.g. of intersection points, that have no
+//! counterpart among the bridged predicates.
+//!
+//! Like the predicates, sums, differences and products of expansions are exact, as long as no intermediate value
+//! overflows or underflows.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// `2^27 + 1`, used to split a double into two non-overlapping halves of 26 bits each.
+const SPLITTER: f64 = 134_217_729.0;
+
+/// A real number, represented exactly as a sum of non-overlapping doubles.
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::expansion::Expansion;
+///
+/// // the doubles closest to 0.1, 0.2 and 0.3 do not cancel, their exact sum is 2^-55
+/// let sum = &Expansion::sum(0.1, 0.2) - &Expansion::new(0.3);
+/// assert_eq!(1, sum.sign());
+/// assert_eq!(2f64.powi(-55), sum.estimate());
+///
+/// // the determinant of [[1e30, 1], [1e30 + 2^50, 1]] is exactly -2^50
+/// let det = Expansion::det2x2(
+///     &Expansion::new(1e30), &Expansion::new(1.0),
+///     &Expansion::new(1e30 + 2f64.powi(50)), &Expansion::new(1.0),
+/// );
+/// assert_eq!(-(2f64.powi(50)), det.estimate());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Expansion {
+    /// Non-overlapping components by increasing magnitude, with zeros eliminated. Never empty; zero is `[0.0]`.
+    x: Vec<f64>,
+}
+
+impl Expansion {
+    /// Creates an expansion from a single double.
+    pub fn new(a: f64) -> Self {
+        Expansion { x: vec![a] }
+    }
+
+    /// Creates the expansion of the exact sum `a + b`.
+    pub fn sum(a: f64, b: f64) -> Self {
+        let (x, y) = two_sum(a, b);
+        Self::from_pair(x, y)
+    }
+
+    /// Creates the expansion of the exact difference `a - b`.
+    pub fn diff(a: f64, b: f64) -> Self {
+        let (x, y) = two_diff(a, b);
+        Self::from_pair(x, y)
+    }
+
+    /// Creates the expansion of the exact product `a * b`.
+    pub fn product(a: f64, b: f64) -> Self {
+        let (x, y) = two_product(a, b);
+        Self::from_pair(x, y)
+    }
+
+    /// Computes the exact determinant `a11 * a22 - a12 * a21` of a 2x2 matrix.
+    pub fn det2x2(a11: &Expansion, a12: &Expansion, a21: &Expansion, a22: &Expansion) -> Self {
+        &(a11 * a22) - &(a12 * a21)
+    }
+
+    /// Computes the exact product of this expansion with a double.
+    pub fn scale(&self, b: f64) -> Self {
+        Expansion {
+            x: scale_expansion_zeroelim(&self.x, b),
+        }
+    }
+
+    /// Gets the sign of the expansion.
+    ///
+    /// ### Return values
+    /// - `+1` if the expansion is positive
+    /// - `0` if the expansion is `0`
+    /// - `-1` if the expansion is negative
+    pub fn sign(&self) -> i16 {
+        let top = self.x[self.x.len() - 1];
+        if top > 0.0 {
+            1
+        } else if top < 0.0 {
+            -1
+        } else {
+            0
+        }
+    }
+
+    /// Computes an approximation of the value of the expansion, by summing its components.
+    pub fn estimate(&self) -> f64 {
+        self.x.iter().sum()
+    }
+
+    /// Gets the number of components of the expansion.
+    pub fn length(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Gets the components of the expansion, by increasing magnitude.
+    pub fn components(&self) -> &[f64] {
+        &self.x
+    }
+
+    fn from_pair(x: f64, y: f64) -> Self {
+        if y == 0.0 {
+            Expansion { x: vec![x] }
+        } else {
+            Expansion { x: vec![y, x] }
+        }
+    }
+}
+
+impl From<f64> for Expansion {
+    fn from(a: f64) -> Self {
+        Expansion::new(a)
+    }
+}
+
+impl Add for &Expansion {
+    type Output = Expansion;
+
+    fn add(self, rhs: &Expansion) -> Expansion {
+        Expansion {
+            x: fast_expansion_sum_zeroelim(&self.x, &rhs.x),
+        }
+    }
+}
+
+impl Sub for &Expansion {
+    type Output = Expansion;
+
+    fn sub(self, rhs: &Expansion) -> Expansion {
+        self + &(-rhs)
+    }
+}
+
+impl Mul for &Expansion {
+    type Output = Expansion;
+
+    fn mul(self, rhs: &Expansion) -> Expansion {
+        // Sum of the partial products of `self` with each component of the shorter operand.
+        let (long, short) = if self.x.len() >= rhs.x.len() {
+            (&self.x, &rhs.x)
+        } else {
+            (&rhs.x, &self.x)
+        };
+        let mut x = scale_expansion_zeroelim(long, short[0]);
+        for &b in &short[1..] {
+            x = fast_expansion_sum_zeroelim(&x, &scale_expansion_zeroelim(long, b));
+        }
+        Expansion { x }
+    }
+}
+
+impl Add for Expansion {
+    type Output = Expansion;
+
+    fn add(self, rhs: Expansion) -> Expansion {
+        &self + &rhs
+    }
+}
+
+impl Sub for Expansion {
+    type Output = Expansion;
+
+    fn sub(self, rhs: Expansion) -> Expansion {
+        &self - &rhs
+    }
+}
+
+impl Mul for Expansion {
+    type Output = Expansion;
+
+    fn mul(self, rhs: Expansion) -> Expansion {
+        &self * &rhs
+    }
+}
+
+impl Neg for &Expansion {
+    type Output = Expansion;
+
+    fn neg(self) -> Expansion {
+        Expansion {
+            x: self.x.iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+impl Neg for Expansion {
+    type Output = Expansion;
+
+    fn neg(mut self) -> Expansion {
+        self.x.iter_mut().for_each(|c| *c = -*c);
+        self
+    }
+}
+
+#[inline]
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let bvirt = x - a;
+    (x, b - bvirt)
+}
+
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let bvirt = x - a;
+    let avirt = x - bvirt;
+    let bround = b - bvirt;
+    let around = a - avirt;
+    (x, around + bround)
+}
+
+#[inline]
+fn two_diff(a: f64, b: f64) -> (f64, f64) {
+    let x = a - b;
+    let bvirt = a - x;
+    let avirt = x + bvirt;
+    let bround = bvirt - b;
+    let around = a - avirt;
+    (x, around + bround)
+}
+
+#[inline]
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let abig = c - a;
+    let ahi = c - abig;
+    (ahi, a - ahi)
+}
+
+#[inline]
+fn two_product_presplit(a: f64, b: f64, bhi: f64, blo: f64) -> (f64, f64) {
+    let x = a * b;
+    let (ahi, alo) = split(a);
+    let err1 = x - (ahi * bhi);
+    let err2 = err1 - (alo * bhi);
+    let err3 = err2 - (ahi * blo);
+    (x, (alo * blo) - err3)
+}
+
+#[inline]
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let (bhi, blo) = split(b);
+    two_product_presplit(a, b, bhi, blo)
+}
+
+fn scale_expansion_zeroelim(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(2 * e.len());
+    let (bhi, blo) = split(b);
+    let (mut q, hh) = two_product_presplit(e[0], b, bhi, blo);
+    if hh != 0.0 {
+        h.push(hh);
+    }
+    for &enow in &e[1..] {
+        let (product1, product0) = two_product_presplit(enow, b, bhi, blo);
+        let (sum, hh) = two_sum(q, product0);
+        if hh != 0.0 {
+            h.push(hh);
+        }
+        let (q_new, hh) = fast_two_sum(product1, sum);
+        q = q_new;
+        if hh != 0.0 {
+            h.push(hh);
+        }
+    }
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+fn fast_expansion_sum_zeroelim(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() + f.len());
+    // Components past the end are never used, reading them as zero keeps the loops below close to the original.
+    let at = |x: &[f64], i: usize| x.get(i).copied().unwrap_or(0.0);
+
+    let (mut ei, mut fi) = (0, 0);
+    let (mut enow, mut fnow) = (e[0], f[0]);
+    let mut q;
+    if (fnow > enow) == (fnow > -enow) {
+        q = enow;
+        ei += 1;
+        enow = at(e, ei);
+    } else {
+        q = fnow;
+        fi += 1;
+        fnow = at(f, fi);
+    }
+    if ei < e.len() && fi < f.len() {
+        let (q_new, hh) = if (fnow > enow) == (fnow > -enow) {
+            let r = fast_two_sum(enow, q);
+            ei += 1;
+            enow = at(e, ei);
+            r
+        } else {
+            let r = fast_two_sum(fnow, q);
+            fi += 1;
+            fnow = at(f, fi);
+            r
+        };
+        q = q_new;
+        if hh != 0.0 {
+            h.push(hh);
+        }
+        while ei < e.len() && fi < f.len() {
+            let (q_new, hh) = if (fnow > enow) == (fnow > -enow) {
+                let r = two_sum(q, enow);
+                ei += 1;
+                enow = at(e, ei);
+                r
+            } else {
+                let r = two_sum(q, fnow);
+                fi += 1;
+                fnow = at(f, fi);
+                r
+            };
+            q = q_new;
+            if hh != 0.0 {
+                h.push(hh);
+            }
+        }
+    }
+    while ei < e.len() {
+        let (q_new, hh) = two_sum(q, enow);
+        ei += 1;
+        enow = at(e, ei);
+        q = q_new;
+        if hh != 0.0 {
+            h.push(hh);
+        }
+    }
+    while fi < f.len() {
+        let (q_new, hh) = two_sum(q, fnow);
+        fi += 1;
+        fnow = at(f, fi);
+        q = q_new;
+        if hh != 0.0 {
+            h.push(hh);
+        }
+    }
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
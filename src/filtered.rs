@@ -0,0 +1,413 @@
+//! Predicates that only evaluate geogram's floating point filters.
+//!
+//! The filters are cheap, and certain for most inputs. Where they are not, the predicates below return `None`
+//! instead of falling back to exact arithmetic, so that the uncertain cases can be collected and evaluated later,
+//! e.g. with [`orient_3d`](crate::orient_3d). They do not depend on the [`FilterMode`](crate::FilterMode).
+
+use crate::Sign;
+
+#[cxx::bridge(namespace = "GEOGRAM")]
+mod ffi {
+    unsafe extern "C++" {
+        include!("geogram_predicates/include/geogram_ffi.h");
+
+        fn det_3d_filtered(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> i16;
+
+        fn det_4d_filtered(a: &[f64; 4], b: &[f64; 4], c: &[f64; 4], d: &[f64; 4]) -> i16;
+
+        fn dot_3d_filtered(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> i16;
+
+        fn in_circle_2d_filtered(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], p: &[f64; 2]) -> i16;
+
+        fn in_circle_3d_filtered(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], p: &[f64; 3]) -> i16;
+
+        fn in_sphere_3d_filtered(
+            a: &[f64; 3],
+            b: &[f64; 3],
+            c: &[f64; 3],
+            d: &[f64; 3],
+            p: &[f64; 3],
+        ) -> i16;
+
+        fn orient_2d_filtered(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> i16;
+
+        #[allow(clippy::too_many_arguments)]
+        fn orient_2dlifted_filtered(
+            a: &[f64; 2],
+            b: &[f64; 2],
+            c: &[f64; 2],
+            p: &[f64; 2],
+            h_a: f64,
+            h_b: f64,
+            h_c: f64,
+            h_p: f64,
+        ) -> i16;
+
+        fn orient_3d_filtered(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], d: &[f64; 3]) -> i16;
+
+        #[allow(clippy::too_many_arguments)]
+        fn orient_3dlifted_filtered(
+            a: &[f64; 3],
+            b: &[f64; 3],
+            c: &[f64; 3],
+            d: &[f64; 3],
+            p: &[f64; 3],
+            h_a: f64,
+            h_b: f64,
+            h_c: f64,
+            h_d: f64,
+            h_p: f64,
+        ) -> i16;
+    }
+}
+
+/// Converts the result of a filter, where `0` means uncertain.
+fn certain(sign: i16) -> Option<Sign> {
+    (sign != 0).then_some(sign)
+}
+
+/// Computes the orientation predicate in 2d, with the floating point filter only.
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+///
+/// ### Return values
+/// * `Some(+1)` - if the triangle is oriented counter-clockwise
+/// * `Some(-1)` - if the triangle is oriented clockwise
+/// * `None` - if the filter is uncertain, which includes all flat triangles
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0];
+/// let b = [1.0, 0.0];
+///
+/// assert_eq!(Some(1), gp::orient_2d_filtered(&a, &b, &[0.0, 1.0]));
+/// assert_eq!(Some(-1), gp::orient_2d_filtered(&a, &b, &[0.0, -1.0]));
+/// assert_eq!(None, gp::orient_2d_filtered(&a, &b, &[2.0, 0.0]));
+/// ```
+pub fn orient_2d_filtered(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> Option<Sign> {
+    certain(ffi::orient_2d_filtered(a, b, c))
+}
+
+/// Computes the orientation predicate in 3d, with the floating point filter only.
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron
+///
+/// ### Return values
+/// * `Some(+1)` - if the tetrahedron is oriented positively
+/// * `Some(-1)` - if the tetrahedron is oriented negatively
+/// * `None` - if the filter is uncertain, which includes all flat tetrahedra
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0];
+/// let c = [0.0, 1.0, 0.0];
+///
+/// assert_eq!(Some(1), gp::orient_3d_filtered(&a, &b, &c, &[0.0, 0.0, 1.0]));
+/// assert_eq!(Some(-1), gp::orient_3d_filtered(&a, &c, &b, &[0.0, 0.0, 1.0]));
+/// assert_eq!(None, gp::orient_3d_filtered(&a, &b, &c, &[1.0, 1.0, 0.0]));
+/// ```
+pub fn orient_3d_filtered(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], d: &[f64; 3]) -> Option<Sign> {
+    certain(ffi::orient_3d_filtered(a, b, c, d))
+}
+
+/// Tests whether a point is in the circum-circle of a triangle, with the floating point filter only.
+///
+/// As for [`in_circle_2d_SOS`](crate::in_circle_2d_SOS), the result is inversed if the triangle `a`, `b`, `c` is
+/// oriented clockwise.
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+/// - `p` point to test
+///
+/// ### Return values
+/// * `Some(+1)` - if `p` is inside the circum-circle of `a`, `b`, `c`
+/// * `Some(-1)` - if `p` is outside the circum-circle of `a`, `b`, `c`
+/// * `None` - if the filter is uncertain, which includes all points on the circum-circle
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0];
+/// let b = [2.0, 0.0];
+/// let c = [0.0, 2.0];
+///
+/// assert_eq!(Some(1), gp::in_circle_2d_filtered(&a, &b, &c, &[1.0, 1.0]));
+/// assert_eq!(Some(-1), gp::in_circle_2d_filtered(&a, &b, &c, &[3.0, 3.0]));
+/// // On the circum-circle
+/// assert_eq!(None, gp::in_circle_2d_filtered(&a, &b, &c, &[2.0, 2.0]));
+/// ```
+pub fn in_circle_2d_filtered(
+    a: &[f64; 2],
+    b: &[f64; 2],
+    c: &[f64; 2],
+    p: &[f64; 2],
+) -> Option<Sign> {
+    certain(ffi::in_circle_2d_filtered(a, b, c, p))
+}
+
+/// Tests whether a 3d point is in the circum-circle of a 3d triangle, with the floating point filter only.
+///
+/// As for [`in_circle_3d_SOS`](crate::in_circle_3d_SOS), `p` is tested against the smallest sphere through `a`, `b`,
+/// `c`.
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+/// - `p` point to test
+///
+/// ### Return values
+/// * `Some(+1)` - if `p` is inside the circum-circle of `a`, `b`, `c`
+/// * `Some(-1)` - if `p` is outside the circum-circle of `a`, `b`, `c`
+/// * `None` - if the filter is uncertain, which includes all points on the circum-circle
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0];
+/// let c = [0.0, 2.0, 0.0];
+///
+/// assert_eq!(Some(1), gp::in_circle_3d_filtered(&a, &b, &c, &[1.0, 1.0, 1.0]));
+/// assert_eq!(Some(-1), gp::in_circle_3d_filtered(&a, &b, &c, &[1.0, 1.0, 2.0]));
+/// // On the circum-circle
+/// assert_eq!(None, gp::in_circle_3d_filtered(&a, &b, &c, &[2.0, 2.0, 0.0]));
+/// ```
+pub fn in_circle_3d_filtered(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    p: &[f64; 3],
+) -> Option<Sign> {
+    certain(ffi::in_circle_3d_filtered(a, b, c, p))
+}
+
+/// Tests whether a point is in the circum-sphere of a tetrahedron, with the floating point filter only.
+///
+/// As for [`in_sphere_3d_SOS`](crate::in_sphere_3d_SOS), the tetrahedron `a`, `b`, `c`, `d` needs to be positively
+/// oriented.
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron
+/// - `p` point to test
+///
+/// ### Return values
+/// * `Some(+1)` - if `p` is inside the circum-sphere of `a`, `b`, `c`, `d`
+/// * `Some(-1)` - if `p` is outside the circum-sphere of `a`, `b`, `c`, `d`
+/// * `None` - if the filter is uncertain, which includes all points on the circum-sphere
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0];
+/// let c = [0.0, 2.0, 0.0];
+/// let d = [0.0, 0.0, 2.0];
+///
+/// assert_eq!(Some(1), gp::in_sphere_3d_filtered(&a, &b, &c, &d, &[0.5, 0.5, 0.5]));
+/// assert_eq!(Some(-1), gp::in_sphere_3d_filtered(&a, &b, &c, &d, &[3.0, 3.0, 3.0]));
+/// // On the circum-sphere
+/// assert_eq!(None, gp::in_sphere_3d_filtered(&a, &b, &c, &d, &[2.0, 2.0, 0.0]));
+/// ```
+pub fn in_sphere_3d_filtered(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    d: &[f64; 3],
+    p: &[f64; 3],
+) -> Option<Sign> {
+    certain(ffi::in_sphere_3d_filtered(a, b, c, d, p))
+}
+
+/// Computes the 3d orientation test with lifted points, with the floating point filter only.
+///
+/// See [`orient_2dlifted_SOS`](crate::orient_2dlifted_SOS) for the meaning of the heights.
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+/// - `p` point to test
+/// - `h_a`, `h_b`, `h_c` the heights of the lifted vertices
+/// - `h_p` the height of the lifted point `p`
+///
+/// ### Return values
+/// * `Some(+1)` - if `p'` lies below the plane through `a'`, `b'`, `c'`
+/// * `Some(-1)` - if `p'` lies above the plane through `a'`, `b'`, `c'`
+/// * `None` - if the filter is uncertain, which includes all points `p'` on the plane
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0];
+/// let b = [2.0, 0.0];
+/// let c = [0.0, 2.0];
+/// let p = [0.5, 0.5];
+///
+/// assert_eq!(Some(1), gp::orient_2dlifted_filtered(&a, &b, &c, &p, 0.0, 0.0, 0.0, -1.0));
+/// assert_eq!(Some(-1), gp::orient_2dlifted_filtered(&a, &b, &c, &p, 0.0, 0.0, 0.0, 1.0));
+/// assert_eq!(None, gp::orient_2dlifted_filtered(&a, &b, &c, &p, 0.0, 0.0, 0.0, 0.0));
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn orient_2dlifted_filtered(
+    a: &[f64; 2],
+    b: &[f64; 2],
+    c: &[f64; 2],
+    p: &[f64; 2],
+    h_a: f64,
+    h_b: f64,
+    h_c: f64,
+    h_p: f64,
+) -> Option<Sign> {
+    certain(ffi::orient_2dlifted_filtered(
+        a, b, c, p, h_a, h_b, h_c, h_p,
+    ))
+}
+
+/// Computes the 4d orientation test with lifted points, with the floating point filter only.
+///
+/// See [`orient_3dlifted_SOS`](crate::orient_3dlifted_SOS) for the meaning of the heights.
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron
+/// - `p` point to test
+/// - `h_a`, `h_b`, `h_c`, `h_d` the heights of the lifted vertices
+/// - `h_p` the height of the lifted point `p`
+///
+/// ### Return values
+/// * `Some(+1)` - if `p'` lies below the hyperplane through `a'`, `b'`, `c'`, `d'`
+/// * `Some(-1)` - if `p'` lies above the hyperplane through `a'`, `b'`, `c'`, `d'`
+/// * `None` - if the filter is uncertain, which includes all points `p'` on the hyperplane
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0];
+/// let c = [0.0, 2.0, 0.0];
+/// let d = [0.0, 0.0, 2.0];
+/// let p = [0.5, 0.5, 0.5];
+///
+/// let orient = |h_p| gp::orient_3dlifted_filtered(&a, &b, &c, &d, &p, 0.0, 0.0, 0.0, 0.0, h_p);
+/// assert_eq!(Some(1), orient(-1.0));
+/// assert_eq!(Some(-1), orient(1.0));
+/// assert_eq!(None, orient(0.0));
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn orient_3dlifted_filtered(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    d: &[f64; 3],
+    p: &[f64; 3],
+    h_a: f64,
+    h_b: f64,
+    h_c: f64,
+    h_d: f64,
+    h_p: f64,
+) -> Option<Sign> {
+    certain(ffi::orient_3dlifted_filtered(
+        a, b, c, d, p, h_a, h_b, h_c, h_d, h_p,
+    ))
+}
+
+/// Computes the sign of the determinant of a 3x3 matrix, with the floating point filter only.
+///
+/// ### Parameters
+/// - `a`, `b`, `c` the rows of the matrix
+///
+/// ### Return values
+/// * `Some(sign)` - the sign of the determinant, if the filter is certain
+/// * `None` - if the filter is uncertain, which includes all singular matrices
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [1.0, 0.0, 0.0];
+/// let b = [0.0, 1.0, 0.0];
+///
+/// assert_eq!(Some(1), gp::det_3d_filtered(&a, &b, &[0.0, 0.0, 1.0]));
+/// assert_eq!(Some(-1), gp::det_3d_filtered(&b, &a, &[0.0, 0.0, 1.0]));
+/// assert_eq!(None, gp::det_3d_filtered(&a, &b, &[1.0, 1.0, 0.0]));
+/// ```
+pub fn det_3d_filtered(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> Option<Sign> {
+    certain(ffi::det_3d_filtered(a, b, c))
+}
+
+/// Computes the sign of the determinant of a 4x4 matrix, with the floating point filter only.
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` the rows of the matrix
+///
+/// ### Return values
+/// * `Some(sign)` - the sign of the determinant, if the filter is certain
+/// * `None` - if the filter is uncertain, which includes all singular matrices
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [1.0, 0.0, 0.0, 0.0];
+/// let b = [0.0, 1.0, 0.0, 0.0];
+/// let c = [0.0, 0.0, 1.0, 0.0];
+///
+/// assert_eq!(Some(1), gp::det_4d_filtered(&a, &b, &c, &[0.0, 0.0, 0.0, 1.0]));
+/// assert_eq!(Some(-1), gp::det_4d_filtered(&b, &a, &c, &[0.0, 0.0, 0.0, 1.0]));
+/// assert_eq!(None, gp::det_4d_filtered(&a, &b, &c, &[1.0, 1.0, 1.0, 0.0]));
+/// ```
+pub fn det_4d_filtered(a: &[f64; 4], b: &[f64; 4], c: &[f64; 4], d: &[f64; 4]) -> Option<Sign> {
+    certain(ffi::det_4d_filtered(a, b, c, d))
+}
+
+/// Computes the sign of the dot product of the vectors `ab` and `ac`, with the floating point filter only.
+///
+/// ### Parameters
+/// - `a`, `b`, `c` three 3d points
+///
+/// ### Return values
+/// * `Some(sign)` - the sign of the dot product, if the filter is certain
+/// * `None` - if the filter is uncertain, which includes all orthogonal vectors
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0];
+///
+/// assert_eq!(Some(1), gp::dot_3d_filtered(&a, &b, &[1.0, 1.0, 0.0]));
+/// assert_eq!(Some(-1), gp::dot_3d_filtered(&a, &b, &[-1.0, 1.0, 0.0]));
+/// assert_eq!(None, gp::dot_3d_filtered(&a, &b, &[0.0, 1.0, 0.0]));
+/// ```
+pub fn dot_3d_filtered(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> Option<Sign> {
+    certain(ffi::dot_3d_filtered(a, b, c))
+}
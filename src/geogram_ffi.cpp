@@ -3,6 +3,7 @@
 #include <algorithm>
 #include <functional>
 #include <iomanip>
+#include <mutex>
 #include <set>
 #include <string>
 #include <unordered_map>
@@ -14,32 +15,83 @@ int16_t det_3d(const ::std::array<double, 3> &a, const ::std::array<double, 3> &
     return det;
 }
 
+int16_t det_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c) {
+    GEO::Sign det = GEO::PCK::det_3d_filtered(a.data(), b.data(), c.data());
+    return det;
+}
+
 int16_t det_4d(const ::std::array<double, 4> &a, const ::std::array<double, 4> &b, const ::std::array<double, 4> &c, const ::std::array<double, 4> &d) {
     GEO::Sign det = GEO::PCK::det_4d(a.data(), b.data(), c.data(), d.data());
     return det;
 }
 
+int16_t det_4d_filtered(const ::std::array<double, 4> &a, const ::std::array<double, 4> &b, const ::std::array<double, 4> &c, const ::std::array<double, 4> &d) {
+    GEO::Sign det = GEO::PCK::det_4d_filtered(a.data(), b.data(), c.data(), d.data());
+    return det;
+}
+
 int16_t dot_3d(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c) {
     GEO::Sign dot = GEO::PCK::dot_3d(a.data(), b.data(), c.data());
     return dot;
 }
 
+int16_t dot_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c) {
+    GEO::Sign dot = GEO::PCK::dot_3d_filtered(a.data(), b.data(), c.data());
+    return dot;
+}
+
+uint64_t filters_skipped() {
+    return GEO::PCK::filters_skipped();
+}
+
 int16_t geo_sgn(double x) {
     return GEO::geo_sgn(x);
 };
 
+FilterMode get_filter_mode() {
+    switch (GEO::PCK::get_filter_mode()) {
+    case GEO::PCK::EXACT_ONLY:
+        return FilterMode::ExactOnly;
+    default:
+        return FilterMode::FilterAndExact;
+    }
+}
+
 int16_t in_circle_2d_SOS(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c, const ::std::array<double, 2> &p) {
     GEO::Sign is_in = GEO::PCK::in_circle_2d_SOS(a.data(), b.data(), c.data(), p.data());
     return is_in;
 }
 
+int16_t in_circle_2d_filtered(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c, const ::std::array<double, 2> &p) {
+    GEO::Sign is_in = GEO::PCK::in_circle_2d_filtered(a.data(), b.data(), c.data(), p.data());
+    return is_in;
+}
+
+int16_t in_circle_3d_SOS(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &p) {
+    GEO::Sign is_in = GEO::PCK::in_circle_3d_SOS(a.data(), b.data(), c.data(), p.data());
+    return is_in;
+}
+
+int16_t in_circle_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &p) {
+    GEO::Sign is_in = GEO::PCK::in_circle_3d_filtered(a.data(), b.data(), c.data(), p.data());
+    return is_in;
+}
+
+int16_t in_sphere_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d, const ::std::array<double, 3> &p) {
+    GEO::Sign is_in = GEO::PCK::in_sphere_3d_filtered(a.data(), b.data(), c.data(), d.data(), p.data());
+    return is_in;
+}
+
 int16_t in_sphere_3d_SOS(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d, const ::std::array<double, 3> &p) {
     GEO::Sign is_in = GEO::PCK::in_sphere_3d_SOS(a.data(), b.data(), c.data(), d.data(), p.data());
     return is_in;
 }
 
 void initialize() {
-    GEO::PCK::initialize();
+    // The expansion constants are globals, only compute them once so that
+    // concurrent calls do not race.
+    static std::once_flag initialized;
+    std::call_once(initialized, GEO::PCK::initialize);
 }
 
 int16_t orient_2d(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c) {
@@ -47,16 +99,31 @@ int16_t orient_2d(const ::std::array<double, 2> &a, const ::std::array<double, 2
     return orientation;
 }
 
+int16_t orient_2d_filtered(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c) {
+    GEO::Sign orientation = GEO::PCK::orient_2d_filtered(a.data(), b.data(), c.data());
+    return orientation;
+}
+
 int16_t orient_2dlifted_SOS(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c, const ::std::array<double, 2> &p, double h_a, double h_b, double h_c, double h_p) {
     GEO::Sign regularity = GEO::PCK::orient_2dlifted_SOS(a.data(), b.data(), c.data(), p.data(), h_a, h_b, h_c, h_p);
     return regularity;
 }
 
+int16_t orient_2dlifted_filtered(const ::std::array<double, 2> &a, const ::std::array<double, 2> &b, const ::std::array<double, 2> &c, const ::std::array<double, 2> &p, double h_a, double h_b, double h_c, double h_p) {
+    GEO::Sign regularity = GEO::PCK::orient_2dlifted_filtered(a.data(), b.data(), c.data(), p.data(), h_a, h_b, h_c, h_p);
+    return regularity;
+}
+
 int16_t orient_3d(const ::std::array<double, 3> &a, const  ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d) {
     GEO::Sign orientation = GEO::PCK::orient_3d(a.data(), b.data(), c.data(), d.data());
     return orientation;
 }
 
+int16_t orient_3d_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d) {
+    GEO::Sign orientation = GEO::PCK::orient_3d_filtered(a.data(), b.data(), c.data(), d.data());
+    return orientation;
+}
+
 int16_t orient_3d_inexact(const ::std::array<double, 3> &a, const  ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d) {
     GEO::Sign orientation = GEO::PCK::orient_3d_inexact(a.data(), b.data(), c.data(), d.data());
     return orientation;
@@ -67,6 +134,11 @@ int16_t orient_3dlifted_SOS(const ::std::array<double, 3> &a, const ::std::array
     return regularity;
 }
 
+int16_t orient_3dlifted_filtered(const ::std::array<double, 3> &a, const ::std::array<double, 3> &b, const ::std::array<double, 3> &c, const ::std::array<double, 3> &d, const ::std::array<double, 3> &p, double h_a, double h_b, double h_c, double h_d, double h_p) {
+    GEO::Sign regularity = GEO::PCK::orient_3dlifted_filtered(a.data(), b.data(), c.data(), d.data(), p.data(), h_a, h_b, h_c, h_d, h_p);
+    return regularity;
+}
+
 bool points_are_colinear_3d(const ::std::array<double, 3> &p1, const ::std::array<double, 3> &p2, const ::std::array<double, 3> &p3) {
     return GEO::PCK::points_are_colinear_3d(p1.data(), p2.data(), p3.data());
 }
@@ -79,10 +151,33 @@ bool points_are_identical_3d(const ::std::array<double, 3> &p1, const ::std::arr
     return GEO::PCK::points_are_identical_3d(p1.data(), p2.data());
 }
 
+void set_filter_mode(FilterMode mode) {
+    switch (mode) {
+    case FilterMode::ExactOnly:
+        GEO::PCK::set_filter_mode(GEO::PCK::EXACT_ONLY);
+        break;
+    default:
+        GEO::PCK::set_filter_mode(GEO::PCK::FILTER_AND_EXACT);
+        break;
+    }
+}
+
 void show_stats() {
     GEO::PCK::show_stats();
 }
 
+int16_t side1_3d_SOS(const ::std::array<double, 3> &p0, const ::std::array<double, 3> &p1, const ::std::array<double, 3> &q0) {
+    return GEO::PCK::side1_SOS(p0.data(), p1.data(), q0.data(), 3);
+}
+
+int16_t side1_nd_SOS(rust::Slice<const double> p0, rust::Slice<const double> p1, rust::Slice<const double> q0) {
+    return GEO::PCK::side1_SOS(p0.data(), p1.data(), q0.data(), GEO::coord_index_t(p0.size()));
+}
+
+int16_t side2_3d_SOS(const ::std::array<double, 3> &p0, const ::std::array<double, 3> &p1, const ::std::array<double, 3> &p2, const ::std::array<double, 3> &q0, const ::std::array<double, 3> &q1) {
+    return GEO::PCK::side2_SOS(p0.data(), p1.data(), p2.data(), q0.data(), q1.data(), 3);
+}
+
 void terminate() {
     GEO::PCK::terminate();
 }
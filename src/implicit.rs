@@ -0,0 +1,523 @@
+//! Exact predicates on implicitly defined points.
+//!
+//! An implicit point is not given by `f64` coordinates, but by the construction that defines it, e.g. as the
+//! intersection of two segments. Rounding such a point to doubles moves it off the segments, so that predicates on the
+//! rounded point contradict the ones on the segments. Instead, the predicates of this module evaluate the coordinates
+//! of implicit points in homogeneous form `(x, y, w)`, as polynomials in the explicit input coordinates, like the
+//! _indirect predicates_ of Attene. The polynomials are first evaluated with [`Interval`]s, and only if they cannot
+//! decide with exact [`Expansion`]s, so the results are exact.
+//!
+//! Exact predicates require [`initialize`](crate::initialize) to be called first.
+
+use crate::determinant::det;
+use crate::expansion::Expansion;
+use crate::interval::{Interval, Ring};
+use crate::Sign;
+use std::cmp::Ordering;
+
+/// A point in 2d, given explicitly or as the intersection of two segments.
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::implicit::ImplicitPoint2d;
+///
+/// gp::initialize();
+///
+/// let x = ImplicitPoint2d::segment_intersection(&[0.0, 0.0], &[3.0, 1.0], &[0.0, 1.0], &[3.0, 0.0]).unwrap();
+/// assert_eq!([1.5, 0.5], x.approximate());
+///
+/// let parallel = ImplicitPoint2d::segment_intersection(&[0.0, 0.0], &[3.0, 1.0], &[0.0, 1.0], &[3.0, 2.0]);
+/// assert_eq!(None, parallel);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImplicitPoint2d(Point2d);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Point2d {
+    Explicit([f64; 2]),
+    /// The intersection of the lines through `[u, v]` and `[a, b]`.
+    Intersection([[f64; 2]; 4]),
+}
+
+impl ImplicitPoint2d {
+    /// Creates the intersection point of the lines through two segments.
+    ///
+    /// The point is on both segments iff they intersect, see
+    /// [`segments_intersect_2d`](crate::polygon::segments_intersect_2d).
+    ///
+    /// ### Parameters
+    /// - `u`, `v` the endpoints of the first segment
+    /// - `a`, `b` the endpoints of the second segment
+    ///
+    /// ### Returns
+    /// - the intersection point, or `None` if the lines are parallel or a segment is a single point
+    pub fn segment_intersection(
+        u: &[f64; 2],
+        v: &[f64; 2],
+        a: &[f64; 2],
+        b: &[f64; 2],
+    ) -> Option<Self> {
+        let point = ImplicitPoint2d(Point2d::Intersection([*u, *v, *a, *b]));
+        if point.w_sign() == 0 {
+            None
+        } else {
+            Some(point)
+        }
+    }
+
+    /// Rounds the point to `f64` coordinates.
+    ///
+    /// The coordinates are computed from exact homogeneous coordinates, and are hence within a few ulps of the exact
+    /// point. They should not be passed to predicates, whose results would be inconsistent with the implicit point.
+    pub fn approximate(&self) -> [f64; 2] {
+        match self.0 {
+            Point2d::Explicit(p) => p,
+            Point2d::Intersection(_) => {
+                let [x, y, w] = self.homogeneous::<Expansion>().map(|c| c.estimate());
+                [x / w, y / w]
+            }
+        }
+    }
+
+    /// Tests whether the point is given by `f64` coordinates.
+    pub(crate) fn is_explicit(&self) -> bool {
+        matches!(self.0, Point2d::Explicit(_))
+    }
+
+    /// Computes homogeneous coordinates `(x, y, w)` of the point, with `w` of degree 2 and `x`, `y` of degree 3.
+    fn homogeneous<T: Ring>(&self) -> [T; 3] {
+        match &self.0 {
+            Point2d::Explicit([x, y]) => [T::from(*x), T::from(*y), T::from(1.0)],
+            Point2d::Intersection([u, v, a, b]) => {
+                // The point divides `uv` in the ratio of the signed areas of the triangles `abu` and `abv`.
+                let area = |p: &[f64; 2]| {
+                    let [bx, by, px, py] = [b[0], b[1], p[0], p[1]].map(T::from);
+                    let [ax, ay] = a.map(T::from);
+                    (bx - ax.clone()) * (py - ay.clone()) - (by - ay) * (px - ax)
+                };
+                let (du, dv) = (area(u), area(v));
+                let [x, y] =
+                    [0, 1].map(|k| du.clone() * T::from(v[k]) - dv.clone() * T::from(u[k]));
+                [x, y, du - dv]
+            }
+        }
+    }
+
+    fn w_sign(&self) -> Sign {
+        let [.., w] = self.homogeneous::<Interval>();
+        match w.sign() {
+            Some(sign) => sign,
+            None => {
+                let [.., w] = self.homogeneous::<Expansion>();
+                w.sign()
+            }
+        }
+    }
+}
+
+impl From<[f64; 2]> for ImplicitPoint2d {
+    fn from(p: [f64; 2]) -> Self {
+        ImplicitPoint2d(Point2d::Explicit(p))
+    }
+}
+
+/// A point in 3d, given explicitly or as the intersection of a segment and a triangle.
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::implicit::ImplicitPoint3d;
+///
+/// gp::initialize();
+///
+/// let triangle = [[0.0, 0.0, 1.0], [4.0, 0.0, 1.0], [0.0, 4.0, 1.0]];
+/// let x = ImplicitPoint3d::segment_triangle_intersection(&[1.0, 1.0, 0.0], &[1.0, 1.0, 4.0], &triangle).unwrap();
+/// assert_eq!([1.0, 1.0, 1.0], x.approximate());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImplicitPoint3d(Point3d);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Point3d {
+    Explicit([f64; 3]),
+    /// The intersection of the line through `u`, `v` and the plane through `a`, `b`, `c`.
+    Intersection([[f64; 3]; 5]),
+}
+
+/// The construction of an [`ImplicitPoint3d`], see [`ImplicitPoint3d::construction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Construction3d {
+    Explicit([u64; 3]),
+    Intersection([[u64; 3]; 5]),
+}
+
+impl ImplicitPoint3d {
+    /// Creates the intersection point of the line through a segment and the plane through a triangle.
+    ///
+    /// The point is on the segment and the triangle iff they intersect in a single point, see
+    /// [`segment_triangle_intersection_3d`](crate::intersection::segment_triangle_intersection_3d).
+    ///
+    /// ### Parameters
+    /// - `u`, `v` the endpoints of the segment
+    /// - `triangle` the vertices of the triangle
+    ///
+    /// ### Returns
+    /// - the intersection point, or `None` if the line is parallel to the plane, or if the triangle is degenerate
+    pub fn segment_triangle_intersection(
+        u: &[f64; 3],
+        v: &[f64; 3],
+        triangle: &[[f64; 3]; 3],
+    ) -> Option<Self> {
+        let [a, b, c] = triangle;
+        let point = ImplicitPoint3d(Point3d::Intersection([*u, *v, *a, *b, *c]));
+        if point.w_sign() == 0 {
+            None
+        } else {
+            Some(point)
+        }
+    }
+
+    /// Rounds the point to `f64` coordinates.
+    ///
+    /// The coordinates are computed from exact homogeneous coordinates, and are hence within a few ulps of the exact
+    /// point. They should not be passed to predicates, whose results would be inconsistent with the implicit point.
+    pub fn approximate(&self) -> [f64; 3] {
+        match self.0 {
+            Point3d::Explicit(p) => p,
+            Point3d::Intersection(_) => {
+                let [x, y, z, w] = self.homogeneous::<Expansion>().map(|c| c.estimate());
+                [x / w, y / w, z / w]
+            }
+        }
+    }
+
+    /// Tests whether the point is given by `f64` coordinates.
+    pub(crate) fn is_explicit(&self) -> bool {
+        matches!(self.0, Point3d::Explicit(_))
+    }
+
+    /// Gets the bits of the coordinates that the point is built from, with the endpoints of the segment in a fixed
+    /// order.
+    ///
+    /// Points with the same construction are equal, and can hence be merged without exact arithmetic. Equal points may
+    /// still have different constructions.
+    pub(crate) fn construction(&self) -> Construction3d {
+        let bits = |p: &[f64; 3]| p.map(f64::to_bits);
+        match &self.0 {
+            Point3d::Explicit(p) => Construction3d::Explicit(bits(p)),
+            Point3d::Intersection([u, v, a, b, c]) => {
+                let [u, v] = [bits(u), bits(v)];
+                Construction3d::Intersection([u.min(v), u.max(v), bits(a), bits(b), bits(c)])
+            }
+        }
+    }
+
+    /// Computes homogeneous coordinates `(x, y, z, w)` of the point, with `w` of degree 3 and `x`, `y`, `z` of degree
+    /// 4.
+    fn homogeneous<T: Ring>(&self) -> [T; 4] {
+        match &self.0 {
+            Point3d::Explicit([x, y, z]) => [T::from(*x), T::from(*y), T::from(*z), T::from(1.0)],
+            Point3d::Intersection([u, v, a, b, c]) => {
+                // The point divides `uv` in the ratio of the signed volumes of the tetrahedra `abcu` and `abcv`.
+                let volume = |p: &[f64; 3]| {
+                    let rows: Vec<Vec<T>> = [b, c, p]
+                        .iter()
+                        .map(|q| (0..3).map(|k| T::from(q[k]) - T::from(a[k])).collect())
+                        .collect();
+                    det(&rows)
+                };
+                let (du, dv) = (volume(u), volume(v));
+                let [x, y, z] =
+                    [0, 1, 2].map(|k| du.clone() * T::from(v[k]) - dv.clone() * T::from(u[k]));
+                [x, y, z, du - dv]
+            }
+        }
+    }
+
+    fn w_sign(&self) -> Sign {
+        let [.., w] = self.homogeneous::<Interval>();
+        match w.sign() {
+            Some(sign) => sign,
+            None => {
+                let [.., w] = self.homogeneous::<Expansion>();
+                w.sign()
+            }
+        }
+    }
+}
+
+impl From<[f64; 3]> for ImplicitPoint3d {
+    fn from(p: [f64; 3]) -> Self {
+        ImplicitPoint3d(Point3d::Explicit(p))
+    }
+}
+
+/// Computes the sign of the determinant of the homogeneous coordinates, times the signs of the `w` coordinates.
+///
+/// Returns `None` if a sign is uncertain, which only happens with [`Interval`]s.
+fn homogeneous_orient<T: Ring, const N: usize>(
+    rows: &[[T; N]],
+    sign: impl Fn(&T) -> Option<Sign>,
+) -> Option<Sign> {
+    let mut result = 1;
+    for row in rows {
+        result *= sign(&row[N - 1])?;
+    }
+    let rows: Vec<Vec<T>> = rows.iter().map(|row| row.to_vec()).collect();
+    Some(result * sign(&det(&rows))?)
+}
+
+/// Computes the orientation predicate in 2d, on implicit points.
+///
+/// This is [`orient_2d`](crate::orient_2d) of the exact points, which is called if all points are explicit. The result
+/// is exact for coordinates in [`range::ORIENT_2D_IMPLICIT`](crate::range::ORIENT_2D_IMPLICIT).
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+///
+/// ### Return values
+/// * `+1` - if the triangle is oriented counter-clockwise
+/// * `0` - if the triangle is flat
+/// * `-1` - if the triangle is oriented clockwise
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::implicit::ImplicitPoint2d;
+///
+/// gp::initialize();
+///
+/// // The crossing of the diagonals of a quadrilateral
+/// let [a, b, c, d] = [[0.0, 0.0], [3.0, 0.1], [2.9, 3.0], [0.1, 2.8]];
+/// let x = ImplicitPoint2d::segment_intersection(&a, &c, &b, &d).unwrap();
+///
+/// // The intersection is exactly on both diagonals, which is unlikely for its rounded coordinates
+/// assert_eq!(0, gp::implicit::orient_2d(&a.into(), &c.into(), &x));
+/// assert_eq!(0, gp::implicit::orient_2d(&b.into(), &d.into(), &x));
+/// assert_eq!(1, gp::implicit::orient_2d(&a.into(), &b.into(), &x));
+/// ```
+pub fn orient_2d(a: &ImplicitPoint2d, b: &ImplicitPoint2d, c: &ImplicitPoint2d) -> Sign {
+    if let [Point2d::Explicit(a), Point2d::Explicit(b), Point2d::Explicit(c)] = [a.0, b.0, c.0] {
+        return crate::orient_2d(&a, &b, &c);
+    }
+    let points = [a, b, c];
+    homogeneous_orient(&points.map(|p| p.homogeneous::<Interval>()), Interval::sign)
+        .or_else(|| {
+            homogeneous_orient(&points.map(|p| p.homogeneous::<Expansion>()), |x| {
+                Some(x.sign())
+            })
+        })
+        .unwrap()
+}
+
+/// Computes the orientation predicate in 3d, on implicit points.
+///
+/// This is [`orient_3d`](crate::orient_3d) of the exact points, which is called if all points are explicit. The result
+/// is exact for coordinates in [`range::ORIENT_3D_IMPLICIT`](crate::range::ORIENT_3D_IMPLICIT).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron
+///
+/// ### Return values
+/// * `+1` - if the tetrahedron is oriented positively
+/// * `0` - if the tetrahedron is flat
+/// * `-1` - if the tetrahedron is oriented negatively
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::implicit::ImplicitPoint3d;
+///
+/// gp::initialize();
+///
+/// let triangle = [[0.1, 0.0, 0.3], [4.0, 0.2, 0.1], [0.3, 4.0, 0.7]];
+/// let x = ImplicitPoint3d::segment_triangle_intersection(&[1.0, 1.0, -1.0], &[1.1, 0.9, 3.0], &triangle).unwrap();
+///
+/// let [a, b, c] = triangle.map(ImplicitPoint3d::from);
+/// assert_eq!(0, gp::implicit::orient_3d(&a, &b, &c, &x));
+/// assert_eq!(1, gp::implicit::orient_3d(&a, &b, &c, &[1.0, 1.0, 3.0].into()));
+/// ```
+pub fn orient_3d(
+    a: &ImplicitPoint3d,
+    b: &ImplicitPoint3d,
+    c: &ImplicitPoint3d,
+    d: &ImplicitPoint3d,
+) -> Sign {
+    if let [Point3d::Explicit(a), Point3d::Explicit(b), Point3d::Explicit(c), Point3d::Explicit(d)] =
+        [a.0, b.0, c.0, d.0]
+    {
+        return crate::orient_3d(&a, &b, &c, &d);
+    }
+    // The determinant of the rows `(x, y, z, 1)` is the opposite of the one of `b - a`, `c - a`, `d - a`.
+    let points = [a, b, c, d];
+    -homogeneous_orient(&points.map(|p| p.homogeneous::<Interval>()), Interval::sign)
+        .or_else(|| {
+            homogeneous_orient(&points.map(|p| p.homogeneous::<Expansion>()), |x| {
+                Some(x.sign())
+            })
+        })
+        .unwrap()
+}
+
+/// Compares the `k`-th coordinates of two implicit points in 2d, i.e. computes the sign of `x_a / w_a - x_b / w_b`.
+pub(crate) fn coordinate_cmp_2d(a: &ImplicitPoint2d, b: &ImplicitPoint2d, k: usize) -> Ordering {
+    if let [Point2d::Explicit(a), Point2d::Explicit(b)] = [a.0, b.0] {
+        // Adding `0.0` maps `-0.0` to `0.0`, like in `lexico_cmp_2d`.
+        return (a[k] + 0.0).total_cmp(&(b[k] + 0.0));
+    }
+    fn sign<T: Ring>(
+        a: &[T; 3],
+        b: &[T; 3],
+        k: usize,
+        sign: impl Fn(&T) -> Option<Sign>,
+    ) -> Option<Sign> {
+        let difference = a[k].clone() * b[2].clone() - b[k].clone() * a[2].clone();
+        Some(sign(&a[2])? * sign(&b[2])? * sign(&difference)?)
+    }
+    let sign = sign(
+        &a.homogeneous::<Interval>(),
+        &b.homogeneous::<Interval>(),
+        k,
+        Interval::sign,
+    )
+    .or_else(|| {
+        sign(
+            &a.homogeneous::<Expansion>(),
+            &b.homogeneous::<Expansion>(),
+            k,
+            |x| Some(x.sign()),
+        )
+    })
+    .unwrap();
+    sign.cmp(&0)
+}
+
+/// Compares two implicit points in 2d lexicographically.
+///
+/// This is [`lexico_cmp_2d`](crate::lexico_cmp_2d) of the exact points: they are ordered by their first coordinate,
+/// then by their second one. Unlike a comparison of rounded coordinates, the result is `Equal` iff the points are
+/// identical, e.g. if two intersections of different segments coincide. It is exact for coordinates in
+/// [`range::ORIENT_2D_IMPLICIT`](crate::range::ORIENT_2D_IMPLICIT).
+///
+/// ### Parameters
+/// - `a`, `b` the points to compare
+///
+/// ### Returns
+/// - the order of `a` relative to `b`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::implicit::ImplicitPoint2d;
+/// use std::cmp::Ordering;
+///
+/// gp::initialize();
+///
+/// // Three lines through a common point, at one third of the diagonal of the unit square
+/// let x = ImplicitPoint2d::segment_intersection(&[0.0, 0.0], &[1.0, 1.0], &[0.0, 1.0], &[0.5, 0.0]).unwrap();
+/// let y = ImplicitPoint2d::segment_intersection(&[0.0, 0.0], &[1.0, 1.0], &[1.0, 0.0], &[0.0, 0.5]).unwrap();
+///
+/// assert_eq!(Ordering::Equal, gp::implicit::lexico_cmp_2d(&x, &y));
+/// assert_eq!(Ordering::Less, gp::implicit::lexico_cmp_2d(&x, &[0.5, 0.0].into()));
+/// ```
+pub fn lexico_cmp_2d(a: &ImplicitPoint2d, b: &ImplicitPoint2d) -> Ordering {
+    coordinate_cmp_2d(a, b, 0).then_with(|| coordinate_cmp_2d(a, b, 1))
+}
+
+/// Compares the `k`-th coordinates of two implicit points in 3d, i.e. computes the sign of `x_a / w_a - x_b / w_b`.
+pub(crate) fn coordinate_cmp_3d(a: &ImplicitPoint3d, b: &ImplicitPoint3d, k: usize) -> Ordering {
+    if let [Point3d::Explicit(a), Point3d::Explicit(b)] = [a.0, b.0] {
+        return (a[k] + 0.0).total_cmp(&(b[k] + 0.0));
+    }
+    fn sign<T: Ring>(
+        a: &[T; 4],
+        b: &[T; 4],
+        k: usize,
+        sign: impl Fn(&T) -> Option<Sign>,
+    ) -> Option<Sign> {
+        let difference = a[k].clone() * b[3].clone() - b[k].clone() * a[3].clone();
+        Some(sign(&a[3])? * sign(&b[3])? * sign(&difference)?)
+    }
+    let sign = sign(
+        &a.homogeneous::<Interval>(),
+        &b.homogeneous::<Interval>(),
+        k,
+        Interval::sign,
+    )
+    .or_else(|| {
+        sign(
+            &a.homogeneous::<Expansion>(),
+            &b.homogeneous::<Expansion>(),
+            k,
+            |x| Some(x.sign()),
+        )
+    })
+    .unwrap();
+    sign.cmp(&0)
+}
+
+/// Compares two implicit points in 3d lexicographically.
+///
+/// This is [`lexico_cmp_3d`](crate::lexico_cmp_3d) of the exact points, see [`lexico_cmp_2d`]. It is exact for
+/// coordinates in [`range::ORIENT_3D_IMPLICIT`](crate::range::ORIENT_3D_IMPLICIT).
+///
+/// ### Parameters
+/// - `a`, `b` the points to compare
+///
+/// ### Returns
+/// - the order of `a` relative to `b`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::implicit::ImplicitPoint3d;
+/// use std::cmp::Ordering;
+///
+/// gp::initialize();
+///
+/// // A vertical segment through a horizontal triangle, and through a tilted one that crosses it in the same point
+/// let [u, v] = [[1.0, 1.0, -3.0], [1.0, 1.0, 3.0]];
+/// let flat = ImplicitPoint3d::segment_triangle_intersection(&u, &v, &[[0.0, 0.0, 1.0], [3.0, 0.0, 1.0], [0.0, 3.0, 1.0]]);
+/// let tilted = ImplicitPoint3d::segment_triangle_intersection(&u, &v, &[[0.0, 0.0, 0.0], [3.0, 0.0, 3.0], [0.0, 3.0, 0.0]]);
+///
+/// assert_eq!(Ordering::Equal, gp::implicit::lexico_cmp_3d(&flat.unwrap(), &tilted.unwrap()));
+/// assert_eq!(Ordering::Greater, gp::implicit::lexico_cmp_3d(&flat.unwrap(), &[1.0, 1.0, 0.5].into()));
+/// ```
+pub fn lexico_cmp_3d(a: &ImplicitPoint3d, b: &ImplicitPoint3d) -> Ordering {
+    coordinate_cmp_3d(a, b, 0)
+        .then_with(|| coordinate_cmp_3d(a, b, 1))
+        .then_with(|| coordinate_cmp_3d(a, b, 2))
+}
+
+/// Computes the orientation predicate in 2d, on the projections of implicit points in 3d along a coordinate axis.
+///
+/// The projection drops the coordinate `axis`, and keeps the following ones in cyclic order, like the projections in
+/// [`intersection`](crate::intersection).
+pub(crate) fn orient_2d_projected(
+    a: &ImplicitPoint3d,
+    b: &ImplicitPoint3d,
+    c: &ImplicitPoint3d,
+    axis: usize,
+) -> Sign {
+    let [i, j] = [(axis + 1) % 3, (axis + 2) % 3];
+    if let [Point3d::Explicit(a), Point3d::Explicit(b), Point3d::Explicit(c)] = [a.0, b.0, c.0] {
+        return crate::orient_2d(&[a[i], a[j]], &[b[i], b[j]], &[c[i], c[j]]);
+    }
+    fn project<T: Ring>(p: &ImplicitPoint3d, [i, j]: [usize; 2]) -> [T; 3] {
+        let [x, y, z, w] = p.homogeneous::<T>();
+        let [xi, xj] = [i, j].map(|k| [&x, &y, &z][k].clone());
+        [xi, xj, w]
+    }
+    let points = [a, b, c];
+    homogeneous_orient(
+        &points.map(|p| project::<Interval>(p, [i, j])),
+        Interval::sign,
+    )
+    .or_else(|| {
+        homogeneous_orient(&points.map(|p| project::<Expansion>(p, [i, j])), |x| {
+            Some(x.sign())
+        })
+    })
+    .unwrap()
+}
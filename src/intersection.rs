@@ -0,0 +1,690 @@
+//! Exact intersection tests for segments and triangles in 3d.
+//!
+//! Segments are given as arrays of two endpoints, triangles and planes as arrays of three vertices. The tests only rely
+//! on the signs of [`orient_3d`], and of [`orient_2d`] on projections when the triangles are coplanar, so they never
+//! construct intersection points and the results are exact. Degenerate triangles, whose vertices are colinear, are
+//! treated as the segment they span.
+//!
+//! Exact predicates require [`initialize`](crate::initialize) to be called first.
+
+use crate::bvh::{bounding_box, Bvh};
+use crate::polygon::segments_intersect_2d;
+use crate::{lexico_cmp_2d, lexico_cmp_3d, orient_2d, orient_3d, points_are_colinear_3d};
+use std::cmp::Ordering;
+
+/// The ways two triangles can intersect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriangleIntersection {
+    /// The triangles have no point in common.
+    Disjoint,
+    /// The triangles have a single point in common, which is a vertex of at least one of them.
+    VertexTouch,
+    /// The interiors of the triangles are disjoint, but the triangles have a segment in common, or a single point
+    /// where an edge of each crosses the other one.
+    EdgeTouch,
+    /// The triangles are coplanar, and their interiors overlap.
+    CoplanarOverlap,
+    /// The triangles are not coplanar, and their interiors intersect.
+    Crossing,
+}
+
+/// Classifies the intersection of two triangles in 3d.
+///
+/// ### Parameters
+/// - `p` the vertices of the first triangle
+/// - `q` the vertices of the second triangle
+///
+/// ### Returns
+/// - how the triangles intersect, which does not depend on the order of the triangles or of their vertices
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::intersection::TriangleIntersection;
+///
+/// gp::initialize();
+///
+/// let p = [[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0]];
+///
+/// let crossing = [[1.0, 1.0, -1.0], [1.0, 1.0, 1.0], [5.0, 5.0, 0.0]];
+/// assert_eq!(TriangleIntersection::Crossing, gp::intersection::triangle_triangle_intersection_3d(&p, &crossing));
+///
+/// // Sharing an edge, as neighbors in a mesh do
+/// let neighbor = [[4.0, 0.0, 0.0], [0.0, 0.0, 0.0], [2.0, -2.0, 1.0]];
+/// assert_eq!(TriangleIntersection::EdgeTouch, gp::intersection::triangle_triangle_intersection_3d(&p, &neighbor));
+///
+/// let apex = [[1.0, 1.0, 0.0], [2.0, 1.0, 1.0], [1.0, 2.0, 1.0]];
+/// assert_eq!(TriangleIntersection::VertexTouch, gp::intersection::triangle_triangle_intersection_3d(&p, &apex));
+///
+/// let overlap = [[1.0, 1.0, 0.0], [5.0, 1.0, 0.0], [1.0, 5.0, 0.0]];
+/// assert_eq!(TriangleIntersection::CoplanarOverlap, gp::intersection::triangle_triangle_intersection_3d(&p, &overlap));
+///
+/// let above = [[0.0, 0.0, 1e-300], [4.0, 0.0, 1e-300], [0.0, 4.0, 1e-300]];
+/// assert_eq!(TriangleIntersection::Disjoint, gp::intersection::triangle_triangle_intersection_3d(&p, &above));
+/// ```
+pub fn triangle_triangle_intersection_3d(
+    p: &[[f64; 3]; 3],
+    q: &[[f64; 3]; 3],
+) -> TriangleIntersection {
+    let p_flat = points_are_colinear_3d(&p[0], &p[1], &p[2]);
+    let q_flat = points_are_colinear_3d(&q[0], &q[1], &q[2]);
+    classify(p, q, p_flat, q_flat)
+}
+
+/// Classifies the intersection of two triangles, given whether they are degenerate.
+fn classify(
+    p: &[[f64; 3]; 3],
+    q: &[[f64; 3]; 3],
+    p_flat: bool,
+    q_flat: bool,
+) -> TriangleIntersection {
+    match (p_flat, q_flat) {
+        (false, false) => triangle_triangle(p, q),
+        (true, false) => segment_triangle(p, q),
+        (false, true) => segment_triangle(q, p),
+        (true, true) => segment_segment(p, q),
+    }
+}
+
+/// An intersection between two faces of a mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FaceIntersection {
+    /// The index of the first face.
+    pub first: usize,
+    /// The index of the second face, with `first < second`.
+    pub second: usize,
+    /// How the faces intersect.
+    pub kind: TriangleIntersection,
+}
+
+/// Finds the self-intersections of a triangle mesh.
+///
+/// Reports every pair of faces that intersect, apart from what they are expected to share: faces without common
+/// vertices must be disjoint, faces with one common vertex must only touch there and faces with a common edge must
+/// only touch along it. Hence folds between neighboring faces are reported as well. The faces are organized in a
+/// bounding volume hierarchy, such that [`triangle_triangle_intersection_3d`] only runs on faces with overlapping
+/// bounding boxes.
+///
+/// ### Parameters
+/// - `points` the vertices of the mesh
+/// - `triangles` the faces of the mesh, as triples of vertex indices
+///
+/// ### Returns
+/// - the intersecting pairs of faces, sorted by `first` and `second`
+///
+/// # Panics
+/// If a face references a vertex that is out of range.
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::intersection::{FaceIntersection, TriangleIntersection};
+///
+/// gp::initialize();
+///
+/// let points = [
+///     [0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [2.0, -2.0, 1.0],
+///     [1.0, 1.0, -1.0], [1.0, 1.0, 1.0], [5.0, 5.0, 0.0],
+/// ];
+/// // The first two faces are neighbors, the last one pierces the first one
+/// let triangles = [[0, 1, 2], [1, 0, 3], [4, 5, 6]];
+///
+/// let intersections = gp::intersection::mesh_self_intersections(&points, &triangles);
+/// assert_eq!(
+///     intersections,
+///     vec![FaceIntersection { first: 0, second: 2, kind: TriangleIntersection::Crossing }]
+/// );
+/// ```
+pub fn mesh_self_intersections(
+    points: &[[f64; 3]],
+    triangles: &[[usize; 3]],
+) -> Vec<FaceIntersection> {
+    let boxes = triangles
+        .iter()
+        .map(|t| bounding_box(t.iter().map(|&v| &points[v])))
+        .collect();
+    let flat: Vec<bool> = triangles
+        .iter()
+        .map(|t| points_are_colinear_3d(&points[t[0]], &points[t[1]], &points[t[2]]))
+        .collect();
+    let mut intersections = Vec::new();
+    Bvh::new(boxes).self_overlaps(|first, second| {
+        let (s, t) = (&triangles[first], &triangles[second]);
+        let mut shared: Vec<usize> = s.iter().copied().filter(|v| t.contains(v)).collect();
+        shared.sort_unstable();
+        shared.dedup();
+        let expected = match shared[..] {
+            [] => Some(TriangleIntersection::Disjoint),
+            [_] => Some(TriangleIntersection::VertexTouch),
+            [u, v] => {
+                // Faces that share an edge and are not coplanar only have that edge in common. This shortcut saves
+                // the full test for most pairs of neighbors.
+                let a = s.iter().find(|w| !shared.contains(w));
+                let b = t.iter().find(|w| !shared.contains(w));
+                if let (Some(&a), Some(&b)) = (a, b) {
+                    if orient(&points[u], &points[v], &points[a], &points[b]) != 0 {
+                        return;
+                    }
+                }
+                Some(TriangleIntersection::EdgeTouch)
+            }
+            // Duplicate faces always intersect beyond what they share.
+            _ => None,
+        };
+        let (p, q) = (s.map(|v| points[v]), t.map(|v| points[v]));
+        let kind = classify(&p, &q, flat[first], flat[second]);
+        if Some(kind) != expected {
+            intersections.push(FaceIntersection {
+                first,
+                second,
+                kind,
+            });
+        }
+    });
+    intersections.sort_unstable_by_key(|x| (x.first, x.second));
+    intersections
+}
+
+/// The ways a segment can intersect a triangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentTriangleIntersection {
+    /// The segment and the triangle have no point in common.
+    Miss,
+    /// The segment meets the triangle in a single point, in the interior of the triangle.
+    Interior,
+    /// The segment meets the triangle in a single point, in the relative interior of an edge.
+    Edge,
+    /// The segment meets the triangle in a single point, which is a vertex of the triangle.
+    Vertex,
+    /// The segment and the triangle meet, and lie in a common plane: the segment is in the plane of the triangle, or
+    /// the triangle is degenerate. They have a segment or a single point in common, e.g. a vertex of the triangle.
+    Coplanar,
+}
+
+/// Classifies the intersection of a segment with a triangle in 3d.
+///
+/// The classification only depends on signs of [`orient_3d`], so a segment that crosses an edge shared by two
+/// triangles is reported as [`SegmentTriangleIntersection::Edge`] by both of them, and never misses both. A segment
+/// whose endpoints are identical is located as a point. A segment in the plane of the triangle, and a segment that
+/// meets a degenerate triangle, whose vertices are colinear, are [`SegmentTriangleIntersection::Coplanar`] with it,
+/// whether they have a segment or a single point in common.
+///
+/// ### Parameters
+/// - `s` the endpoints of the segment
+/// - `t` the vertices of the triangle
+///
+/// ### Returns
+/// - where the segment meets the triangle, which does not depend on the order of the endpoints or of the vertices
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::intersection::SegmentTriangleIntersection;
+///
+/// gp::initialize();
+///
+/// let t = [[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0]];
+/// let classify = |s| gp::intersection::segment_triangle_intersection_3d(&s, &t);
+///
+/// assert_eq!(SegmentTriangleIntersection::Interior, classify([[1.0, 1.0, -1.0], [1.0, 1.0, 1.0]]));
+/// assert_eq!(SegmentTriangleIntersection::Edge, classify([[2.0, 2.0, -1.0], [2.0, 2.0, 1.0]]));
+/// assert_eq!(SegmentTriangleIntersection::Vertex, classify([[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]]));
+/// assert_eq!(SegmentTriangleIntersection::Coplanar, classify([[1.0, 1.0, 0.0], [5.0, 5.0, 0.0]]));
+/// // In the plane, a single point in common is not told apart from a segment
+/// assert_eq!(SegmentTriangleIntersection::Coplanar, classify([[0.0, 0.0, 0.0], [-1.0, -1.0, 0.0]]));
+/// assert_eq!(SegmentTriangleIntersection::Miss, classify([[1.0, 1.0, 1e-300], [1.0, 1.0, 1.0]]));
+/// // Through the edge, and just next to it
+/// assert_eq!(SegmentTriangleIntersection::Edge, classify([[1.0, 1.0, -1.0], [3.0, 3.0, 1.0]]));
+/// assert_eq!(SegmentTriangleIntersection::Miss, classify([[1.0, 1.0, -1.0], [3.0, 3.0 + 4.0 * f64::EPSILON, 1.0]]));
+/// ```
+pub fn segment_triangle_intersection_3d(
+    s: &[[f64; 3]; 2],
+    t: &[[f64; 3]; 3],
+) -> SegmentTriangleIntersection {
+    let [u, v] = s;
+    let coplanar = || {
+        let degenerate = [*u, *v, *v];
+        if triangle_triangle_intersection_3d(&degenerate, t) == TriangleIntersection::Disjoint {
+            SegmentTriangleIntersection::Miss
+        } else {
+            SegmentTriangleIntersection::Coplanar
+        }
+    };
+    if points_are_colinear_3d(&t[0], &t[1], &t[2]) {
+        return coplanar();
+    }
+    let o = [u, v].map(|w| orient(&t[0], &t[1], &t[2], w));
+    if o[0] == o[1] && o[0] != 0 {
+        return SegmentTriangleIntersection::Miss;
+    }
+    let sides = if u == v {
+        if o[0] != 0 {
+            return SegmentTriangleIntersection::Miss;
+        }
+        let axis = projection_axis(t);
+        let t = counter_clockwise(t.each_ref().map(|w| project(w, axis)));
+        let u = project(u, axis);
+        [0, 1, 2].map(|i| orient_2d(&t[i], &t[(i + 1) % 3], &u))
+    } else if o == [0; 2] {
+        return coplanar();
+    } else {
+        // The segment meets the plane in a single point, on the line through `a` and `b`.
+        let (a, b) = if o[0] != 0 { (u, v) } else { (v, u) };
+        [0, 1, 2].map(|i| orient(a, b, &t[i], &t[(i + 1) % 3]))
+    };
+    // The point is inside the triangle iff it is on the same side of all edges, up to a common sign. Outside, it is
+    // on both sides of some edges, as the signed areas of the triangles it spans with the edges add up to the area of
+    // the triangle.
+    if sides.contains(&1) && sides.contains(&-1) {
+        return SegmentTriangleIntersection::Miss;
+    }
+    match sides.iter().filter(|&&side| side == 0).count() {
+        0 => SegmentTriangleIntersection::Interior,
+        1 => SegmentTriangleIntersection::Edge,
+        _ => SegmentTriangleIntersection::Vertex,
+    }
+}
+
+/// Classifies the intersection of a segment with a triangle in 3d.
+///
+/// Same as [`segment_triangle_intersection_3d`], whose name follows [`triangle_triangle_intersection_3d`].
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::intersection::SegmentTriangleIntersection;
+///
+/// gp::initialize();
+///
+/// let t = [[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0]];
+/// let s = [[1.0, 1.0, -1.0], [1.0, 1.0, 1.0]];
+/// assert_eq!(SegmentTriangleIntersection::Interior, gp::intersection::segment_triangle_intersect_3d(&s, &t));
+/// ```
+pub fn segment_triangle_intersect_3d(
+    s: &[[f64; 3]; 2],
+    t: &[[f64; 3]; 3],
+) -> SegmentTriangleIntersection {
+    segment_triangle_intersection_3d(s, t)
+}
+
+/// The positions of a segment relative to a plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentPlaneSide {
+    /// Both endpoints are strictly on the positive side of the plane, where [`orient_3d`] is `+1`.
+    Positive,
+    /// Both endpoints are strictly on the negative side of the plane.
+    Negative,
+    /// The endpoints are strictly on opposite sides, so the segment crosses the plane in its relative interior.
+    Crossing,
+    /// One endpoint is in the plane, and the other one is not.
+    Touching,
+    /// Both endpoints are in the plane.
+    Coplanar,
+}
+
+/// Classifies the position of a segment relative to the plane through three points.
+///
+/// The plane is oriented like [`orient_3d`], i.e. `d` is on its positive side if `orient_3d(a, b, c, d)` is `+1`. If
+/// the three points are colinear, they do not define a plane, and every segment is reported as
+/// [`SegmentPlaneSide::Coplanar`].
+///
+/// ### Parameters
+/// - `s` the endpoints of the segment
+/// - `plane` three points of the plane
+///
+/// ### Returns
+/// - on which side of the plane the segment is, which does not depend on the order of the endpoints
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::intersection::SegmentPlaneSide;
+///
+/// gp::initialize();
+///
+/// let plane = [[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0]];
+/// let side = |s| gp::intersection::segment_plane_side(&s, &plane);
+///
+/// assert_eq!(SegmentPlaneSide::Positive, side([[1.0, 1.0, 1.0], [9.0, 9.0, 2.0]]));
+/// assert_eq!(SegmentPlaneSide::Negative, side([[1.0, 1.0, -1.0], [9.0, 9.0, -1e-300]]));
+/// assert_eq!(SegmentPlaneSide::Crossing, side([[1.0, 1.0, -1.0], [9.0, 9.0, 1.0]]));
+/// assert_eq!(SegmentPlaneSide::Touching, side([[1.0, 1.0, 0.0], [9.0, 9.0, 1.0]]));
+/// assert_eq!(SegmentPlaneSide::Coplanar, side([[1.0, 1.0, 0.0], [9.0, 9.0, 0.0]]));
+/// ```
+pub fn segment_plane_side(s: &[[f64; 3]; 2], plane: &[[f64; 3]; 3]) -> SegmentPlaneSide {
+    let [a, b, c] = plane;
+    let o = s.each_ref().map(|w| orient(a, b, c, w));
+    match o {
+        [0, 0] => SegmentPlaneSide::Coplanar,
+        [0, _] | [_, 0] => SegmentPlaneSide::Touching,
+        [1, 1] => SegmentPlaneSide::Positive,
+        [-1, -1] => SegmentPlaneSide::Negative,
+        _ => SegmentPlaneSide::Crossing,
+    }
+}
+
+/// Classifies the intersection of two non-degenerate triangles.
+fn triangle_triangle(p: &[[f64; 3]; 3], q: &[[f64; 3]; 3]) -> TriangleIntersection {
+    let o1 = p.each_ref().map(|v| orient(&q[0], &q[1], &q[2], v));
+    let o2 = q.each_ref().map(|v| orient(&p[0], &p[1], &p[2], v));
+    if on_one_side(&o1) || on_one_side(&o2) {
+        return TriangleIntersection::Disjoint;
+    }
+    if o1 == [0; 3] {
+        return coplanar_triangles(p, q);
+    }
+
+    // Both triangles meet the line where the planes intersect, in the segments `s1` and `s2`. Their endpoints are
+    // compared along the direction `n1 x n2`, where `n1` and `n2` are the normals of `p` and `q`.
+    let s1 = Section::new(&o1, false);
+    let s2 = Section::new(&o2, true);
+    let cmp = |x: [usize; 2], y: [usize; 2]| {
+        // The sign of `y - x` is given by the orientation of the lines through both edges.
+        let s = o1[x[0]] * o2[y[0]] * orient(&p[x[0]], &p[x[1]], &q[y[0]], &q[y[1]]);
+        0.cmp(&s)
+    };
+    let [lo1, hi1] = s1.ends;
+    let [lo2, hi2] = s2.ends;
+    if cmp(hi1, lo2).is_lt() || cmp(lo1, hi2).is_gt() {
+        return TriangleIntersection::Disjoint;
+    }
+    let overlap = lo1 != hi1 && lo2 != hi2 && cmp(hi1, lo2).is_gt() && cmp(lo1, hi2).is_lt();
+    if overlap {
+        // The relative interior of a section is interior to its triangle iff the triangle crosses the other plane.
+        return if s1.crossing && s2.crossing {
+            TriangleIntersection::Crossing
+        } else {
+            TriangleIntersection::EdgeTouch
+        };
+    }
+
+    // The sections share a single point, which is an endpoint of both. It is a vertex iff some endpoint in both
+    // sections is.
+    let at_vertex = s1
+        .ends
+        .iter()
+        .any(|&x| o1[x[1]] == 0 && cmp(x, lo2).is_ge() && cmp(x, hi2).is_le())
+        || s2
+            .ends
+            .iter()
+            .any(|&y| o2[y[1]] == 0 && cmp(lo1, y).is_le() && cmp(hi1, y).is_ge());
+    if at_vertex {
+        TriangleIntersection::VertexTouch
+    } else {
+        TriangleIntersection::EdgeTouch
+    }
+}
+
+/// The intersection of a non-degenerate triangle with the plane of the other triangle, i.e. a segment on the line
+/// where both planes intersect.
+struct Section {
+    /// The endpoints, ordered along the line. Each is the point where the line through two vertices `[a, b]` meets the
+    /// plane, where `a` is not in the plane. It is the vertex `b` if `b` is in the plane.
+    ends: [[usize; 2]; 2],
+    /// Whether the triangle has vertices strictly on both sides of the plane.
+    crossing: bool,
+}
+
+impl Section {
+    /// Computes the section from the sides `o` of the vertices w.r.t. the plane, which are neither all zero, nor all
+    /// on the same side.
+    ///
+    /// The endpoints are ordered along `n x m`, where `n` is the normal of the triangle and `m` the normal of the
+    /// plane, or along `m x n` if `reversed` is set.
+    fn new(o: &[i16; 3], reversed: bool) -> Self {
+        let next = |k: usize| (k + 1) % 3;
+        let prev = |k: usize| (k + 2) % 3;
+        // `sigma` is the orientation of the endpoints and the vertex `a` w.r.t. the triangle.
+        let (ends, a, sigma) = match o.iter().filter(|&&s| s == 0).count() {
+            0 => {
+                // A single vertex on one side, whose edges cross the plane.
+                let k = (0..3)
+                    .find(|&k| o[k] != o[next(k)] && o[k] != o[prev(k)])
+                    .unwrap();
+                ([[k, next(k)], [k, prev(k)]], k, 1)
+            }
+            1 => {
+                let k = (0..3).find(|&k| o[k] == 0).unwrap();
+                if o[next(k)] == o[prev(k)] {
+                    // A single vertex in the plane.
+                    ([[next(k), k], [next(k), k]], next(k), 1)
+                } else {
+                    // A vertex in the plane, and the opposite edge crossing it.
+                    ([[next(k), k], [next(k), prev(k)]], next(k), -1)
+                }
+            }
+            _ => {
+                // An edge in the plane.
+                let k = (0..3).find(|&k| o[k] != 0).unwrap();
+                ([[k, next(k)], [k, prev(k)]], k, 1)
+            }
+        };
+        // The endpoints are ordered along `n x m` iff `a` is to their right within the triangle exactly when it is
+        // above the plane.
+        let forward = (sigma * o[a] < 0) != reversed;
+        Section {
+            ends: if forward { ends } else { [ends[1], ends[0]] },
+            crossing: o.contains(&1) && o.contains(&-1),
+        }
+    }
+}
+
+/// Classifies the intersection of two coplanar, non-degenerate triangles.
+fn coplanar_triangles(p: &[[f64; 3]; 3], q: &[[f64; 3]; 3]) -> TriangleIntersection {
+    let axis = projection_axis(q);
+    let p = counter_clockwise(p.each_ref().map(|v| project(v, axis)));
+    let q = counter_clockwise(q.each_ref().map(|v| project(v, axis)));
+
+    // By the separating axis theorem the triangles are disjoint iff the other one is strictly outside of some edge,
+    // and their interiors are disjoint iff it is outside of some edge or on it.
+    let mut touching = None;
+    for (t, other) in [(&p, &q), (&q, &p)] {
+        for i in 0..3 {
+            let (a, b) = (&t[i], &t[(i + 1) % 3]);
+            let sides = other.each_ref().map(|v| orient_2d(a, b, v));
+            if sides.iter().all(|&s| s < 0) {
+                return TriangleIntersection::Disjoint;
+            }
+            if touching.is_none() && sides.iter().all(|&s| s <= 0) {
+                touching = Some((a, b, other, sides));
+            }
+        }
+    }
+    let Some((a, b, other, sides)) = touching else {
+        return TriangleIntersection::CoplanarOverlap;
+    };
+
+    // The triangles only share points on the line through `ab`, where the other triangle has one vertex or an edge.
+    let on_line: Vec<&[f64; 2]> = (0..3)
+        .filter(|&i| sides[i] == 0)
+        .map(|i| &other[i])
+        .collect();
+    if collinear_overlap_is_point([a, b], &on_line) {
+        TriangleIntersection::VertexTouch
+    } else {
+        TriangleIntersection::EdgeTouch
+    }
+}
+
+/// Classifies the intersection of the degenerate triangle `p` with the non-degenerate triangle `q`.
+fn segment_triangle(p: &[[f64; 3]; 3], q: &[[f64; 3]; 3]) -> TriangleIntersection {
+    let o = p.each_ref().map(|v| orient(&q[0], &q[1], &q[2], v));
+    if on_one_side(&o) {
+        return TriangleIntersection::Disjoint;
+    }
+    let [u, v] = span(p);
+    if o == [0; 3] {
+        return coplanar_segment_triangle([u, v], q);
+    }
+
+    // The segment meets the plane in a single point, on the line through `a` and `b`.
+    let (a, b) = if orient(&q[0], &q[1], &q[2], u) != 0 {
+        (u, v)
+    } else {
+        (v, u)
+    };
+    let sides = [0, 1, 2].map(|i| orient(a, b, &q[i], &q[(i + 1) % 3]));
+    if sides.contains(&1) && sides.contains(&-1) {
+        return TriangleIntersection::Disjoint;
+    }
+    // The point is a vertex of `p` if one is in the plane, and a vertex of `q` if it is on two of its edges.
+    if o.contains(&0) || sides.iter().filter(|&&s| s == 0).count() >= 2 {
+        TriangleIntersection::VertexTouch
+    } else {
+        TriangleIntersection::EdgeTouch
+    }
+}
+
+/// Classifies the intersection of the segment `uv` with a coplanar, non-degenerate triangle `q`.
+fn coplanar_segment_triangle([u, v]: [&[f64; 3]; 2], q: &[[f64; 3]; 3]) -> TriangleIntersection {
+    let axis = projection_axis(q);
+    let (u, v) = (project(u, axis), project(v, axis));
+    let q = counter_clockwise(q.each_ref().map(|w| project(w, axis)));
+
+    let edge_sides = [0, 1, 2].map(|i| [&u, &v].map(|w| orient_2d(&q[i], &q[(i + 1) % 3], w)));
+    if edge_sides.iter().any(|s| s[0] < 0 && s[1] < 0) {
+        return TriangleIntersection::Disjoint;
+    }
+    if u == v {
+        return TriangleIntersection::VertexTouch;
+    }
+    let line_sides = q.each_ref().map(|w| orient_2d(&u, &v, w));
+    if on_one_side(&line_sides) {
+        return TriangleIntersection::Disjoint;
+    }
+
+    let is_point = if line_sides.contains(&1) && line_sides.contains(&-1) {
+        // The line crosses the interior of `q`, the segment only touches it if it is outside of some edge or on it.
+        edge_sides.iter().any(|s| s[0] <= 0 && s[1] <= 0)
+    } else {
+        // The line touches `q` in one vertex or along an edge.
+        let on_line: Vec<&[f64; 2]> = (0..3)
+            .filter(|&i| line_sides[i] == 0)
+            .map(|i| &q[i])
+            .collect();
+        collinear_overlap_is_point([&u, &v], &on_line)
+    };
+    if is_point {
+        TriangleIntersection::VertexTouch
+    } else {
+        TriangleIntersection::EdgeTouch
+    }
+}
+
+/// Classifies the intersection of two degenerate triangles.
+fn segment_segment(p: &[[f64; 3]; 3], q: &[[f64; 3]; 3]) -> TriangleIntersection {
+    let [u1, v1] = span(p);
+    let [u2, v2] = span(q);
+    if orient(u1, v1, u2, v2) != 0 {
+        return TriangleIntersection::Disjoint;
+    }
+    // Project along an axis that keeps the plane of the four points, or their line if they are colinear.
+    let axis = (0..3)
+        .find(|&k| {
+            let w = [u1, v1, u2, v2].map(|w| project(w, k));
+            [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]
+                .iter()
+                .any(|&[i, j, l]| orient_2d(&w[i], &w[j], &w[l]) != 0)
+        })
+        .or_else(|| (0..3).find(|&k| [v1, u2, v2].iter().any(|w| project(w, k) != project(u1, k))))
+        .unwrap_or(0);
+    let p = p.each_ref().map(|w| project(w, axis));
+    let q = q.each_ref().map(|w| project(w, axis));
+    let [a, b, c, d] = [u1, v1, u2, v2].map(|w| project(w, axis));
+    if !segments_intersect_2d(&a, &b, &c, &d) {
+        return TriangleIntersection::Disjoint;
+    }
+
+    let collinear = orient_2d(&a, &b, &c) == 0 && orient_2d(&a, &b, &d) == 0;
+    let at_vertex = if collinear {
+        // Segments on a common line only have a single point in common at their endpoints.
+        collinear_overlap_is_point([&a, &b], &[&c, &d])
+    } else {
+        // The segments cross in a single point, which is a vertex iff it is on the line through the other segment.
+        p.iter().any(|w| orient_2d(&c, &d, w) == 0) || q.iter().any(|w| orient_2d(&a, &b, w) == 0)
+    };
+    if at_vertex {
+        TriangleIntersection::VertexTouch
+    } else {
+        TriangleIntersection::EdgeTouch
+    }
+}
+
+/// Tests whether the segment `ab` and the collinear points or segment `on_line` have a single point in common, given
+/// that they intersect.
+fn collinear_overlap_is_point([a, b]: [&[f64; 2]; 2], on_line: &[&[f64; 2]]) -> bool {
+    let (lo1, hi1) = lexico_extremes(&[a, b]);
+    let (lo2, hi2) = lexico_extremes(on_line);
+    let lo = if lexico_cmp_2d(lo1, lo2).is_ge() {
+        lo1
+    } else {
+        lo2
+    };
+    let hi = if lexico_cmp_2d(hi1, hi2).is_le() {
+        hi1
+    } else {
+        hi2
+    };
+    lexico_cmp_2d(lo, hi) == Ordering::Equal
+}
+
+fn lexico_extremes<'a>(points: &[&'a [f64; 2]]) -> (&'a [f64; 2], &'a [f64; 2]) {
+    let lo = points
+        .iter()
+        .copied()
+        .min_by(|x, y| lexico_cmp_2d(x, y))
+        .unwrap();
+    let hi = points
+        .iter()
+        .copied()
+        .max_by(|x, y| lexico_cmp_2d(x, y))
+        .unwrap();
+    (lo, hi)
+}
+
+/// Computes [`orient_3d`], with a shortcut for tetrahedra with repeated vertices.
+///
+/// Neighboring faces share vertices, and the exact path of [`orient_3d`] is slow to find out that such a tetrahedron is
+/// flat.
+pub(crate) fn orient(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], d: &[f64; 3]) -> i16 {
+    if a == b || a == c || a == d || b == c || b == d || c == d {
+        0
+    } else {
+        orient_3d(a, b, c, d)
+    }
+}
+
+/// Tests whether all signs are positive, or all are negative.
+pub(crate) fn on_one_side(signs: &[i16; 3]) -> bool {
+    signs.iter().all(|&s| s > 0) || signs.iter().all(|&s| s < 0)
+}
+
+/// Gets the extreme vertices of a degenerate triangle, i.e. the endpoints of the segment it spans.
+fn span(p: &[[f64; 3]; 3]) -> [&[f64; 3]; 2] {
+    let lo = p.iter().min_by(|x, y| lexico_cmp_3d(x, y)).unwrap();
+    let hi = p.iter().max_by(|x, y| lexico_cmp_3d(x, y)).unwrap();
+    [lo, hi]
+}
+
+/// Finds a coordinate axis, along which the non-degenerate triangle `q` projects to a non-degenerate triangle.
+pub(crate) fn projection_axis(q: &[[f64; 3]; 3]) -> usize {
+    (0..3)
+        .find(|&k| orient_2d(&project(&q[0], k), &project(&q[1], k), &project(&q[2], k)) != 0)
+        .unwrap_or(0)
+}
+
+/// Projects a point along a coordinate axis, by dropping that coordinate.
+fn project(p: &[f64; 3], axis: usize) -> [f64; 2] {
+    match axis {
+        0 => [p[1], p[2]],
+        1 => [p[2], p[0]],
+        _ => [p[0], p[1]],
+    }
+}
+
+fn counter_clockwise(mut t: [[f64; 2]; 3]) -> [[f64; 2]; 3] {
+    if orient_2d(&t[0], &t[1], &t[2]) < 0 {
+        t.swap(1, 2);
+    }
+    t
+}
@@ -0,0 +1,222 @@
+//! Interval arithmetic, and filtered evaluation of custom predicates.
+//!
+//! geogram's predicates first evaluate a floating point filter, and only fall back to exact arithmetic when the filter
+//! cannot decide the sign. [`sign_of`] brings the same strategy to predicates that this crate does not ship: the
+//! polynomial is first evaluated with [`Interval`]s, which are cheap and certified, and then, if the interval contains
+//! zero, with [`Expansion`]s, which are exact.
+//!
+//! To evaluate one polynomial with both number types, write it once as a function that is generic over [`Ring`].
+
+use crate::expansion::Expansion;
+use crate::Sign;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A closed interval of reals, with bounds that are rounded outwards.
+///
+/// Every operation rounds its lower bound down and its upper bound up, by one ulp beyond the rounded to nearest
+/// result. Hence the result of an operation always contains the exact result of the operation applied to any reals in
+/// the operands, also when the bounds overflow or underflow.
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::interval::Interval;
+///
+/// let x = Interval::from(0.1) + Interval::from(0.2) - Interval::from(0.3);
+/// // 0.1 + 0.2 - 0.3 is not exactly zero in floating point, and the interval cannot tell its sign
+/// assert!(x.contains(0.0));
+/// assert_eq!(None, x.sign());
+///
+/// let y = Interval::from(3.0) * Interval::from(-2.0);
+/// assert_eq!(Some(-1), y.sign());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    lo: f64,
+    hi: f64,
+}
+
+impl Interval {
+    /// Creates the interval `[lo, hi]`.
+    ///
+    /// ### Parameters
+    /// - `lo`, `hi` the bounds of the interval, with `lo <= hi`
+    ///
+    /// ### Panics
+    /// - if `lo > hi`, or if a bound is NaN
+    pub fn new(lo: f64, hi: f64) -> Self {
+        assert!(lo <= hi, "invalid interval [{lo}, {hi}]");
+        Interval { lo, hi }
+    }
+
+    /// Gets the lower bound of the interval.
+    pub fn lo(&self) -> f64 {
+        self.lo
+    }
+
+    /// Gets the upper bound of the interval.
+    pub fn hi(&self) -> f64 {
+        self.hi
+    }
+
+    /// Tests whether the interval contains a value.
+    pub fn contains(&self, x: f64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    /// Gets the sign of all values in the interval, if they have the same.
+    ///
+    /// ### Return values
+    /// - `Some(+1)` if the interval only contains positive values
+    /// - `Some(0)` if the interval is `[0, 0]`
+    /// - `Some(-1)` if the interval only contains negative values
+    /// - `None` if the interval contains values of different signs, or a bound is NaN, e.g. after `inf - inf`
+    pub fn sign(&self) -> Option<Sign> {
+        if self.lo > 0.0 {
+            Some(1)
+        } else if self.hi < 0.0 {
+            Some(-1)
+        } else if self.lo == 0.0 && self.hi == 0.0 {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    /// Rounds the bounds of an interval computed with rounding to nearest outwards.
+    fn outward(lo: f64, hi: f64) -> Self {
+        Interval {
+            lo: lo.next_down(),
+            hi: hi.next_up(),
+        }
+    }
+}
+
+impl From<f64> for Interval {
+    /// Creates the interval `[x, x]`, which holds exactly `x`.
+    fn from(x: f64) -> Self {
+        Interval { lo: x, hi: x }
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+
+    fn add(self, rhs: Interval) -> Interval {
+        Interval::outward(self.lo + rhs.lo, self.hi + rhs.hi)
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+
+    fn sub(self, rhs: Interval) -> Interval {
+        Interval::outward(self.lo - rhs.hi, self.hi - rhs.lo)
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+
+    fn mul(self, rhs: Interval) -> Interval {
+        let products = [
+            self.lo * rhs.lo,
+            self.lo * rhs.hi,
+            self.hi * rhs.lo,
+            self.hi * rhs.hi,
+        ];
+        // A NaN product, i.e. 0 * inf, poisons both bounds, so that the sign is unknown.
+        if products.iter().any(|p| p.is_nan()) {
+            return Interval {
+                lo: f64::NAN,
+                hi: f64::NAN,
+            };
+        }
+        let lo = products.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = products.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Interval::outward(lo, hi)
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+
+    fn neg(self) -> Interval {
+        Interval {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+/// A number type in which polynomials with `f64` coefficients can be evaluated.
+///
+/// It is implemented by `f64`, [`Interval`] and [`Expansion`].
+pub trait Ring:
+    Clone
+    + From<f64>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+}
+
+impl<T> Ring for T where
+    T: Clone + From<f64> + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Neg<Output = T>
+{
+}
+
+/// Computes the sign of a polynomial, with intervals first and exact arithmetic if the intervals are inconclusive.
+///
+/// Both `interval` and `exact` should evaluate the same polynomial, which is simplest with a function that is generic
+/// over [`Ring`], see the example. The exact result is correct as long as no intermediate product of the expansions
+/// overflows or underflows, see the [`range`](crate::range) module.
+///
+/// ### Parameters
+/// - `x` the variables of the polynomial
+/// - `interval` the polynomial, evaluated with intervals
+/// - `exact` the polynomial, evaluated with expansions
+///
+/// ### Return values
+/// - `+1` if the polynomial is positive at `x`
+/// - `0` if the polynomial is zero at `x`
+/// - `-1` if the polynomial is negative at `x`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::expansion::Expansion;
+/// use gp::interval::{Interval, Ring};
+///
+/// // The orientation of the triangle (x[0], x[1]), (x[2], x[3]), (x[4], x[5])
+/// fn orient<T: Ring>(x: &[T]) -> T {
+///     let [ax, ay, bx, by, cx, cy] = [0, 1, 2, 3, 4, 5].map(|i| x[i].clone());
+///     (bx - ax.clone()) * (cy - ay.clone()) - (by - ay) * (cx - ax)
+/// }
+///
+/// // Decided by the intervals
+/// let x = [0.0, 0.0, 2.0, 0.0, 1.0, 1.0];
+/// assert_eq!(1, gp::interval::sign_of(&x, orient::<Interval>, orient::<Expansion>));
+///
+/// // Nearly collinear, decided by the expansions
+/// let x = [0.5, 0.5, 12.0, 12.0, 24.0, 24.0 + f64::EPSILON * 16.0];
+/// assert_eq!(None, orient(&x.map(Interval::from)).sign());
+/// assert_eq!(1, gp::interval::sign_of(&x, orient::<Interval>, orient::<Expansion>));
+///
+/// // Exactly collinear
+/// let x = [0.5, 0.5, 12.0, 12.0, 24.0, 24.0];
+/// assert_eq!(0, gp::interval::sign_of(&x, orient::<Interval>, orient::<Expansion>));
+/// ```
+pub fn sign_of<F, G>(x: &[f64], interval: F, exact: G) -> Sign
+where
+    F: Fn(&[Interval]) -> Interval,
+    G: Fn(&[Expansion]) -> Expansion,
+{
+    let intervals: Vec<Interval> = x.iter().copied().map(Interval::from).collect();
+    if let Some(sign) = interval(&intervals).sign() {
+        return sign;
+    }
+    let expansions: Vec<Expansion> = x.iter().copied().map(Expansion::from).collect();
+    exact(&expansions).sign()
+}
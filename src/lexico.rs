@@ -0,0 +1,130 @@
+//! Lexicographic comparison and sorting of points.
+
+use crate::{points_are_identical_2d, points_are_identical_3d};
+use std::cmp::Ordering;
+
+/// Compares two coordinates, treating `-0.0` and `0.0` as equal.
+fn coordinate_cmp(x: f64, y: f64) -> Ordering {
+    // Adding `0.0` maps `-0.0` to `0.0`, so that the total order agrees with `points_are_identical_*`.
+    (x + 0.0).total_cmp(&(y + 0.0))
+}
+
+/// Compares two 2d points lexicographically.
+///
+/// Points are ordered by their first coordinate, then by their second one, like in geogram's `LexicoCompare`. The
+/// order is total: `-0.0` and `0.0` are equal, and NaN coordinates come after all others, or before them if negative.
+///
+/// ### Parameters
+/// - `p`, `q` the points to compare
+///
+/// ### Returns
+/// - the order of `p` relative to `q`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less, gp::lexico_cmp_2d(&[0.0, 1.0], &[1.0, 0.0]));
+/// assert_eq!(Ordering::Greater, gp::lexico_cmp_2d(&[1.0, 1.0], &[1.0, 0.0]));
+/// assert_eq!(Ordering::Equal, gp::lexico_cmp_2d(&[-0.0, 1.0], &[0.0, 1.0]));
+/// ```
+pub fn lexico_cmp_2d(p: &[f64; 2], q: &[f64; 2]) -> Ordering {
+    coordinate_cmp(p[0], q[0]).then_with(|| coordinate_cmp(p[1], q[1]))
+}
+
+/// Compares two 3d points lexicographically.
+///
+/// See [`lexico_cmp_2d`] for the order.
+///
+/// ### Parameters
+/// - `p`, `q` the points to compare
+///
+/// ### Returns
+/// - the order of `p` relative to `q`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less, gp::lexico_cmp_3d(&[0.0, 1.0, 2.0], &[0.0, 1.0, 3.0]));
+/// assert_eq!(Ordering::Equal, gp::lexico_cmp_3d(&[0.0, 1.0, 2.0], &[0.0, 1.0, 2.0]));
+/// ```
+pub fn lexico_cmp_3d(p: &[f64; 3], q: &[f64; 3]) -> Ordering {
+    lexico_cmp_nd(p, q)
+}
+
+/// Compares two points of any dimension lexicographically.
+///
+/// See [`lexico_cmp_2d`] for the order.
+///
+/// ### Parameters
+/// - `p`, `q` the points to compare
+///
+/// ### Returns
+/// - the order of `p` relative to `q`
+///
+/// ### Panics
+/// - if `p` and `q` have different dimensions
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [0.0, 1.0, 2.0, 3.0];
+/// let q = [0.0, 1.0, 2.0, 4.0];
+/// assert_eq!(Ordering::Less, gp::lexico_cmp_nd(&p, &q));
+/// ```
+pub fn lexico_cmp_nd(p: &[f64], q: &[f64]) -> Ordering {
+    assert_eq!(p.len(), q.len(), "points of different dimensions");
+    p.iter()
+        .zip(q)
+        .map(|(&x, &y)| coordinate_cmp(x, y))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Sorts 2d points lexicographically, and removes duplicates.
+///
+/// Two points are duplicates if [`points_are_identical_2d`] holds. The predicates with Simulation of Simplicity
+/// perturb the points in this crate in the order of their addresses, which in the sorted slice coincides with the
+/// lexicographic order of geogram's `SOS_LEXICO` mode.
+///
+/// ### Parameters
+/// - `points` the points to sort and deduplicate in place
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let mut points = vec![[1.0, 0.0], [0.0, 1.0], [1.0, 0.0], [0.0, -0.0], [0.0, 0.0]];
+/// gp::sort_dedup_2d(&mut points);
+/// assert_eq!(vec![[0.0, -0.0], [0.0, 1.0], [1.0, 0.0]], points);
+/// ```
+pub fn sort_dedup_2d(points: &mut Vec<[f64; 2]>) {
+    points.sort_by(lexico_cmp_2d);
+    points.dedup_by(|p, q| points_are_identical_2d(p, q));
+}
+
+/// Sorts 3d points lexicographically, and removes duplicates.
+///
+/// Two points are duplicates if [`points_are_identical_3d`] holds. See [`sort_dedup_2d`] for the relation to
+/// Simulation of Simplicity.
+///
+/// ### Parameters
+/// - `points` the points to sort and deduplicate in place
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let mut points = vec![[1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]];
+/// gp::sort_dedup_3d(&mut points);
+/// assert_eq!(vec![[0.0, 0.0, 1.0], [1.0, 0.0, 0.0]], points);
+/// ```
+pub fn sort_dedup_3d(points: &mut Vec<[f64; 3]>) {
+    points.sort_by(lexico_cmp_3d);
+    points.dedup_by(|p, q| points_are_identical_3d(p, q));
+}
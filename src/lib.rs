@@ -1,14 +1,71 @@
 //! # Geogram Predicates
 //!
 //! A crate for rust interoperability with `geogram`s _robust predicates_; via `cxx`.
+//!
+//! ## Thread safety
+//!
+//! The predicates are plain functions of their arguments and may be called from several threads at once, once
+//! [`initialize`] has returned. The state they share is synchronized on the C++ side:
+//! - the statistics counters of [`show_stats`] are atomic (and compiled out unless geogram's `PCK_STATS` is defined),
+//! - the pools that allocate large expansions on the heap are guarded by a spinlock,
+//! - the global Simulation of Simplicity mode is atomic, and stays at geogram's default (perturbation by address) in
+//!   this crate.
+//!
+//! [`initialize`] itself only runs once, however often it is called. With the `rayon` feature, the [`parallel`] module
+//! provides batch variants of some predicates.
 
+mod affine;
+pub mod arrangement;
+pub mod boolean;
+mod bvh;
+pub mod cdt;
+mod checked;
+mod determinant;
+mod distance;
+pub mod expansion;
+mod filtered;
+pub mod implicit;
+pub mod intersection;
+pub mod interval;
+mod lexico;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod polygon;
+mod predicates_4d;
+mod predicates_nd;
+pub mod range;
+#[cfg(feature = "reference")]
+pub mod reference;
+mod remesh;
+pub mod validate;
+
+pub use affine::*;
+pub use checked::*;
+pub use determinant::*;
+pub use distance::*;
+pub use filtered::*;
 pub use geogram_ffi::*;
+pub use lexico::*;
+pub use predicates_4d::*;
+pub use predicates_nd::*;
+
+/// The result of a predicate: `+1`, `0` or `-1`.
+pub type Sign = i16;
 
 #[cxx::bridge(namespace = "GEOGRAM")]
 mod geogram_ffi {
     // Shared structs with fields visible to both languages.
     // ...
 
+    /// The arithmetic used by the predicates, see [`set_filter_mode`].
+    #[derive(Debug)]
+    enum FilterMode {
+        /// Evaluates the floating point filter of a predicate, and falls back to exact arithmetic when the filter is inconclusive. This is the default.
+        FilterAndExact,
+        /// Skips the filters, and always evaluates the predicates with exact arithmetic.
+        ExactOnly,
+    }
+
     // Rust types and signatures exposed to C++.
     // ...
 
@@ -16,6 +73,8 @@ mod geogram_ffi {
     unsafe extern "C++" {
         include!("geogram_predicates/include/geogram_ffi.h");
 
+        type FilterMode;
+
         /// Computes the sign of the determinant of a 3x3 matrix formed by three 3D points.
         ///
         /// ### Parameters
@@ -83,6 +142,13 @@ mod geogram_ffi {
         /// ```
         fn dot_3d(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> i16;
 
+        /// Gets the number of floating point filters that the calling thread skipped under
+        /// [`FilterMode::ExactOnly`].
+        ///
+        /// Not part of the public API, the tests use it to check that every predicate honors the mode.
+        #[doc(hidden)]
+        fn filters_skipped() -> u64;
+
         /// Gets the sign of a value.
         ///
         /// ### Parameters
@@ -108,6 +174,19 @@ mod geogram_ffi {
         /// ```
         fn geo_sgn(x: f64) -> i16;
 
+        /// Gets the arithmetic used by the predicates.
+        ///
+        /// ### Returns
+        /// - the mode last set with [`set_filter_mode`], [`FilterMode::FilterAndExact`] by default
+        ///
+        /// # Example
+        /// ```
+        /// use geogram_predicates as gp;
+        ///
+        /// assert_eq!(gp::FilterMode::FilterAndExact, gp::get_filter_mode());
+        /// ```
+        fn get_filter_mode() -> FilterMode;
+
         /// Tests whether a point is in the circum-circle of a triangle.
         ///
         /// If the triangle `a` , `b` , `c` is oriented clockwise instead of counter-clockwise, then the result is inversed.
@@ -142,6 +221,37 @@ mod geogram_ffi {
         /// ```
         fn in_circle_2d_SOS(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], p: &[f64; 2]) -> i16;
 
+        /// Tests whether a 3d point is in the circum-circle of a 3d triangle.
+        ///
+        /// The point `p` is tested against the smallest sphere that passes through `a`, `b`, `c`, i.e. the sphere that has the circum-circle of the triangle as its equator. Unlike [`in_circle_2d_SOS`], the result does not depend on the orientation of the triangle.
+        ///
+        /// ### Parameters
+        /// - `a`, `b`, `c` vertices of the triangle
+        /// - `p` point to test
+        ///
+        /// ### Return values
+        /// * `+1` - if `p` is inside the circum-circle of `a`, `b`, `c`
+        /// * `-1` - if `p` is outside the circum-circle of `a`, `b`, `c`
+        /// * `perturb()` - if `p` is exactly on the circum-circle of the triangle `a`, `b`, `c`, where `perturb()` denotes a globally consistent perturbation, that returns either `+1` or `-1`
+        ///
+        /// # Example
+        /// ```
+        /// use geogram_predicates as gp;
+        ///
+        /// // Define three points that form a triangle
+        /// let a = [0.0, 0.0, 0.0];
+        /// let b = [2.0, 0.0, 0.0];
+        /// let c = [0.0, 2.0, 0.0];
+        ///
+        /// // Define two points, to test against the triangles circum-circle
+        /// let p_in = [1.0, 1.0, 1.0];
+        /// assert_eq!(1, gp::in_circle_3d_SOS(&a, &b, &c, &p_in));
+        ///
+        /// let p_out = [1.0, 1.0, 2.0];
+        /// assert_eq!(-1, gp::in_circle_3d_SOS(&a, &b, &c, &p_out));
+        /// ```
+        fn in_circle_3d_SOS(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], p: &[f64; 3]) -> i16;
+
         /// Tests whether a point is in the circum-sphere of a tetrahedron.
         ///
         /// ### Parameters
@@ -179,6 +289,8 @@ mod geogram_ffi {
         ) -> i16;
 
         /// Needs to be called before using any predicate.
+        ///
+        /// Only the first call has an effect, so it is safe to call it several times, also concurrently.
         fn initialize();
 
         /// Computes the orientation predicate in 2d.
@@ -453,9 +565,67 @@ mod geogram_ffi {
         /// ```
         fn points_are_identical_3d(p1: &[f64; 3], p2: &[f64; 3]) -> bool;
 
+        /// Selects the arithmetic used by the predicates, to benchmark or verify the floating point filters.
+        ///
+        /// By default, a predicate first evaluates a floating point filter, and only falls back to exact arithmetic when the filter is inconclusive. [`FilterMode::ExactOnly`] forces the exact path. Both modes are exact, so they only differ in cost. [`points_are_colinear_3d`] and [`points_are_identical_2d`] are always exact, [`orient_3d_inexact`] never is. The predicates that are evaluated in Rust with [`interval::Interval`]s and [`expansion::Expansion`]s, such as [`orient_4d`], [`in_sphere_4d_SOS`], and [`orient_nd`] and [`det_sign`] beyond the sizes that call geogram, ignore the mode and are always exact.
+        ///
+        /// There is no mode that skips exact arithmetic, as a `0` would then mean either "uncertain" or an exact degeneracy, for the caller as well as for the algorithms of this crate. The filters alone are evaluated by the `*_filtered` variants of the predicates, such as [`orient_3d_filtered`], which return `None` when uncertain.
+        ///
+        /// The mode is global. Changing it while other threads evaluate predicates is safe, and does not change their results.
+        ///
+        /// ### Parameters
+        /// - `mode` the arithmetic to use from now on
+        ///
+        /// # Example
+        /// ```
+        /// use geogram_predicates as gp;
+        ///
+        /// gp::initialize();
+        ///
+        /// // Nearly collinear points, which the filter cannot decide
+        /// let a = [0.5, 0.5];
+        /// let b = [12.0, 12.0];
+        /// let c = [24.0, 24.0 + f64::EPSILON * 16.0];
+        ///
+        /// gp::set_filter_mode(gp::FilterMode::ExactOnly);
+        /// assert_eq!(1, gp::orient_2d(&a, &b, &c));
+        ///
+        /// gp::set_filter_mode(gp::FilterMode::FilterAndExact);
+        /// assert_eq!(1, gp::orient_2d(&a, &b, &c));
+        /// ```
+        fn set_filter_mode(mode: FilterMode);
+
         /// Displays some statistics about predicates, including the number of calls, the number of exact arithmetics calls, and the number of Simulation of Simplicity calls.
         fn show_stats();
 
+        /// Computes the side of a point relative to a bisector.
+        ///
+        /// Tests on which side of the bisector of `p0` and `p1` the point `q0` lies, i.e. the sign of `|q0 - p1|^2 - |q0 - p0|^2`. See [`side_of_bisector_nd`] for other dimensions.
+        ///
+        /// ### Parameters
+        /// - `p0`, `p1` the points that define the bisector
+        /// - `q0` point to test
+        ///
+        /// ### Return values
+        /// * `+1` - if `q0` is on the side of `p0`, i.e. closer to `p0` than to `p1`
+        /// * `-1` - if `q0` is on the side of `p1`
+        /// * `perturb()` - if `q0` is exactly on the bisector, where `perturb()` denotes a globally consistent perturbation, that returns either `+1` or `-1`
+        ///
+        /// # Example
+        /// ```
+        /// use geogram_predicates as gp;
+        ///
+        /// gp::initialize();
+        ///
+        /// let p0 = [0.0, 0.0, 0.0];
+        /// let p1 = [2.0, 0.0, 0.0];
+        ///
+        /// assert_eq!(1, gp::side1_3d_SOS(&p0, &p1, &[0.9, 5.0, 5.0]));
+        /// assert_eq!(-1, gp::side1_3d_SOS(&p0, &p1, &[1.1, 5.0, 5.0]));
+        /// assert_eq!(-gp::side1_3d_SOS(&p1, &p0, &[1.0, 5.0, 5.0]), gp::side1_3d_SOS(&p0, &p1, &[1.0, 5.0, 5.0]));
+        /// ```
+        fn side1_3d_SOS(p0: &[f64; 3], p1: &[f64; 3], q0: &[f64; 3]) -> i16;
+
         /// Needs to be called at the end of the program.
         fn terminate();
     }
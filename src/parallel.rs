@@ -0,0 +1,88 @@
+//! Batch evaluation of predicates on a [`rayon`] thread pool.
+//!
+//! The queries refer to their vertices by index into a shared slice of points. Under Simulation of Simplicity, a
+//! degenerate configuration is perturbed based on the address of its points, so the results only agree with the ones of
+//! a serial loop over the same slice, not over copies of the points.
+//!
+//! This module requires the `rayon` feature.
+
+use rayon::prelude::*;
+
+/// Computes [`orient_3d`](crate::orient_3d) for many tetrahedra in parallel.
+///
+/// ### Parameters
+/// - `points` the vertices of the tetrahedra
+/// - `tetrahedra` each entry holds the indices of the vertices `a`, `b`, `c`, `d` of one tetrahedron
+///
+/// ### Returns
+/// - the orientation of each tetrahedron, in the order of `tetrahedra`
+///
+/// ### Panics
+/// - if an index is out of range of `points`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let points = [
+///     [0.0, 0.0, 0.0],
+///     [1.0, 0.0, 0.0],
+///     [0.0, 1.0, 0.0],
+///     [0.0, 0.0, 1.0],
+///     [1.0, 1.0, 0.0],
+/// ];
+/// let tetrahedra = [[0, 1, 2, 3], [0, 2, 1, 3], [0, 1, 2, 4]];
+///
+/// assert_eq!(vec![1, -1, 0], gp::parallel::orient_3d(&points, &tetrahedra));
+/// ```
+pub fn orient_3d(points: &[[f64; 3]], tetrahedra: &[[usize; 4]]) -> Vec<i16> {
+    tetrahedra
+        .par_iter()
+        .map(|&[a, b, c, d]| crate::orient_3d(&points[a], &points[b], &points[c], &points[d]))
+        .collect()
+}
+
+/// Computes [`in_sphere_3d_SOS`](crate::in_sphere_3d_SOS) for many queries in parallel.
+///
+/// As for the serial predicate, each tetrahedron `a`, `b`, `c`, `d` needs to be positively oriented.
+///
+/// ### Parameters
+/// - `points` the vertices of the tetrahedra and the query points
+/// - `queries` each entry holds the indices of `a`, `b`, `c`, `d`, `p`, to test the point `p` against the circum-sphere
+///   of the tetrahedron `a`, `b`, `c`, `d`
+///
+/// ### Returns
+/// - the result of each query, in the order of `queries`
+///
+/// ### Panics
+/// - if an index is out of range of `points`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let points = [
+///     [0.0, 0.0, 0.0],
+///     [2.0, 0.0, 0.0],
+///     [0.0, 2.0, 0.0],
+///     [0.75, 0.75, 1.0],
+///     [0.75, 0.75, 0.5],
+///     [0.75, 0.75, 1.5],
+/// ];
+/// let queries = [[0, 1, 2, 3, 4], [0, 1, 2, 3, 5]];
+///
+/// assert_eq!(vec![1, -1], gp::parallel::in_sphere_3d_SOS(&points, &queries));
+/// ```
+#[allow(non_snake_case)]
+pub fn in_sphere_3d_SOS(points: &[[f64; 3]], queries: &[[usize; 5]]) -> Vec<i16> {
+    queries
+        .par_iter()
+        .map(|&[a, b, c, d, p]| {
+            crate::in_sphere_3d_SOS(&points[a], &points[b], &points[c], &points[d], &points[p])
+        })
+        .collect()
+}
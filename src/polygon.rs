@@ -0,0 +1,272 @@
+//! Exact orientation and simplicity tests for 2d polygons.
+//!
+//! A polygon is given as a slice of vertices, where edge `i` joins vertex `i` and vertex `(i + 1) % n`.
+//! All combinatorial decisions are taken with [`orient_2d`] and exact coordinate comparisons,
+//! so the results are correct even for near-degenerate slivers.
+
+use crate::{lexico_cmp_2d, orient_2d, points_are_identical_2d};
+
+/// A reason why a polygon is not simple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PolygonDefect {
+    /// The vertices `first` and `second` have exactly the same coordinates.
+    RepeatedVertex { first: usize, second: usize },
+    /// The edges `first` and `second` intersect, apart from the vertex shared by consecutive edges.
+    IntersectingEdges { first: usize, second: usize },
+    /// A coordinate of the vertex `index` is NaN or infinite.
+    NonFiniteVertex { index: usize },
+}
+
+/// Computes the orientation of a polygon.
+///
+/// The orientation is evaluated with [`orient_2d`] at the lexicographically lowest vertex, which always is a convex
+/// vertex of a simple polygon. Hence the result is exact, whereas the sign of a floating point signed area may be
+/// wrong for near-degenerate polygons.
+///
+/// ### Parameters
+/// - `polygon` the vertices of the polygon
+///
+/// ### Return values
+/// * `+1` - if the polygon is oriented counter-clockwise
+/// * `0` - if the polygon is degenerate, i.e. it has less than three distinct vertices or a spike at its lowest vertex
+/// * `-1` - if the polygon is oriented clockwise
+///
+/// The result is only meaningful for simple polygons, see [`is_simple_polygon`].
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let ccw = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+/// assert_eq!(1, gp::polygon::polygon_orientation_2d(&ccw));
+///
+/// let cw = [[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]];
+/// assert_eq!(-1, gp::polygon::polygon_orientation_2d(&cw));
+///
+/// // A thin sliver, one ulp away from being flat
+/// let sliver = [[0.5, 0.5], [12.0, 12.0], [24.0, 24.0 + f64::EPSILON * 16.0]];
+/// assert_eq!(1, gp::polygon::polygon_orientation_2d(&sliver));
+/// ```
+pub fn polygon_orientation_2d(polygon: &[[f64; 2]]) -> i16 {
+    let n = polygon.len();
+    let Some(lowest) = (0..n).min_by(|&i, &j| lexico_cmp_2d(&polygon[i], &polygon[j])) else {
+        return 0;
+    };
+    let v = &polygon[lowest];
+
+    // Skip vertices that coincide with the lowest one, to find its distinct neighbours.
+    let Some(prev) = (1..n)
+        .map(|k| &polygon[(lowest + n - k) % n])
+        .find(|p| !points_are_identical_2d(p, v))
+    else {
+        return 0;
+    };
+    let next = (1..n)
+        .map(|k| &polygon[(lowest + k) % n])
+        .find(|p| !points_are_identical_2d(p, v))
+        .expect("a distinct predecessor implies a distinct successor");
+
+    orient_2d(prev, v, next)
+}
+
+/// Tests whether a polygon is simple.
+///
+/// A polygon is simple, if it has at least three vertices, all of them finite, no two vertices are identical and no two
+/// edges intersect, except for consecutive edges at their shared vertex.
+///
+/// ### Parameters
+/// - `polygon` the vertices of the polygon
+///
+/// ### Return values
+/// - `true` - if the polygon is simple
+/// - `false` - otherwise
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let square = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+/// assert!(gp::polygon::is_simple_polygon(&square));
+///
+/// let bowtie = [[0.0, 0.0], [2.0, 2.0], [2.0, 0.0], [0.0, 2.0]];
+/// assert!(!gp::polygon::is_simple_polygon(&bowtie));
+///
+/// let nan = [[0.0, 0.0], [1.0, 0.0], [f64::NAN, 1.0], [0.0, 1.0]];
+/// assert!(!gp::polygon::is_simple_polygon(&nan));
+/// ```
+pub fn is_simple_polygon(polygon: &[[f64; 2]]) -> bool {
+    polygon.len() >= 3 && polygon_defects(polygon).is_empty()
+}
+
+/// Finds all defects that prevent a polygon from being simple.
+///
+/// Reports every pair of identical vertices and every pair of intersecting edges, where edge `i` joins vertex `i` and
+/// vertex `(i + 1) % n`. Consecutive edges only count as intersecting if they overlap beyond their shared vertex.
+/// Within each pair `first < second` holds; repeated vertices are listed before intersecting edges.
+///
+/// A vertex with a NaN or infinite coordinate cannot be compared exactly, so if there is any, only the non-finite
+/// vertices are reported.
+///
+/// ### Parameters
+/// - `polygon` the vertices of the polygon
+///
+/// ### Returns
+/// - the defects of the polygon, which is empty if the polygon is simple
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use gp::polygon::PolygonDefect;
+///
+/// let bowtie = [[0.0, 0.0], [2.0, 2.0], [2.0, 0.0], [0.0, 2.0]];
+/// let defects = gp::polygon::polygon_defects(&bowtie);
+/// assert_eq!(defects, vec![PolygonDefect::IntersectingEdges { first: 0, second: 2 }]);
+///
+/// let pinched = [[0.0, 0.0], [1.0, 1.0], [2.0, 0.0], [2.0, 2.0], [1.0, 1.0], [0.0, 2.0]];
+/// let defects = gp::polygon::polygon_defects(&pinched);
+/// assert_eq!(defects[0], PolygonDefect::RepeatedVertex { first: 1, second: 4 });
+///
+/// let unbounded = [[0.0, 0.0], [f64::INFINITY, 0.0], [0.0, 1.0]];
+/// let defects = gp::polygon::polygon_defects(&unbounded);
+/// assert_eq!(defects, vec![PolygonDefect::NonFiniteVertex { index: 1 }]);
+/// ```
+pub fn polygon_defects(polygon: &[[f64; 2]]) -> Vec<PolygonDefect> {
+    let non_finite: Vec<_> = (0..polygon.len())
+        .filter(|&index| !polygon[index].iter().all(|x| x.is_finite()))
+        .map(|index| PolygonDefect::NonFiniteVertex { index })
+        .collect();
+    if !non_finite.is_empty() {
+        return non_finite;
+    }
+    let mut defects = repeated_vertices(polygon);
+    defects.extend(intersecting_edges(polygon));
+    defects
+}
+
+/// Tests whether two closed segments intersect.
+///
+/// The test is exact, and includes touching and overlapping segments, as well as degenerate segments of length zero.
+///
+/// ### Parameters
+/// - `a`, `b` the endpoints of the first segment
+/// - `c`, `d` the endpoints of the second segment
+///
+/// ### Return values
+/// - `true` - if the segments `ab` and `cd` share at least one point
+/// - `false` - otherwise
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// assert!(gp::polygon::segments_intersect_2d(&[0.0, 0.0], &[2.0, 2.0], &[2.0, 0.0], &[0.0, 2.0]));
+/// assert!(gp::polygon::segments_intersect_2d(&[0.0, 0.0], &[2.0, 0.0], &[1.0, 0.0], &[3.0, 0.0]));
+/// assert!(!gp::polygon::segments_intersect_2d(&[0.0, 0.0], &[1.0, 0.0], &[2.0, 0.0], &[3.0, 0.0]));
+/// ```
+pub fn segments_intersect_2d(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], d: &[f64; 2]) -> bool {
+    let o_abc = orient_2d(a, b, c);
+    let o_abd = orient_2d(a, b, d);
+    let o_cda = orient_2d(c, d, a);
+    let o_cdb = orient_2d(c, d, b);
+
+    if o_abc * o_abd > 0 || o_cda * o_cdb > 0 {
+        return false;
+    }
+    if o_abc == 0 && o_abd == 0 && o_cda == 0 && o_cdb == 0 {
+        // All points are on a common line, along which the lexicographic order is monotone.
+        let (ab_min, ab_max) = lexico_minmax(a, b);
+        let (cd_min, cd_max) = lexico_minmax(c, d);
+        return lexico_cmp_2d(cd_min, ab_max).is_le() && lexico_cmp_2d(ab_min, cd_max).is_le();
+    }
+    true
+}
+
+fn repeated_vertices(polygon: &[[f64; 2]]) -> Vec<PolygonDefect> {
+    let mut order: Vec<usize> = (0..polygon.len()).collect();
+    order.sort_by(|&i, &j| lexico_cmp_2d(&polygon[i], &polygon[j]).then(i.cmp(&j)));
+
+    let mut pairs = Vec::new();
+    for run in order.chunk_by(|&i, &j| points_are_identical_2d(&polygon[i], &polygon[j])) {
+        for (k, &first) in run.iter().enumerate() {
+            pairs.extend(run[k + 1..].iter().map(|&second| (first, second)));
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+        .into_iter()
+        .map(|(first, second)| PolygonDefect::RepeatedVertex { first, second })
+        .collect()
+}
+
+fn intersecting_edges(polygon: &[[f64; 2]]) -> Vec<PolygonDefect> {
+    let n = polygon.len();
+    if n < 2 {
+        return Vec::new();
+    }
+    let edge = |i: usize| (&polygon[i], &polygon[(i + 1) % n]);
+
+    // Sweep over the edges ordered by their leftmost x-coordinate, so that only edges with overlapping bounding boxes
+    // are tested exactly.
+    let bbox = |i: usize| {
+        let (p, q) = edge(i);
+        (
+            [p[0].min(q[0]), p[1].min(q[1])],
+            [p[0].max(q[0]), p[1].max(q[1])],
+        )
+    };
+    let boxes: Vec<_> = (0..n).map(bbox).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| boxes[i].0[0].total_cmp(&boxes[j].0[0]));
+
+    let mut pairs = Vec::new();
+    for (k, &i) in order.iter().enumerate() {
+        for &j in &order[k + 1..] {
+            if boxes[j].0[0] > boxes[i].1[0] {
+                break;
+            }
+            if boxes[j].0[1] > boxes[i].1[1] || boxes[i].0[1] > boxes[j].1[1] {
+                continue;
+            }
+            let (first, second) = (i.min(j), i.max(j));
+            let (a, b) = edge(first);
+            let (c, d) = edge(second);
+            let intersect = if n == 2 {
+                // Both edges join the same two vertices.
+                true
+            } else if second == first + 1 {
+                consecutive_edges_overlap(a, b, d)
+            } else if first == 0 && second == n - 1 {
+                consecutive_edges_overlap(c, d, b)
+            } else {
+                segments_intersect_2d(a, b, c, d)
+            };
+            if intersect {
+                pairs.push((first, second));
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+        .into_iter()
+        .map(|(first, second)| PolygonDefect::IntersectingEdges { first, second })
+        .collect()
+}
+
+/// Tests whether the consecutive edges `ab` and `bc` share more than their common vertex `b`.
+fn consecutive_edges_overlap(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> bool {
+    if points_are_identical_2d(a, b) || points_are_identical_2d(b, c) {
+        return false;
+    }
+    // The edges overlap if they are colinear and `a` and `c` lie on the same side of `b`.
+    orient_2d(a, b, c) == 0 && lexico_cmp_2d(a, b) == lexico_cmp_2d(c, b)
+}
+
+fn lexico_minmax<'a>(p: &'a [f64; 2], q: &'a [f64; 2]) -> (&'a [f64; 2], &'a [f64; 2]) {
+    if lexico_cmp_2d(p, q).is_le() {
+        (p, q)
+    } else {
+        (q, p)
+    }
+}
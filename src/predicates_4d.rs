@@ -0,0 +1,160 @@
+//! Orientation and in-sphere predicates in 4d.
+//!
+//! geogram's predicate kernels stop at the `side4` family in 4d, so these predicates are evaluated in Rust, with the
+//! same strategy: an [`Interval`] filter first, and exact [`Expansion`]s if the filter cannot decide.
+
+use crate::determinant::det;
+use crate::expansion::Expansion;
+use crate::interval::{Interval, Ring};
+use crate::predicates_nd::{orient_simplex, rows};
+use crate::Sign;
+
+/// The determinants of the lifted simplex `p0, ..., p5`.
+struct Lifted<T> {
+    /// `deltas[k]` is the determinant of the rows `p_i - p0`, for `i` from `1` to `5` without `k + 1`.
+    deltas: [T; 5],
+    /// The determinant of the rows `(p_i - p0, |p_i - p0|^2)`, for `i` from `1` to `5`.
+    d: T,
+}
+
+fn lifted<T: Ring>(p: [&[f64; 4]; 6]) -> Lifted<T> {
+    let rows = rows::<T, 4>(&p);
+    let deltas: [T; 5] = std::array::from_fn(|skip| {
+        let minor: Vec<Vec<T>> = (0..5)
+            .filter(|&i| i != skip)
+            .map(|i| rows[i].clone())
+            .collect();
+        det(&minor)
+    });
+    // Expansion along the last column, whose cofactors alternate in sign starting with `+`.
+    let mut d = T::from(0.0);
+    for (k, (row, delta)) in rows.iter().zip(&deltas).enumerate() {
+        let sq_dist = row
+            .iter()
+            .fold(T::from(0.0), |sum, x| sum + x.clone() * x.clone());
+        d = if k % 2 == 0 {
+            d + delta.clone() * sq_dist
+        } else {
+            d - delta.clone() * sq_dist
+        };
+    }
+    Lifted { deltas, d }
+}
+
+/// Computes the orientation predicate in 4d.
+///
+/// This is the sign of the determinant of `b - a`, `c - a`, `d - a`, `e - a`, which generalizes
+/// [`orient_3d`](crate::orient_3d). The result is exact for coordinates in
+/// [`range::ORIENT_4D`](crate::range::ORIENT_4D). It is evaluated in Rust, with [`Interval`]s and then exact
+/// expansions, and does not depend on the [`FilterMode`](crate::FilterMode).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d`, `e` vertices of the simplex
+///
+/// ### Return values
+/// * `+1` - if the simplex is oriented positively
+/// * `0` - if the simplex is flat
+/// * `-1` - if the simplex is oriented negatively
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0, 0.0];
+/// let c = [0.0, 1.0, 0.0, 0.0];
+/// let d = [0.0, 0.0, 1.0, 0.0];
+///
+/// assert_eq!(1, gp::orient_4d(&a, &b, &c, &d, &[0.0, 0.0, 0.0, 1.0]));
+/// assert_eq!(-1, gp::orient_4d(&a, &b, &c, &d, &[0.0, 0.0, 0.0, -1.0]));
+/// assert_eq!(0, gp::orient_4d(&a, &b, &c, &d, &[1.0, 1.0, 1.0, 0.0]));
+/// ```
+pub fn orient_4d(a: &[f64; 4], b: &[f64; 4], c: &[f64; 4], d: &[f64; 4], e: &[f64; 4]) -> Sign {
+    orient_simplex(&[a, b, c, d, e])
+}
+
+/// Tests whether a point is in the circum-sphere of a 4d simplex, with Simulation of Simplicity.
+///
+/// This generalizes [`in_sphere_3d_SOS`](crate::in_sphere_3d_SOS) to 4d, including its symbolic perturbation: the
+/// squared norms of the points, i.e. their heights on the lifting paraboloid, are lowered by infinitesimals that
+/// decrease in the order of the addresses of the points. A point on the circum-sphere is hence never reported as such.
+/// Unlike in 3d, the simplex may have either orientation. The result is exact for coordinates in
+/// [`range::IN_SPHERE_4D_SOS`](crate::range::IN_SPHERE_4D_SOS). Like [`orient_4d`], it does not depend on the
+/// [`FilterMode`](crate::FilterMode).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d`, `e` vertices of the simplex
+/// - `p` point to test
+///
+/// ### Return values
+/// * `+1` - if `p` is inside the circum-sphere of the simplex
+/// * `-1` - if `p` is outside the circum-sphere of the simplex
+///
+/// ### Panics
+/// - if the simplex is flat, i.e. if [`orient_4d`] is zero
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0, 0.0];
+/// let c = [0.0, 2.0, 0.0, 0.0];
+/// let d = [0.0, 0.0, 2.0, 0.0];
+/// let e = [0.0, 0.0, 0.0, 2.0];
+///
+/// assert_eq!(1, gp::in_sphere_4d_SOS(&a, &b, &c, &d, &e, &[0.5, 0.5, 0.5, 0.5]));
+/// assert_eq!(-1, gp::in_sphere_4d_SOS(&a, &b, &c, &d, &e, &[3.0, 3.0, 3.0, 3.0]));
+/// // On the circum-sphere, decided by the symbolic perturbation
+/// let p = [2.0, 2.0, 0.0, 0.0];
+/// assert_eq!(1, gp::in_sphere_4d_SOS(&a, &b, &c, &d, &e, &p).abs());
+/// ```
+#[allow(non_snake_case)]
+pub fn in_sphere_4d_SOS(
+    a: &[f64; 4],
+    b: &[f64; 4],
+    c: &[f64; 4],
+    d: &[f64; 4],
+    e: &[f64; 4],
+    p: &[f64; 4],
+) -> Sign {
+    let points = [a, b, c, d, e, p];
+    let filter = lifted::<Interval>(points);
+    if let (Some(o), Some(r)) = (filter.deltas[4].sign(), filter.d.sign()) {
+        if o != 0 && r != 0 {
+            return -o * r;
+        }
+    }
+
+    let Lifted { deltas, d } = lifted::<Expansion>(points);
+    let o = deltas[4].sign();
+    assert_ne!(o, 0, "the simplex must not be flat");
+    let r = d.sign();
+    if r != 0 {
+        return -o * r;
+    }
+    // The coefficients of the infinitesimals in the expansion of `d`, in the order of the addresses of the points.
+    let mut order = [0, 1, 2, 3, 4, 5];
+    order.sort_by_key(|&i| points[i] as *const [f64; 4]);
+    for i in order {
+        let z = match i {
+            0 => deltas
+                .iter()
+                .enumerate()
+                .fold(Expansion::new(0.0), |sum, (k, delta)| {
+                    if k % 2 == 0 {
+                        &sum + delta
+                    } else {
+                        &sum - delta
+                    }
+                })
+                .sign(),
+            _ if i % 2 == 1 => -deltas[i - 1].sign(),
+            _ => deltas[i - 1].sign(),
+        };
+        if z != 0 {
+            return -o * z;
+        }
+    }
+    unreachable!("the perturbation of `p` is always decisive")
+}
@@ -0,0 +1,89 @@
+//! Orientation predicate in any dimension.
+
+use crate::determinant::det;
+use crate::expansion::Expansion;
+use crate::interval::{Interval, Ring};
+use crate::{orient_2d, orient_3d, Sign};
+
+/// The differences of the points to the first one, as rows of a matrix.
+pub(crate) fn rows<T: Ring, const D: usize>(points: &[&[f64; D]]) -> Vec<Vec<T>> {
+    let [p0, rest @ ..] = points else {
+        return Vec::new();
+    };
+    rest.iter()
+        .map(|q| (0..D).map(|j| T::from(q[j]) - T::from(p0[j])).collect())
+        .collect()
+}
+
+/// Computes the sign of the determinant of the differences of `points` to the first one, with an [`Interval`] filter
+/// and an exact [`Expansion`] fallback.
+pub(crate) fn orient_simplex<const D: usize>(points: &[&[f64; D]]) -> Sign {
+    debug_assert_eq!(points.len(), D + 1);
+    match det(&rows::<Interval, D>(points)).sign() {
+        Some(sign) => sign,
+        None => det(&rows::<Expansion, D>(points)).sign(),
+    }
+}
+
+/// Computes the orientation predicate in any dimension.
+///
+/// This is the sign of the determinant of `p1 - p0`, ..., `pD - p0`, which is [`orient_2d`] for `D = 2`,
+/// [`orient_3d`] for `D = 3` and [`orient_4d`](crate::orient_4d) for `D = 4`. In 2d and 3d, geogram's filtered kernels
+/// are called. In higher dimensions, the determinant is first evaluated with [`Interval`]s, and only if they cannot
+/// decide with exact [`Expansion`]s, by an expansion into minors that needs no division and `O(2^D D)` products. The
+/// result is exact for coordinates in [`SafeRange::of_degree(D)`](crate::range::SafeRange::of_degree).
+///
+/// Hence the [`FilterMode`](crate::FilterMode) set with [`set_filter_mode`](crate::set_filter_mode) applies in 2d and
+/// 3d only. In higher dimensions, the result is always exact.
+///
+/// Exact predicates require [`initialize`](crate::initialize) to be called first.
+///
+/// ### Parameters
+/// - `points` the `D + 1` vertices `p0`, ..., `pD` of the simplex
+///
+/// ### Return values
+/// * `+1` - if the simplex is oriented positively
+/// * `0` - if the simplex is flat
+/// * `-1` - if the simplex is oriented negatively
+///
+/// ### Panics
+/// - if there are not `D + 1` points
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let triangle = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+/// assert_eq!(1, gp::orient_nd(&triangle));
+///
+/// let mut simplex = [[0.0; 5]; 6];
+/// for i in 0..5 {
+///     simplex[i + 1][i] = 1.0;
+/// }
+/// assert_eq!(1, gp::orient_nd(&simplex));
+/// simplex.swap(1, 2);
+/// assert_eq!(-1, gp::orient_nd(&simplex));
+/// simplex[5] = [0.5, 0.5, 0.0, 0.0, 0.0];
+/// assert_eq!(0, gp::orient_nd(&simplex));
+/// ```
+pub fn orient_nd<const D: usize>(points: &[[f64; D]]) -> Sign {
+    assert_eq!(
+        points.len(),
+        D + 1,
+        "a simplex in {D}d has {} vertices",
+        D + 1
+    );
+    match D {
+        2 => {
+            let p = |i: usize| -> &[f64; 2] { points[i].as_slice().try_into().unwrap() };
+            orient_2d(p(0), p(1), p(2))
+        }
+        3 => {
+            let p = |i: usize| -> &[f64; 3] { points[i].as_slice().try_into().unwrap() };
+            orient_3d(p(0), p(1), p(2), p(3))
+        }
+        _ => orient_simplex(&points.iter().collect::<Vec<_>>()),
+    }
+}
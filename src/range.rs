@@ -0,0 +1,211 @@
+//! Input ranges in which the predicates are exact.
+//!
+//! geogram falls back to Shewchuk's expansion arithmetic when its floating point filters are inconclusive. The
+//! expansions are only exact as long as no intermediate product overflows, or underflows to subnormal numbers. For a
+//! predicate whose exact evaluation is a polynomial of degree `d` in the coordinates, this holds if every non-zero
+//! coordinate `x` satisfies `min <= |x| < max`, where `max` leaves room for `d`-fold products of coordinate
+//! differences, and `min` keeps their least significant bits above `2^-1074`.
+//!
+//! Note that the range checks in the filters, e.g. the `sqrt^5(min_double/eps)` bound of geogram's
+//! `in_sphere_3d_filter_optim`, only decide between the filter and the exact evaluation, and do not widen these ranges.
+//!
+//! The [`checked_*`](crate::checked_orient_3d) predicates run a finer test on the actual input, based on the binary
+//! exponents of the coordinates. Input in the ranges below always passes that test.
+
+/// The magnitudes of non-zero coordinates for which a predicate is exact, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SafeRange {
+    /// The smallest allowed magnitude of a non-zero coordinate.
+    pub min: f64,
+    /// The exclusive upper bound of the magnitudes of the coordinates.
+    pub max: f64,
+}
+
+/// A reason why input is not in a [`SafeRange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeViolation {
+    /// The point `point` has a NaN or infinite coordinate.
+    NonFinite { point: usize },
+    /// The point `point` has a coordinate of magnitude `max` or larger, the input should be scaled down.
+    TooLarge { point: usize },
+    /// The point `point` has a non-zero coordinate of magnitude smaller than `min`, the input should be scaled up.
+    TooSmall { point: usize },
+}
+
+/// Returns `2^e`, for `-1022 <= e <= 1023`.
+const fn pow2(e: i32) -> f64 {
+    f64::from_bits(((e + 1023) as u64) << 52)
+}
+
+impl SafeRange {
+    /// The range of a polynomial of `degree` in the coordinates.
+    ///
+    /// Let `|x| < 2^e` for all coordinates. Their differences are smaller than `2^(e + 1)`, and their products
+    /// of `degree` factors, including the head room for sums and splitting, need to stay below `2^1023`. If on the
+    /// other hand `|x| >= 2^k`, the least significant bit of `x` is at least `2^(k - 52)`, and the one of a product at
+    /// least `2^(degree * (k - 52))`, which needs to be representable.
+    ///
+    /// ### Parameters
+    /// - `degree` the degree of the polynomial, at least `1`
+    ///
+    /// # Example
+    /// ```
+    /// use geogram_predicates as gp;
+    /// use gp::range::SafeRange;
+    ///
+    /// assert_eq!(gp::range::ORIENT_3D, SafeRange::of_degree(3));
+    /// // The range of orient_nd in 6d
+    /// assert!(SafeRange::of_degree(6).contains(1e20));
+    /// ```
+    pub const fn of_degree(degree: i32) -> Self {
+        SafeRange {
+            min: pow2(52 - (1074 / degree)),
+            max: pow2((1023 - HEAD_ROOM) / degree - 1),
+        }
+    }
+
+    /// Tests whether a coordinate is in the range.
+    ///
+    /// ### Parameters
+    /// - `x` the coordinate to test
+    ///
+    /// ### Returns
+    /// - `true` if `x` is zero, or if `min <= |x| < max`
+    ///
+    /// # Example
+    /// ```
+    /// use geogram_predicates as gp;
+    ///
+    /// assert!(gp::range::ORIENT_3D.contains(0.0));
+    /// assert!(gp::range::ORIENT_3D.contains(-1e50));
+    /// assert!(!gp::range::ORIENT_3D.contains(1e200));
+    /// assert!(!gp::range::ORIENT_3D.contains(1e-200));
+    /// assert!(!gp::range::ORIENT_3D.contains(f64::NAN));
+    /// ```
+    pub fn contains(&self, x: f64) -> bool {
+        x == 0.0 || (self.min..self.max).contains(&x.abs())
+    }
+
+    /// Tests whether all coordinates of a set of points are in the range.
+    ///
+    /// ### Parameters
+    /// - `points` the points to test
+    ///
+    /// ### Returns
+    /// - `Ok(())` if the predicate is exact for any choice of its arguments among `points`
+    /// - the first point that is out of range, and why, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use geogram_predicates as gp;
+    ///
+    /// let points = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1e-250, 0.0], [0.0, 0.0, 1e250]];
+    ///
+    /// assert_eq!(Ok(()), gp::range::ORIENT_3D.check(&points[..2]));
+    /// assert_eq!(
+    ///     Err(gp::range::RangeViolation::TooSmall { point: 2 }),
+    ///     gp::range::ORIENT_3D.check(&points)
+    /// );
+    /// assert_eq!(
+    ///     Err(gp::range::RangeViolation::TooLarge { point: 1 }),
+    ///     gp::range::ORIENT_3D.check(&[points[0], points[3]])
+    /// );
+    /// ```
+    pub fn check<const N: usize>(&self, points: &[[f64; N]]) -> Result<(), RangeViolation> {
+        for (point, p) in points.iter().enumerate() {
+            for &x in p {
+                if !x.is_finite() {
+                    return Err(RangeViolation::NonFinite { point });
+                }
+                if x.abs() >= self.max {
+                    return Err(RangeViolation::TooLarge { point });
+                }
+                if x != 0.0 && x.abs() < self.min {
+                    return Err(RangeViolation::TooSmall { point });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The safe range of [`collinear_2d`](crate::collinear_2d) and [`collinear_nd`](crate::collinear_nd).
+pub const COLLINEAR: SafeRange = SafeRange::of_degree(2);
+/// The safe range of [`compare_distance_2d`](crate::compare_distance_2d), and of its 3d and nd variants.
+pub const COMPARE_DISTANCE: SafeRange = SafeRange::of_degree(2);
+/// The safe range of the weights of [`compare_power_distance_2d`](crate::compare_power_distance_2d), and of its 3d and
+/// nd variants, which are compared to squared distances.
+pub const COMPARE_POWER_DISTANCE: SafeRange = SafeRange::of_degree(1);
+/// The safe range of [`det_3d`](crate::det_3d).
+pub const DET_3D: SafeRange = SafeRange::of_degree(3);
+/// The safe range of [`det_4d`](crate::det_4d).
+pub const DET_4D: SafeRange = SafeRange::of_degree(4);
+/// The safe range of [`dot_3d`](crate::dot_3d).
+pub const DOT_3D: SafeRange = SafeRange::of_degree(2);
+/// The safe range of [`orient_2d`](crate::orient_2d).
+pub const ORIENT_2D: SafeRange = SafeRange::of_degree(2);
+/// The safe range of [`orient_3d`](crate::orient_3d).
+pub const ORIENT_3D: SafeRange = SafeRange::of_degree(3);
+/// The safe range of [`orient_4d`](crate::orient_4d).
+pub const ORIENT_4D: SafeRange = SafeRange::of_degree(4);
+/// The safe range of [`implicit::orient_2d`](crate::implicit::orient_2d), where intersection points have homogeneous
+/// coordinates of degree 3.
+pub const ORIENT_2D_IMPLICIT: SafeRange = SafeRange::of_degree(8);
+/// The safe range of [`implicit::orient_3d`](crate::implicit::orient_3d), where intersection points have homogeneous
+/// coordinates of degree 4.
+pub const ORIENT_3D_IMPLICIT: SafeRange = SafeRange::of_degree(15);
+/// The safe range of [`points_are_colinear_3d`](crate::points_are_colinear_3d).
+pub const POINTS_ARE_COLINEAR_3D: SafeRange = SafeRange::of_degree(2);
+/// The safe range of [`side1_3d_SOS`](crate::side1_3d_SOS).
+pub const SIDE1_3D_SOS: SafeRange = SafeRange::of_degree(2);
+/// The safe range of [`side2_3d_SOS`](crate::side2_3d_SOS), whose exact evaluation has degree 4.
+pub const SIDE2_3D_SOS: SafeRange = SafeRange::of_degree(4);
+/// The safe range of [`in_circle_2d_SOS`](crate::in_circle_2d_SOS), whose exact evaluation has degree 8.
+pub const IN_CIRCLE_2D_SOS: SafeRange = SafeRange::of_degree(8);
+/// The safe range of [`in_circle_3d_SOS`](crate::in_circle_3d_SOS), whose exact evaluation has degree 8.
+pub const IN_CIRCLE_3D_SOS: SafeRange = SafeRange::of_degree(8);
+/// The safe range of [`in_sphere_3d_SOS`](crate::in_sphere_3d_SOS).
+pub const IN_SPHERE_3D_SOS: SafeRange = SafeRange::of_degree(5);
+/// The safe range of [`in_sphere_4d_SOS`](crate::in_sphere_4d_SOS).
+pub const IN_SPHERE_4D_SOS: SafeRange = SafeRange::of_degree(6);
+/// The safe range of [`orient_2dlifted_SOS`](crate::orient_2dlifted_SOS), for both the coordinates and the heights.
+pub const ORIENT_2DLIFTED_SOS: SafeRange = SafeRange::of_degree(3);
+/// The safe range of [`orient_3dlifted_SOS`](crate::orient_3dlifted_SOS), for both the coordinates and the heights.
+pub const ORIENT_3DLIFTED_SOS: SafeRange = SafeRange::of_degree(4);
+
+/// Head room for the sums of products, and for the Veltkamp split of the factors in `two_product`.
+const HEAD_ROOM: i32 = 36;
+
+/// The binary magnitudes of a set of finite values: their differences are smaller than `2^hi` in absolute value, and
+/// multiples of `2^lo`.
+#[derive(Clone, Copy)]
+pub(crate) struct Magnitude {
+    hi: i32,
+    lo: i32,
+}
+
+impl Magnitude {
+    pub(crate) fn of(values: impl IntoIterator<Item = f64>) -> Self {
+        let mut magnitude = Magnitude { hi: 0, lo: 0 };
+        for x in values.into_iter().filter(|&x| x != 0.0) {
+            // |x| = m * 2^e, with an integer m.
+            let raw = x.abs().to_bits();
+            let (m, e) = match (raw >> 52) as i32 {
+                0 => (raw, -1074),
+                exp => (raw & ((1 << 52) - 1) | (1 << 52), exp - 1075),
+            };
+            magnitude.hi = magnitude.hi.max(e + 64 - m.leading_zeros() as i32 + 1);
+            magnitude.lo = magnitude.lo.min(e + m.trailing_zeros() as i32);
+        }
+        magnitude
+    }
+}
+
+/// Tests whether products of the given degrees in values of the given magnitudes, and all their partial products,
+/// neither overflow nor underflow.
+pub(crate) fn exact(terms: &[(Magnitude, i32)]) -> bool {
+    let hi: i32 = terms.iter().map(|(m, degree)| m.hi * degree).sum();
+    let lo: i32 = terms.iter().map(|(m, degree)| m.lo * degree).sum();
+    // The largest finite value is below 2^1024, the smallest subnormal one is 2^-1074.
+    hi + HEAD_ROOM <= 1023 && lo >= -1074
+}
@@ -0,0 +1,831 @@
+//! Exact rational reference implementations of the predicates, for differential testing.
+//!
+//! Every function evaluates the same polynomial as its geogram counterpart, in arbitrary precision rationals instead of
+//! filters and expansions. The Simulation of Simplicity (SOS) follows geogram's perturbation scheme, which orders the
+//! points by address, so a reference predicate agrees with the bridged one when both are called on the same references.
+//!
+//! These functions are orders of magnitude slower than the bridged predicates, and only meant to check them. They panic
+//! on non-finite coordinates, and where geogram asserts that an input is not degenerate.
+//!
+//! This module requires the `reference` feature.
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, Zero};
+use std::cmp::Ordering;
+
+type Q = BigRational;
+
+/// Reference for [`det_3d`](crate::det_3d).
+///
+/// ### Parameters
+/// - `a`, `b`, `c` the three rows of the matrix
+///
+/// ### Returns
+/// - the sign of the determinant of the matrix
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [1.0, 0.0, 0.0];
+/// let b = [0.0, 1.0, 0.0];
+/// let c = [0.0, 0.0, 1.0];
+///
+/// assert_eq!(1, gp::reference::det_3d(&a, &b, &c));
+/// ```
+pub fn det_3d(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> i16 {
+    sign(&det3([a, b, c].map(rational)))
+}
+
+/// Reference for [`det_4d`](crate::det_4d).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` the four rows of the matrix
+///
+/// ### Returns
+/// - the sign of the determinant of the matrix
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 1.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0, 0.0];
+/// let c = [0.0, 0.0, 1.0, 0.0];
+/// let d = [0.0, 0.0, 0.0, 1.0];
+///
+/// assert_eq!(-1, gp::reference::det_4d(&a, &b, &c, &d));
+/// ```
+pub fn det_4d(a: &[f64; 4], b: &[f64; 4], c: &[f64; 4], d: &[f64; 4]) -> i16 {
+    sign(&det4([a, b, c, d].map(rational)))
+}
+
+/// Reference for [`det_sign`](crate::det_sign).
+///
+/// The determinant is computed by Gaussian elimination over the rationals.
+///
+/// ### Parameters
+/// - `m` the rows of the matrix
+///
+/// ### Returns
+/// - the sign of the determinant of the matrix
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let m = [
+///     [0.0, 1.0, 0.0, 0.0, 0.0],
+///     [1.0, 0.0, 0.0, 0.0, 0.0],
+///     [0.0, 0.0, 2.0, 0.0, 0.0],
+///     [0.0, 0.0, 0.0, 3.0, 0.0],
+///     [0.0, 0.0, 0.0, 1.0, 4.0],
+/// ];
+///
+/// assert_eq!(-1, gp::reference::det_sign(&m));
+/// ```
+pub fn det_sign<const N: usize>(m: &[[f64; N]; N]) -> i16 {
+    let mut rows: Vec<[Q; N]> = m.iter().map(rational).collect();
+    let mut sign = 1;
+    for k in 0..N {
+        let Some(pivot) = (k..N).find(|&i| !rows[i][k].is_zero()) else {
+            return 0;
+        };
+        if pivot != k {
+            rows.swap(pivot, k);
+            sign = -sign;
+        }
+        if rows[k][k].is_negative() {
+            sign = -sign;
+        }
+        let (top, bottom) = rows.split_at_mut(k + 1);
+        let pivot = &top[k];
+        for row in bottom {
+            let factor = &row[k] / &pivot[k];
+            for j in k..N {
+                row[j] -= &factor * &pivot[j];
+            }
+        }
+    }
+    sign
+}
+
+/// Reference for [`affine_rank`](crate::affine_rank).
+///
+/// The rank of the differences to the first point is computed by Gaussian elimination over the rationals.
+///
+/// ### Parameters
+/// - `points` a non-empty set of points
+///
+/// ### Returns
+/// - the dimension of the smallest affine subspace that contains all `points`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let points = [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [2.0, 0.0, 0.0], [3.0, 1.0, 0.0]];
+///
+/// assert_eq!(2, gp::reference::affine_rank(&points));
+/// ```
+pub fn affine_rank<const N: usize>(points: &[[f64; N]]) -> usize {
+    let [first, rest @ ..] = points else {
+        panic!("the affine hull of no points has no dimension");
+    };
+    let mut rows: Vec<[Q; N]> = rest.iter().map(|p| diff(p, first)).collect();
+    let mut rank = 0;
+    for k in 0..N {
+        let Some(pivot) = (rank..rows.len()).find(|&i| !rows[i][k].is_zero()) else {
+            continue;
+        };
+        rows.swap(pivot, rank);
+        let (top, bottom) = rows.split_at_mut(rank + 1);
+        let pivot = &top[rank];
+        for row in bottom {
+            let factor = &row[k] / &pivot[k];
+            for j in k..N {
+                row[j] -= &factor * &pivot[j];
+            }
+        }
+        rank += 1;
+    }
+    rank
+}
+
+/// Reference for [`dot_3d`](crate::dot_3d).
+///
+/// ### Parameters
+/// - `a`, `b`, `c` three 3d points
+///
+/// ### Returns
+/// - the sign of the dot product between the vectors `ab` and `ac`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0];
+/// let c = [-1.0, 1.0, 0.0];
+///
+/// assert_eq!(-1, gp::reference::dot_3d(&a, &b, &c));
+/// ```
+pub fn dot_3d(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> i16 {
+    sign(&dot(&diff(b, a), &diff(c, a)))
+}
+
+/// Reference for [`orient_2d`](crate::orient_2d).
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+///
+/// ### Returns
+/// - the sign of the signed area of the triangle
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0];
+/// let b = [1.0, 0.0];
+/// let c = [0.0, 1.0];
+///
+/// assert_eq!(1, gp::reference::orient_2d(&a, &b, &c));
+/// ```
+pub fn orient_2d(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> i16 {
+    let [u, v] = [b, c].map(|p| diff(p, a));
+    sign(&det2(&u[0], &u[1], &v[0], &v[1]))
+}
+
+/// Reference for [`orient_3d`](crate::orient_3d).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron
+///
+/// ### Returns
+/// - the sign of the signed volume of the tetrahedron
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0];
+/// let c = [0.0, 1.0, 0.0];
+/// let d = [0.0, 0.0, 1.0];
+///
+/// assert_eq!(1, gp::reference::orient_3d(&a, &b, &c, &d));
+/// ```
+pub fn orient_3d(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], d: &[f64; 3]) -> i16 {
+    sign(&det3([b, c, d].map(|p| diff(p, a))))
+}
+
+/// Reference for [`points_are_colinear_3d`](crate::points_are_colinear_3d).
+///
+/// ### Parameters
+/// - `p1`, `p2`, `p3` three 3d points
+///
+/// ### Returns
+/// - whether the three points lie on a common line
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let p1 = [0.0, 0.0, 0.0];
+/// let p2 = [1.0, 1.0, 1.0];
+/// let p3 = [3.0, 3.0, 3.0];
+///
+/// assert!(gp::reference::points_are_colinear_3d(&p1, &p2, &p3));
+/// ```
+pub fn points_are_colinear_3d(p1: &[f64; 3], p2: &[f64; 3], p3: &[f64; 3]) -> bool {
+    let [u, v] = [p2, p3].map(|p| diff(p, p1));
+    [(1, 2), (2, 0), (0, 1)]
+        .iter()
+        .all(|&(i, j)| det2(&u[i], &v[i], &u[j], &v[j]).is_zero())
+}
+
+/// Reference for [`in_circle_2d_SOS`](crate::in_circle_2d_SOS).
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+/// - `p` point to test
+///
+/// ### Returns
+/// - the same sign as [`in_circle_2d_SOS`](crate::in_circle_2d_SOS), including under symbolic perturbation
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0];
+/// let b = [2.0, 0.0];
+/// let c = [1.0, 1.0];
+///
+/// assert_eq!(1, gp::reference::in_circle_2d_SOS(&a, &b, &c, &[1.0, -0.4]));
+/// assert_eq!(-1, gp::reference::in_circle_2d_SOS(&a, &b, &c, &[1.0, -1.2]));
+/// ```
+#[allow(non_snake_case)]
+pub fn in_circle_2d_SOS(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], p: &[f64; 2]) -> i16 {
+    -side3_SOS([a, b, c, p])
+}
+
+/// Reference for [`in_circle_3d_SOS`](crate::in_circle_3d_SOS).
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle
+/// - `p` point to test
+///
+/// ### Returns
+/// - the same sign as [`in_circle_3d_SOS`](crate::in_circle_3d_SOS), including under symbolic perturbation
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0];
+/// let c = [0.0, 2.0, 0.0];
+///
+/// assert_eq!(1, gp::reference::in_circle_3d_SOS(&a, &b, &c, &[1.0, 1.0, 1.0]));
+/// assert_eq!(-1, gp::reference::in_circle_3d_SOS(&a, &b, &c, &[1.0, 1.0, 2.0]));
+/// ```
+#[allow(non_snake_case)]
+pub fn in_circle_3d_SOS(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], p: &[f64; 3]) -> i16 {
+    -side3_SOS([a, b, c, p])
+}
+
+/// Reference for [`in_sphere_3d_SOS`](crate::in_sphere_3d_SOS).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron, which must not be flat
+/// - `p` point to test
+///
+/// ### Returns
+/// - the same sign as [`in_sphere_3d_SOS`](crate::in_sphere_3d_SOS), including under symbolic perturbation
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0];
+/// let c = [0.0, 2.0, 0.0];
+/// let d = [0.75, 0.75, 1.0];
+///
+/// assert_eq!(1, gp::reference::in_sphere_3d_SOS(&a, &b, &c, &d, &[0.75, 0.75, 0.5]));
+/// assert_eq!(-1, gp::reference::in_sphere_3d_SOS(&a, &b, &c, &d, &[0.75, 0.75, 1.5]));
+/// ```
+#[allow(non_snake_case)]
+pub fn in_sphere_3d_SOS(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    d: &[f64; 3],
+    p: &[f64; 3],
+) -> i16 {
+    let points = [a, b, c, d, p];
+    let lifted = [b, c, d, p].map(|q| -dot(&diff(q, a), &diff(q, a)));
+    -side4_SOS(points, lifted)
+}
+
+/// Reference for [`orient_2dlifted_SOS`](crate::orient_2dlifted_SOS).
+///
+/// ### Parameters
+/// - `a`, `b`, `c` vertices of the triangle, which must not be flat
+/// - `p` point to test
+/// - `h_a`, `h_b`, `h_c`, `h_p` the heights of the lifted points
+///
+/// ### Returns
+/// - the same sign as [`orient_2dlifted_SOS`](crate::orient_2dlifted_SOS), including under symbolic perturbation
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0];
+/// let b = [1.0, 0.0];
+/// let c = [0.0, 1.0];
+/// let p = [0.25, 0.25];
+///
+/// assert_eq!(1, gp::reference::orient_2dlifted_SOS(&a, &b, &c, &p, 0.0, 0.0, 0.0, -1.0));
+/// assert_eq!(-1, gp::reference::orient_2dlifted_SOS(&a, &b, &c, &p, 0.0, 0.0, 0.0, 1.0));
+/// ```
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub fn orient_2dlifted_SOS(
+    a: &[f64; 2],
+    b: &[f64; 2],
+    c: &[f64; 2],
+    p: &[f64; 2],
+    h_a: f64,
+    h_b: f64,
+    h_c: f64,
+    h_p: f64,
+) -> i16 {
+    let points = [a, b, c, p];
+    let rows = [(b, h_b), (c, h_c), (p, h_p)].map(|(q, h)| {
+        let [x, y] = diff(q, a);
+        [x, y, rational_scalar(h_a) - rational_scalar(h)]
+    });
+    let [r1, r2, r3] = &rows;
+    let delta1 = det2(&r2[0], &r2[1], &r3[0], &r3[1]);
+    let delta2 = det2(&r1[0], &r1[1], &r3[0], &r3[1]);
+    let delta3 = det2(&r1[0], &r1[1], &r2[0], &r2[1]);
+    let delta3_sign = sign(&delta3);
+    assert_ne!(delta3_sign, 0, "the triangle must not be flat");
+
+    let r = &delta1 * &r1[2] - &delta2 * &r2[2] + &delta3 * &r3[2];
+    if !r.is_zero() {
+        return delta3_sign * sign(&r);
+    }
+    for i in sos_order(&points) {
+        let z = match i {
+            0 => sign(&(&delta2 - &delta1 - &delta3)),
+            1 => sign(&delta1),
+            2 => -sign(&delta2),
+            _ => return 1,
+        };
+        if z != 0 {
+            return delta3_sign * z;
+        }
+    }
+    unreachable!()
+}
+
+/// Reference for [`orient_3dlifted_SOS`](crate::orient_3dlifted_SOS).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d` vertices of the tetrahedron, which must not be flat
+/// - `p` point to test
+/// - `h_a`, `h_b`, `h_c`, `h_d`, `h_p` the heights of the lifted points
+///
+/// ### Returns
+/// - the same sign as [`orient_3dlifted_SOS`](crate::orient_3dlifted_SOS), including under symbolic perturbation
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0];
+/// let c = [0.0, 1.0, 0.0];
+/// let d = [0.0, 0.0, 1.0];
+/// let p = [0.2, 0.2, 0.2];
+///
+/// assert_eq!(1, gp::reference::orient_3dlifted_SOS(&a, &b, &c, &d, &p, 0.0, 0.0, 0.0, 0.0, -1.0));
+/// assert_eq!(-1, gp::reference::orient_3dlifted_SOS(&a, &b, &c, &d, &p, 0.0, 0.0, 0.0, 0.0, 1.0));
+/// ```
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub fn orient_3dlifted_SOS(
+    a: &[f64; 3],
+    b: &[f64; 3],
+    c: &[f64; 3],
+    d: &[f64; 3],
+    p: &[f64; 3],
+    h_a: f64,
+    h_b: f64,
+    h_c: f64,
+    h_d: f64,
+    h_p: f64,
+) -> i16 {
+    let points = [a, b, c, d, p];
+    let lifted = [h_b, h_c, h_d, h_p].map(|h| rational_scalar(h_a) - rational_scalar(h));
+    -side4_SOS(points, lifted)
+}
+
+/// Reference for [`compare_distance_3d`](crate::compare_distance_3d).
+///
+/// ### Parameters
+/// - `p` the reference point
+/// - `q`, `r` the points whose distances to `p` are compared
+///
+/// ### Returns
+/// - the order of the distance from `p` to `q` relative to the distance from `p` to `r`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [0.0, 0.0, 0.0];
+///
+/// assert_eq!(Ordering::Equal, gp::reference::compare_distance_3d(&p, &[2.0, 2.0, 1.0], &[0.0, 0.0, -3.0]));
+/// ```
+pub fn compare_distance_3d(p: &[f64; 3], q: &[f64; 3], r: &[f64; 3]) -> Ordering {
+    let (pq, pr) = (diff(p, q), diff(p, r));
+    dot(&pq, &pq).cmp(&dot(&pr, &pr))
+}
+
+/// Reference for [`compare_power_distance_3d`](crate::compare_power_distance_3d).
+///
+/// ### Parameters
+/// - `p` the reference point
+/// - `q`, `r` the weighted points whose power distances to `p` are compared
+/// - `w_q`, `w_r` the weights of `q` and `r`
+///
+/// ### Returns
+/// - the order of the power distance from `p` to `q` relative to the one from `p` to `r`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+/// use std::cmp::Ordering;
+///
+/// let p = [0.0, 0.0, 0.0];
+///
+/// assert_eq!(Ordering::Equal, gp::reference::compare_power_distance_3d(&p, &[1.0, 0.0, 0.0], 1.0, &[0.0, 2.0, 0.0], 4.0));
+/// ```
+pub fn compare_power_distance_3d(
+    p: &[f64; 3],
+    q: &[f64; 3],
+    w_q: f64,
+    r: &[f64; 3],
+    w_r: f64,
+) -> Ordering {
+    let (pq, pr) = (diff(p, q), diff(p, r));
+    (dot(&pq, &pq) - rational_scalar(w_q)).cmp(&(dot(&pr, &pr) - rational_scalar(w_r)))
+}
+
+/// Reference for [`side1_3d_SOS`](crate::side1_3d_SOS).
+///
+/// ### Parameters
+/// - `p0`, `p1` the points that define the bisector
+/// - `q0` point to test
+///
+/// ### Returns
+/// - the same sign as [`side1_3d_SOS`](crate::side1_3d_SOS), including under symbolic perturbation
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let p0 = [0.0, 0.0, 0.0];
+/// let p1 = [2.0, 0.0, 0.0];
+///
+/// assert_eq!(1, gp::reference::side1_3d_SOS(&p0, &p1, &[0.5, 1.0, 0.0]));
+/// ```
+#[allow(non_snake_case)]
+pub fn side1_3d_SOS(p0: &[f64; 3], p1: &[f64; 3], q0: &[f64; 3]) -> i16 {
+    let (a, b) = (diff(q0, p1), diff(q0, p0));
+    match sign(&(dot(&a, &a) - dot(&b, &b))) {
+        0 if std::ptr::from_ref(p0) < std::ptr::from_ref(p1) => 1,
+        0 => -1,
+        sign => sign,
+    }
+}
+
+/// Reference for [`side2_3d_SOS`](crate::side2_3d_SOS).
+///
+/// ### Parameters
+/// - `p0`, `p1`, `p2` the points that define the bisectors
+/// - `q0`, `q1` the points that define the line
+///
+/// ### Returns
+/// - the same sign as [`side2_3d_SOS`](crate::side2_3d_SOS), including under symbolic perturbation
+/// - `None` if the line is parallel to the bisector of `p0` and `p1`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let p0 = [0.0, 0.0, 0.0];
+/// let p1 = [2.0, 0.0, 0.0];
+///
+/// assert_eq!(Some(1), gp::reference::side2_3d_SOS(&p0, &p1, &[1.0, 1.5, 0.0], &p0, &p1));
+/// ```
+#[allow(non_snake_case)]
+pub fn side2_3d_SOS(
+    p0: &[f64; 3],
+    p1: &[f64; 3],
+    p2: &[f64; 3],
+    q0: &[f64; 3],
+    q1: &[f64; 3],
+) -> Option<i16> {
+    let (d1, d2) = (diff(p1, p0), diff(p2, p0));
+    let (l1, l2) = (dot(&d1, &d1), dot(&d2, &d2));
+    let two = Q::from_integer(BigInt::from(2));
+    let a = |d: &[Q; 3], q: &[f64; 3]| &two * dot(d, &diff(q, p0));
+    let (a10, a11, a20, a21) = (a(&d1, q0), a(&d1, q1), a(&d2, q0), a(&d2, q1));
+    let delta = &a11 - &a10;
+    let delta_sign = sign(&delta);
+    if delta_sign == 0 {
+        return None;
+    }
+
+    let r = &delta * &l2 - &a20 * (&a11 - &l1) - &a21 * (&l1 - &a10);
+    if !r.is_zero() {
+        return Some(delta_sign * sign(&r));
+    }
+    for i in sos_order(&[p0, p1, p2]) {
+        let z = match i {
+            0 => sign(&(&delta - &a21 + &a20)),
+            1 => sign(&(&a21 - &a20)),
+            _ => return Some(-1),
+        };
+        if z != 0 {
+            return Some(delta_sign * z);
+        }
+    }
+    unreachable!()
+}
+
+/// Reference for [`orient_4d`](crate::orient_4d).
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d`, `e` vertices of the simplex
+///
+/// ### Returns
+/// - the sign of the determinant of `b - a`, `c - a`, `d - a`, `e - a`
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0, 0.0];
+/// let b = [1.0, 0.0, 0.0, 0.0];
+/// let c = [0.0, 1.0, 0.0, 0.0];
+/// let d = [0.0, 0.0, 1.0, 0.0];
+/// let e = [0.0, 0.0, 0.0, 1.0];
+///
+/// assert_eq!(1, gp::reference::orient_4d(&a, &b, &c, &d, &e));
+/// ```
+pub fn orient_4d(a: &[f64; 4], b: &[f64; 4], c: &[f64; 4], d: &[f64; 4], e: &[f64; 4]) -> i16 {
+    sign(&det4([b, c, d, e].map(|p| diff(p, a))))
+}
+
+/// Reference for [`in_sphere_4d_SOS`](crate::in_sphere_4d_SOS).
+///
+/// The lifted determinant is evaluated as a polynomial in the infinitesimals: each coefficient is the determinant of
+/// the lifted matrix whose last column is replaced by the derivative w.r.t. one infinitesimal.
+///
+/// ### Parameters
+/// - `a`, `b`, `c`, `d`, `e` vertices of the simplex, which must not be flat
+/// - `p` point to test
+///
+/// ### Returns
+/// - the same sign as [`in_sphere_4d_SOS`](crate::in_sphere_4d_SOS), including under symbolic perturbation
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// let a = [0.0, 0.0, 0.0, 0.0];
+/// let b = [2.0, 0.0, 0.0, 0.0];
+/// let c = [0.0, 2.0, 0.0, 0.0];
+/// let d = [0.0, 0.0, 2.0, 0.0];
+/// let e = [0.0, 0.0, 0.0, 2.0];
+///
+/// assert_eq!(1, gp::reference::in_sphere_4d_SOS(&a, &b, &c, &d, &e, &[0.5, 0.5, 0.5, 0.5]));
+/// ```
+#[allow(non_snake_case)]
+pub fn in_sphere_4d_SOS(
+    a: &[f64; 4],
+    b: &[f64; 4],
+    c: &[f64; 4],
+    d: &[f64; 4],
+    e: &[f64; 4],
+    p: &[f64; 4],
+) -> i16 {
+    let points = [a, b, c, d, e, p];
+    let rows = [b, c, d, e, p].map(|q| diff(q, a));
+    let o = sign(&det4([0, 1, 2, 3].map(|i| rows[i].clone())));
+    assert_ne!(o, 0, "the simplex must not be flat");
+
+    // The lifted matrix, with the last column `|q - a|^2 - (eps_q - eps_a)`.
+    let with_column = |column: [Q; 5]| {
+        let lifted: [[Q; 5]; 5] = std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                if j < 4 {
+                    rows[i][j].clone()
+                } else {
+                    column[i].clone()
+                }
+            })
+        });
+        sign(&det5(lifted))
+    };
+    let r = with_column(rows.clone().map(|u| dot(&u, &u)));
+    if r != 0 {
+        return -o * r;
+    }
+    for i in sos_order(&points) {
+        let column = std::array::from_fn(|k| match i {
+            0 => Q::from_integer(1.into()),
+            _ if k + 1 == i => Q::from_integer((-1).into()),
+            _ => Q::zero(),
+        });
+        let z = with_column(column);
+        if z != 0 {
+            return -o * z;
+        }
+    }
+    unreachable!()
+}
+
+/// Evaluates geogram's `side3` predicate with `q0, q1, q2 = p0, p1, p2`, i.e. whether `p3` is farther from the
+/// circum-center of `p0, p1, p2` than `p0`.
+#[allow(non_snake_case)]
+fn side3_SOS<const N: usize>(points: [&[f64; N]; 4]) -> i16 {
+    let [p0, p1, p2, p3] = points;
+    let l = [p1, p2, p3].map(|p| dot(&diff(p, p0), &diff(p, p0)));
+    let two = Q::from_integer(BigInt::from(2));
+    let a = [p1, p2, p3].map(|p| [p0, p1, p2].map(|q| dot(&diff(p, p0), &diff(q, p0)) * &two));
+
+    let b = [
+        [
+            det2(&a[0][1], &a[0][2], &a[1][1], &a[1][2]),
+            &a[1][1] - &a[1][2],
+            &a[0][2] - &a[0][1],
+        ],
+        [
+            det2(&a[0][2], &a[0][0], &a[1][2], &a[1][0]),
+            &a[1][2] - &a[1][0],
+            &a[0][0] - &a[0][2],
+        ],
+        [
+            det2(&a[0][0], &a[0][1], &a[1][0], &a[1][1]),
+            &a[1][0] - &a[1][1],
+            &a[0][1] - &a[0][0],
+        ],
+    ];
+    let delta = &b[0][0] + &b[1][0] + &b[2][0];
+    let delta_sign = sign(&delta);
+    assert_ne!(delta_sign, 0, "the triangle must not be flat");
+
+    let lambda = b.clone().map(|[b0, b1, b2]| b1 * &l[0] + b2 * &l[1] + b0);
+    let r = (0..3).fold(&delta * &l[2], |r, j| r - &a[2][j] * &lambda[j]);
+    if !r.is_zero() {
+        return delta_sign * sign(&r);
+    }
+    for i in sos_order(&points) {
+        let z = match i {
+            0 => (0..3).fold(delta.clone(), |z, j| z - &a[2][j] * (&b[j][1] + &b[j][2])),
+            1 | 2 => (0..3).fold(Q::zero(), |z, j| z + &a[2][j] * &b[j][i]),
+            _ => return -1,
+        };
+        if !z.is_zero() {
+            return delta_sign * sign(&z);
+        }
+    }
+    unreachable!()
+}
+
+/// Evaluates geogram's `side4` predicate in 3d, where `lifted[i]` is the last column of the row of `points[i + 1]`.
+#[allow(non_snake_case)]
+fn side4_SOS(points: [&[f64; 3]; 5], lifted: [Q; 4]) -> i16 {
+    let [p0, rest @ ..] = points;
+    let rows = rest.map(|p| diff(p, p0));
+    let delta = [0, 1, 2, 3].map(|skip| {
+        let mut others = (0..4).filter(|&i| i != skip).map(|i| rows[i].clone());
+        det3([(); 3].map(|_| others.next().unwrap()))
+    });
+    let delta4_sign = sign(&delta[3]);
+    assert_ne!(delta4_sign, 0, "the tetrahedron must not be flat");
+
+    let r = &delta[0] * &lifted[0] - &delta[1] * &lifted[1] + &delta[2] * &lifted[2]
+        - &delta[3] * &lifted[3];
+    if !r.is_zero() {
+        return delta4_sign * sign(&r);
+    }
+    for i in sos_order(&points) {
+        let z = match i {
+            0 => sign(&(&delta[1] - &delta[0] + &delta[3] - &delta[2])),
+            1 | 3 => sign(&delta[i - 1]),
+            2 => -sign(&delta[1]),
+            _ => return -1,
+        };
+        if z != 0 {
+            return delta4_sign * z;
+        }
+    }
+    unreachable!()
+}
+
+/// Returns the indices of `points` in the order in which geogram perturbs them, i.e. by increasing address. If a point
+/// is passed more than once, each occurrence is treated like its first one.
+fn sos_order<const N: usize>(points: &[&[f64; N]]) -> Vec<usize> {
+    let mut addresses = points
+        .iter()
+        .map(|p| *p as *const [f64; N])
+        .collect::<Vec<_>>();
+    addresses.sort();
+    addresses
+        .iter()
+        .map(|&address| {
+            points
+                .iter()
+                .position(|&p| std::ptr::eq(p, address))
+                .unwrap()
+        })
+        .collect()
+}
+
+fn rational_scalar(x: f64) -> Q {
+    Q::from_float(x).expect("coordinates must be finite")
+}
+
+fn rational<const N: usize>(p: &[f64; N]) -> [Q; N] {
+    p.map(rational_scalar)
+}
+
+fn diff<const N: usize>(p: &[f64; N], q: &[f64; N]) -> [Q; N] {
+    let (p, q) = (rational(p), rational(q));
+    std::array::from_fn(|i| &p[i] - &q[i])
+}
+
+fn dot<const N: usize>(u: &[Q; N], v: &[Q; N]) -> Q {
+    u.iter().zip(v).map(|(a, b)| a * b).sum()
+}
+
+fn det4(rows: [[Q; 4]; 4]) -> Q {
+    let [a, rest @ ..] = &rows;
+    (0..4).fold(Q::zero(), |acc, i| {
+        let minor = rest.clone().map(|r| {
+            let mut cols = (0..4).filter(|&j| j != i).map(|j| r[j].clone());
+            [(); 3].map(|_| cols.next().unwrap())
+        });
+        let term = &a[i] * det3(minor);
+        if i % 2 == 0 {
+            acc + term
+        } else {
+            acc - term
+        }
+    })
+}
+
+fn det5(rows: [[Q; 5]; 5]) -> Q {
+    let [a, rest @ ..] = &rows;
+    (0..5).fold(Q::zero(), |acc, i| {
+        let minor = rest.clone().map(|r| {
+            let mut cols = (0..5).filter(|&j| j != i).map(|j| r[j].clone());
+            [(); 4].map(|_| cols.next().unwrap())
+        });
+        let term = &a[i] * det4(minor);
+        if i % 2 == 0 {
+            acc + term
+        } else {
+            acc - term
+        }
+    })
+}
+
+fn det2(a11: &Q, a12: &Q, a21: &Q, a22: &Q) -> Q {
+    a11 * a22 - a12 * a21
+}
+
+fn det3(rows: [[Q; 3]; 3]) -> Q {
+    let [a, b, c] = &rows;
+    &a[0] * det2(&b[1], &b[2], &c[1], &c[2]) - &b[0] * det2(&a[1], &a[2], &c[1], &c[2])
+        + &c[0] * det2(&a[1], &a[2], &b[1], &b[2])
+}
+
+fn sign(x: &Q) -> i16 {
+    if x.is_positive() {
+        1
+    } else if x.is_negative() {
+        -1
+    } else {
+        0
+    }
+}
@@ -0,0 +1,155 @@
+//! Constrained triangulation of a triangle in 3d, with additional vertices and edges on it.
+//!
+//! The vertices are [`ImplicitPoint3d`]s on the plane of the triangle, and all decisions are taken by the orientation
+//! of their projections along a coordinate axis, so the triangulation is exact. It is not Delaunay, as the in-circle
+//! test on implicit points would be too costly, but its triangles are never flat.
+
+use crate::implicit::{self, ImplicitPoint3d};
+use std::collections::VecDeque;
+
+/// A triangulation of the projection of a triangle, whose triangles are counter-clockwise in the projection.
+struct Triangulation<'a> {
+    points: &'a [ImplicitPoint3d],
+    axis: usize,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl Triangulation<'_> {
+    fn orient(&self, a: usize, b: usize, c: usize) -> i16 {
+        implicit::orient_2d_projected(&self.points[a], &self.points[b], &self.points[c], self.axis)
+    }
+
+    /// Finds the triangle with the directed edge `ab`.
+    fn with_edge(&self, a: usize, b: usize) -> Option<usize> {
+        (0..self.triangles.len()).find(|&t| {
+            let [p, q, r] = self.triangles[t];
+            [(p, q), (q, r), (r, p)].contains(&(a, b))
+        })
+    }
+
+    /// Inserts a vertex inside of the triangulation, or on its boundary.
+    fn insert(&mut self, p: usize) {
+        for t in 0..self.triangles.len() {
+            let [a, b, c] = self.triangles[t];
+            let sides = [(a, b), (b, c), (c, a)].map(|(u, v)| self.orient(u, v, p));
+            if sides.iter().any(|&s| s < 0) {
+                continue;
+            }
+            match sides.iter().position(|&s| s == 0) {
+                None => {
+                    self.triangles[t] = [a, b, p];
+                    self.triangles.extend([[b, c, p], [c, a, p]]);
+                }
+                Some(i) => {
+                    // The vertex is on an edge, which is split in both triangles that share it.
+                    let [u, v, w] = [0, 1, 2].map(|k| self.triangles[t][(i + k) % 3]);
+                    self.triangles[t] = [u, p, w];
+                    self.triangles.push([p, v, w]);
+                    if let Some(n) = self.with_edge(v, u) {
+                        let x = self.triangles[n]
+                            .into_iter()
+                            .find(|&x| x != u && x != v)
+                            .unwrap();
+                        self.triangles[n] = [v, p, x];
+                        self.triangles.push([p, u, x]);
+                    }
+                }
+            }
+            return;
+        }
+    }
+
+    /// Inserts the edge `ab` between two vertices, split at the vertices on it.
+    ///
+    /// ### Returns
+    /// - the pieces of the edge, between consecutive vertices
+    fn insert_edge(&mut self, a: usize, b: usize) -> Vec<[usize; 2]> {
+        if self.with_edge(a, b).is_some() || self.with_edge(b, a).is_some() {
+            return vec![[a, b]];
+        }
+        let vertices: Vec<usize> = self.triangles.iter().flatten().copied().collect();
+        let between = vertices.iter().copied().find(|&v| {
+            v != a && v != b && self.orient(a, b, v) == 0 && {
+                let [pa, pv, pb] = [a, v, b].map(|x| &self.points[x]);
+                implicit::lexico_cmp_3d(pa, pv) == implicit::lexico_cmp_3d(pv, pb)
+            }
+        });
+        if let Some(v) = between {
+            let mut pieces = self.insert_edge(a, v);
+            pieces.extend(self.insert_edge(v, b));
+            return pieces;
+        }
+
+        // Flips the edges crossed by `ab` until none is left, like Sloan. A crossed edge whose quadrilateral is not
+        // convex is retried later, there always is one that can be flipped.
+        let (points, axis) = (self.points, self.axis);
+        let orient = |x: usize, y: usize, z: usize| {
+            implicit::orient_2d_projected(&points[x], &points[y], &points[z], axis)
+        };
+        let crossing = |x: usize, y: usize| {
+            orient(a, b, x) * orient(a, b, y) < 0 && orient(x, y, a) * orient(x, y, b) < 0
+        };
+        let mut queue: VecDeque<[usize; 2]> = self
+            .triangles
+            .iter()
+            .flat_map(|&[p, q, r]| [[p, q], [q, r], [r, p]])
+            .filter(|&[x, y]| x < y && crossing(x, y))
+            .collect();
+        while let Some([x, y]) = queue.pop_front() {
+            let first = self.with_edge(x, y).expect("crossed edges are inner edges");
+            let second = self.with_edge(y, x).expect("crossed edges are inner edges");
+            let apex = |t: [usize; 3]| t.into_iter().find(|&v| v != x && v != y).unwrap();
+            let (p, q) = (apex(self.triangles[first]), apex(self.triangles[second]));
+            if self.orient(x, q, p) > 0 && self.orient(y, p, q) > 0 {
+                self.triangles[first] = [x, q, p];
+                self.triangles[second] = [y, p, q];
+                if crossing(p, q) {
+                    queue.push_back([p, q]);
+                }
+            } else {
+                queue.push_back([x, y]);
+            }
+        }
+        vec![[a, b]]
+    }
+}
+
+/// Triangulates a triangle with additional vertices and constrained edges.
+///
+/// ### Parameters
+/// - `points` the vertices, starting with the vertices of the triangle, all on the plane of the triangle and inside of
+///   it
+/// - `axis` a coordinate axis, along which the triangle does not project to a segment
+/// - `edges` the constrained edges, as pairs of vertex indices, which only meet at their endpoints
+///
+/// ### Returns
+/// - the triangles, with the orientation of the first three points, and the pieces of the constrained edges
+pub(crate) fn triangulate(
+    points: &[ImplicitPoint3d],
+    axis: usize,
+    edges: &[[usize; 2]],
+) -> (Vec<[usize; 3]>, Vec<[usize; 2]>) {
+    let mut triangulation = Triangulation {
+        points,
+        axis,
+        triangles: Vec::new(),
+    };
+    let flipped = triangulation.orient(0, 1, 2) < 0;
+    triangulation
+        .triangles
+        .push(if flipped { [0, 2, 1] } else { [0, 1, 2] });
+    for p in 3..points.len() {
+        triangulation.insert(p);
+    }
+    let pieces = edges
+        .iter()
+        .flat_map(|&[a, b]| triangulation.insert_edge(a, b))
+        .collect();
+    let mut triangles = triangulation.triangles;
+    if flipped {
+        for t in &mut triangles {
+            t.swap(1, 2);
+        }
+    }
+    (triangles, pieces)
+}
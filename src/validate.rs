@@ -0,0 +1,318 @@
+//! Exact validity checks for indexed triangle and tetrahedral meshes.
+//!
+//! A mesh is given as a slice of points and a slice of elements, where each element lists the indices of its vertices.
+//! Triangles in 2d are expected to be oriented counter-clockwise, tetrahedra positively w.r.t. [`orient_3d`]. All
+//! checks are exact and never panic, whatever the input, so they can be used as a pre-flight check on untrusted meshes.
+//!
+//! Exact predicates require [`initialize`](crate::initialize) to be called first.
+
+use crate::{
+    in_circle_2d_SOS, in_circle_3d_SOS, in_sphere_3d_SOS, lexico_cmp_nd, orient_2d, orient_3d,
+    points_are_colinear_3d, points_are_identical_2d, points_are_identical_3d,
+};
+use std::collections::HashMap;
+
+/// The defects found in a mesh.
+///
+/// Elements are referred to by their index in the element slice, vertices by their index in the point slice. All lists
+/// are sorted, and within each pair `first < second` holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MeshReport {
+    /// Elements that reference a vertex out of range, or the same vertex more than once. They are skipped by all other
+    /// checks.
+    pub invalid_elements: Vec<usize>,
+    /// Elements with a negative orientation. Not reported for triangles in 3d, which have no intrinsic orientation.
+    pub inverted_elements: Vec<usize>,
+    /// Elements with zero area or volume.
+    pub degenerate_elements: Vec<usize>,
+    /// Pairs of distinct vertices with identical coordinates.
+    pub duplicate_vertices: Vec<[usize; 2]>,
+    /// Facets, i.e. edges of triangles or triangles of tetrahedra, shared by more than two elements, as sorted vertex
+    /// indices.
+    pub non_manifold_facets: Vec<Vec<usize>>,
+    /// Pairs of adjacent elements that traverse their common facet in the same direction, i.e. that are inconsistently
+    /// oriented or fold over each other.
+    pub inconsistent_facets: Vec<[usize; 2]>,
+    /// Pairs of adjacent elements whose common facet is not locally Delaunay.
+    pub non_delaunay_facets: Vec<[usize; 2]>,
+}
+
+impl MeshReport {
+    /// Tests whether the mesh is free of defects, apart from violations of the Delaunay property.
+    pub fn is_valid(&self) -> bool {
+        self.invalid_elements.is_empty()
+            && self.inverted_elements.is_empty()
+            && self.degenerate_elements.is_empty()
+            && self.duplicate_vertices.is_empty()
+            && self.non_manifold_facets.is_empty()
+            && self.inconsistent_facets.is_empty()
+    }
+
+    /// Tests whether every interior facet of the mesh is locally Delaunay.
+    pub fn is_delaunay(&self) -> bool {
+        self.non_delaunay_facets.is_empty()
+    }
+}
+
+/// Checks a triangle mesh in 2d.
+///
+/// Triangles are expected to be oriented counter-clockwise. The Delaunay property is checked with
+/// [`in_circle_2d_SOS`] on each edge shared by two non-degenerate triangles.
+///
+/// ### Parameters
+/// - `points` the vertices of the mesh
+/// - `triangles` the triangles of the mesh, as triples of vertex indices
+///
+/// ### Returns
+/// - the defects of the mesh
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let points = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 3.0]];
+///
+/// let report = gp::validate::validate_triangle_mesh_2d(&points, &[[0, 1, 2], [0, 2, 3]]);
+/// assert!(report.is_valid() && report.is_delaunay());
+///
+/// // Flipping a triangle inverts it, and breaks the consistency with its neighbor
+/// let report = gp::validate::validate_triangle_mesh_2d(&points, &[[0, 1, 2], [0, 3, 2]]);
+/// assert_eq!(vec![1], report.inverted_elements);
+/// assert_eq!(vec![[0, 1]], report.inconsistent_facets);
+/// ```
+pub fn validate_triangle_mesh_2d(points: &[[f64; 2]], triangles: &[[usize; 3]]) -> MeshReport {
+    let orientation = |t: &[usize; 3]| orient_2d(&points[t[0]], &points[t[1]], &points[t[2]]);
+    let mut report = validate_elements(
+        points.len(),
+        triangles,
+        &TRIANGLE_FACETS,
+        orientation,
+        |t, p| in_circle_2d_SOS(&points[t[0]], &points[t[1]], &points[t[2]], &points[p]),
+    );
+    report.duplicate_vertices = duplicate_vertices(points, points_are_identical_2d);
+    report
+}
+
+/// Checks a triangle mesh in 3d, e.g. a surface mesh.
+///
+/// Triangles in 3d have no intrinsic orientation, so only their orientations relative to each other are checked. The
+/// Delaunay property is checked with [`in_circle_3d_SOS`] on each edge shared by two non-degenerate triangles.
+///
+/// ### Parameters
+/// - `points` the vertices of the mesh
+/// - `triangles` the triangles of the mesh, as triples of vertex indices
+///
+/// ### Returns
+/// - the defects of the mesh
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let points = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [2.0, 0.0, 0.0]];
+///
+/// // The boundary of a tetrahedron, with one spare vertex
+/// let tetrahedron = [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]];
+/// assert!(gp::validate::validate_triangle_mesh_3d(&points, &tetrahedron).is_valid());
+///
+/// // Out of range and repeated vertex indices are reported, instead of panicking
+/// let report = gp::validate::validate_triangle_mesh_3d(&points, &[[0, 1, 5], [0, 1, 1], [0, 1, 4]]);
+/// assert_eq!(vec![0, 1], report.invalid_elements);
+/// assert_eq!(vec![2], report.degenerate_elements);
+/// ```
+pub fn validate_triangle_mesh_3d(points: &[[f64; 3]], triangles: &[[usize; 3]]) -> MeshReport {
+    // Without an intrinsic orientation, non-degenerate triangles count as positively oriented.
+    let orientation = |t: &[usize; 3]| {
+        i16::from(!points_are_colinear_3d(
+            &points[t[0]],
+            &points[t[1]],
+            &points[t[2]],
+        ))
+    };
+    let mut report = validate_elements(
+        points.len(),
+        triangles,
+        &TRIANGLE_FACETS,
+        orientation,
+        |t, p| in_circle_3d_SOS(&points[t[0]], &points[t[1]], &points[t[2]], &points[p]),
+    );
+    report.duplicate_vertices = duplicate_vertices(points, points_are_identical_3d);
+    report
+}
+
+/// Checks a tetrahedral mesh.
+///
+/// Tetrahedra are expected to be oriented positively w.r.t. [`orient_3d`]. The Delaunay property is checked with
+/// [`in_sphere_3d_SOS`] on each triangle shared by two non-degenerate tetrahedra.
+///
+/// ### Parameters
+/// - `points` the vertices of the mesh
+/// - `tetrahedra` the tetrahedra of the mesh, as quadruples of vertex indices
+///
+/// ### Returns
+/// - the defects of the mesh
+///
+/// # Example
+/// ```
+/// use geogram_predicates as gp;
+///
+/// gp::initialize();
+///
+/// let points = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.1, 0.1, -0.1]];
+///
+/// // The last point is inside the circum-sphere of the first tetrahedron
+/// let report = gp::validate::validate_tet_mesh(&points, &[[0, 1, 2, 3], [0, 2, 1, 4]]);
+/// assert!(report.is_valid());
+/// assert_eq!(vec![[0, 1]], report.non_delaunay_facets);
+///
+/// let report = gp::validate::validate_tet_mesh(&points, &[[0, 2, 1, 3]]);
+/// assert_eq!(vec![0], report.inverted_elements);
+/// ```
+pub fn validate_tet_mesh(points: &[[f64; 3]], tetrahedra: &[[usize; 4]]) -> MeshReport {
+    let orientation =
+        |t: &[usize; 4]| orient_3d(&points[t[0]], &points[t[1]], &points[t[2]], &points[t[3]]);
+    let mut report = validate_elements(
+        points.len(),
+        tetrahedra,
+        &TETRAHEDRON_FACETS,
+        orientation,
+        |t, p| {
+            in_sphere_3d_SOS(
+                &points[t[0]],
+                &points[t[1]],
+                &points[t[2]],
+                &points[t[3]],
+                &points[p],
+            )
+        },
+    );
+    report.duplicate_vertices = duplicate_vertices(points, points_are_identical_3d);
+    report
+}
+
+/// The facet opposite to each vertex of a triangle, oriented such that adjacent triangles traverse it in opposite
+/// directions.
+const TRIANGLE_FACETS: [&[usize]; 3] = [&[1, 2], &[2, 0], &[0, 1]];
+
+/// The facet opposite to each vertex of a tetrahedron, oriented outwards.
+const TETRAHEDRON_FACETS: [&[usize]; 4] = [&[1, 2, 3], &[0, 3, 2], &[0, 1, 3], &[0, 2, 1]];
+
+/// Runs the checks shared by all kinds of meshes, apart from the search for duplicate vertices.
+///
+/// `orientation` gives the sign of a valid element, `in_circle` tests a point against the circum-circle or -sphere of a
+/// positively oriented element.
+fn validate_elements<const K: usize>(
+    nb_points: usize,
+    elements: &[[usize; K]],
+    facets: &[&[usize]; K],
+    orientation: impl Fn(&[usize; K]) -> i16,
+    in_circle: impl Fn(&[usize; K], usize) -> i16,
+) -> MeshReport {
+    let mut report = MeshReport::default();
+
+    let mut signs = vec![0; elements.len()];
+    for (e, element) in elements.iter().enumerate() {
+        let out_of_range = element.iter().any(|&v| v >= nb_points);
+        let repeated = (0..K).any(|i| element[i + 1..].contains(&element[i]));
+        if out_of_range || repeated {
+            report.invalid_elements.push(e);
+            continue;
+        }
+        signs[e] = orientation(element);
+        match signs[e] {
+            0 => report.degenerate_elements.push(e),
+            s if s < 0 => report.inverted_elements.push(e),
+            _ => {}
+        }
+    }
+
+    // Group the oriented facets of the valid elements by their vertex sets.
+    let mut facet_elements: HashMap<Vec<usize>, Vec<(usize, usize)>> = HashMap::new();
+    for (e, element) in elements.iter().enumerate() {
+        if report.invalid_elements.binary_search(&e).is_err() {
+            for (i, facet) in facets.iter().enumerate() {
+                let mut key: Vec<usize> = facet.iter().map(|&k| element[k]).collect();
+                key.sort_unstable();
+                facet_elements.entry(key).or_default().push((e, i));
+            }
+        }
+    }
+
+    for (key, incident) in facet_elements {
+        match incident[..] {
+            [(e, i), (f, j)] => {
+                let pair = [e.min(f), e.max(f)];
+                let facet = |e: usize, i: usize| {
+                    facets[i]
+                        .iter()
+                        .map(|&k| elements[e][k])
+                        .collect::<Vec<_>>()
+                };
+                if same_direction(&facet(e, i), &facet(f, j)) {
+                    report.inconsistent_facets.push(pair);
+                }
+                if signs[e] != 0
+                    && signs[f] != 0
+                    && !is_locally_delaunay(&elements[e], signs[e], elements[f][j], &in_circle)
+                {
+                    report.non_delaunay_facets.push(pair);
+                }
+            }
+            [_] => {}
+            _ => report.non_manifold_facets.push(key),
+        }
+    }
+
+    report.non_manifold_facets.sort_unstable();
+    report.inconsistent_facets.sort_unstable();
+    report.non_delaunay_facets.sort_unstable();
+    report
+}
+
+/// Tests whether the vertex `p` of a neighbor is outside the circum-circle or -sphere of `element`, which has the
+/// orientation `sign`.
+fn is_locally_delaunay<const K: usize>(
+    element: &[usize; K],
+    sign: i16,
+    p: usize,
+    in_circle: impl Fn(&[usize; K], usize) -> i16,
+) -> bool {
+    let mut element = *element;
+    if sign < 0 {
+        // The in-circle and in-sphere predicates expect positively oriented elements.
+        element.swap(0, 1);
+    }
+    in_circle(&element, p) <= 0
+}
+
+/// Tests whether two oriented facets with the same vertices are traversed in the same direction.
+fn same_direction(f: &[usize], g: &[usize]) -> bool {
+    let n = f.len();
+    if n == 2 {
+        // Rotating an edge reverses it, so edges are compared as they are.
+        return f == g;
+    }
+    let offset = g.iter().position(|&v| v == f[0]).unwrap_or(0);
+    (0..n).all(|k| f[k] == g[(k + offset) % n])
+}
+
+fn duplicate_vertices<const N: usize>(
+    points: &[[f64; N]],
+    identical: impl Fn(&[f64; N], &[f64; N]) -> bool,
+) -> Vec<[usize; 2]> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&i, &j| lexico_cmp_nd(&points[i], &points[j]).then(i.cmp(&j)));
+
+    let mut pairs = Vec::new();
+    for run in order.chunk_by(|&i, &j| identical(&points[i], &points[j])) {
+        for (k, &first) in run.iter().enumerate() {
+            pairs.extend(run[k + 1..].iter().map(|&second| [first, second]));
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
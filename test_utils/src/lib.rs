@@ -8,7 +8,7 @@ pub fn write_to_png(data: &[Ordering], path: &Path, width: usize, height: usize)
     use std::io::BufWriter;
 
     let file = File::create(path).unwrap();
-    let ref mut w = BufWriter::new(file);
+    let w = &mut BufWriter::new(file);
 
     let mut encoder = png::Encoder::new(w, width as u32, height as u32);
     encoder.set_color(png::ColorType::Grayscale);
@@ -43,9 +43,9 @@ where
         for _ in 0..width {
             let p = [xd, yd];
             data.push(predicate(p).partial_cmp(&0.).unwrap());
-            xd = nextafter(xd, std::f64::INFINITY);
+            xd = nextafter(xd, f64::INFINITY);
         }
-        yd = nextafter(yd, std::f64::INFINITY);
+        yd = nextafter(yd, f64::INFINITY);
     }
     data
 }
@@ -0,0 +1,116 @@
+//! Tests that arrangements of segments are valid planar subdivisions, on inputs with many degeneracies.
+
+use geogram_predicates as gp;
+use gp::arrangement::Arrangement2d;
+use proptest::prelude::*;
+
+mod common;
+
+fn segments() -> impl Strategy<Value = Vec<[[f64; 2]; 2]>> {
+    prop::collection::vec(
+        prop::array::uniform2(prop::array::uniform2(common::coordinate(-4..4, 4.0))),
+        0..12,
+    )
+}
+
+/// Computes the signed area of a boundary cycle from the rounded vertices.
+fn area(arrangement: &Arrangement2d, h: usize) -> f64 {
+    arrangement
+        .cycle(h)
+        .iter()
+        .map(|&e| {
+            let [p, q] =
+                [arrangement.origin(e), arrangement.destination(e)].map(|v| arrangement.point(v));
+            p[0] * q[1] - p[1] * q[0]
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+proptest! {
+    #[test]
+    fn half_edges_are_linked_consistently(segments in segments()) {
+        gp::initialize();
+        let arrangement = Arrangement2d::new(&segments);
+        for h in 0..arrangement.nb_half_edges() {
+            let next = arrangement.next(h);
+            prop_assert_eq!(h, arrangement.prev(next));
+            prop_assert_eq!(h, arrangement.twin(arrangement.twin(h)));
+            prop_assert_eq!(arrangement.destination(h), arrangement.origin(next));
+            prop_assert_eq!(arrangement.face(h), arrangement.face(next));
+            prop_assert_ne!(arrangement.origin(h), arrangement.destination(h));
+            prop_assert!(arrangement.outgoing(arrangement.origin(h)).contains(&h));
+        }
+    }
+
+    #[test]
+    fn euler_characteristic_counts_components(segments in segments()) {
+        gp::initialize();
+        let arrangement = Arrangement2d::new(&segments);
+        // Each connected component is a hole of exactly one face.
+        let components: usize = (0..arrangement.nb_faces()).map(|f| arrangement.holes(f).len()).sum();
+        let [v, e, f] = [arrangement.nb_vertices(), arrangement.nb_half_edges() / 2, arrangement.nb_faces()];
+        prop_assert_eq!(v + f, e + 1 + components);
+        prop_assert_eq!(None, arrangement.outer_boundary(Arrangement2d::UNBOUNDED));
+    }
+
+    #[test]
+    fn faces_are_oriented_consistently(segments in segments()) {
+        gp::initialize();
+        let arrangement = Arrangement2d::new(&segments);
+        for f in 0..arrangement.nb_faces() {
+            if let Some(h) = arrangement.outer_boundary(f) {
+                prop_assert!(area(&arrangement, h) > 0.0);
+            }
+            for &h in arrangement.holes(f) {
+                // Trees have no area, up to rounding errors.
+                prop_assert!(area(&arrangement, h) <= 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn vertices_are_ordered_along_segments(segments in segments()) {
+        gp::initialize();
+        let arrangement = Arrangement2d::new(&segments);
+        for (s, [u, v]) in segments.iter().enumerate() {
+            let along = arrangement.segment_vertices(s);
+            if along.is_empty() {
+                prop_assert!(gp::points_are_identical_2d(u, v));
+                continue;
+            }
+            prop_assert_eq!(*u, arrangement.point(along[0]));
+            prop_assert_eq!(*v, arrangement.point(along[along.len() - 1]));
+            for w in along {
+                prop_assert_eq!(0, gp::implicit::orient_2d(&(*u).into(), &(*v).into(), arrangement.vertex(*w)));
+            }
+            for pair in along.windows(2) {
+                // Vertices are sorted lexicographically, so they are monotone along the segment.
+                prop_assert_eq!(pair[0] < pair[1], gp::lexico_cmp_2d(u, v).is_lt());
+                let h = arrangement.outgoing(pair[0]).iter().find(|&&h| arrangement.destination(h) == pair[1]);
+                prop_assert!(h.is_some_and(|&h| arrangement.segments(h).contains(&s)));
+            }
+        }
+    }
+}
+
+#[test]
+fn nested_squares_are_holes() {
+    gp::initialize();
+    let square = |r: f64| {
+        let [a, b, c, d] = [[-r, -r], [r, -r], [r, r], [-r, r]];
+        [[a, b], [b, c], [c, d], [d, a]]
+    };
+    let segments: Vec<_> = [3.0, 2.0, 1.0].into_iter().flat_map(square).collect();
+    let arrangement = Arrangement2d::new(&segments);
+    assert_eq!(4, arrangement.nb_faces());
+    assert_eq!(1, arrangement.holes(Arrangement2d::UNBOUNDED).len());
+    // The faces are sorted by their lowest vertex, i.e. from the outer to the inner square.
+    for f in 1..3 {
+        let [hole] = arrangement.holes(f) else {
+            panic!("the annulus {f} has one hole");
+        };
+        assert_eq!(f + 1, arrangement.face(arrangement.twin(*hole)));
+    }
+    assert!(arrangement.holes(3).is_empty());
+}
@@ -0,0 +1,95 @@
+//! Tests that boolean operations on polygons are consistent with each other, on inputs with many degeneracies.
+
+use geogram_predicates as gp;
+use gp::boolean::{BooleanOp, PolygonWithHoles};
+use proptest::prelude::*;
+
+mod common;
+
+use common::assert_close;
+
+/// A few rings, which may intersect each other and themselves.
+fn rings() -> impl Strategy<Value = Vec<Vec<[f64; 2]>>> {
+    prop::collection::vec(
+        prop::collection::vec(prop::array::uniform2(common::coordinate(-4..4, 4.0)), 3..6),
+        1..3,
+    )
+}
+
+fn ring_area(ring: &[[f64; 2]]) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|i| {
+            let [p, q] = [ring[i], ring[(i + 1) % n]];
+            p[0] * q[1] - p[1] * q[0]
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Computes the area of a result from the rounded vertices, where holes have a negative area.
+fn area(polygons: &[PolygonWithHoles]) -> f64 {
+    polygons
+        .iter()
+        .flat_map(|polygon| polygon.approximate())
+        .map(|ring| ring_area(&ring))
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn areas_satisfy_inclusion_exclusion(a in rings(), b in rings()) {
+        gp::initialize();
+        let [union, intersection, difference, xor] =
+            [BooleanOp::Union, BooleanOp::Intersection, BooleanOp::Difference, BooleanOp::Xor]
+                .map(|op| area(&gp::boolean::polygon_boolean(&a, &b, op)));
+        let [area_a, area_b] = [&a, &b].map(|rings| area(&gp::boolean::polygon_boolean(rings, &[], BooleanOp::Union)));
+        assert_close(union + intersection, area_a + area_b)?;
+        assert_close(xor, union - intersection)?;
+        assert_close(difference, area_a - intersection)?;
+    }
+
+    #[test]
+    fn boundaries_are_oriented(a in rings(), b in rings()) {
+        gp::initialize();
+        for polygon in gp::boolean::polygon_boolean(&a, &b, BooleanOp::Xor) {
+            let [outer, holes @ ..] = &polygon.approximate()[..] else {
+                unreachable!("a polygon has an outer boundary");
+            };
+            prop_assert!(outer.len() >= 3 && ring_area(outer) > 0.0);
+            for hole in holes {
+                prop_assert!(hole.len() >= 3 && ring_area(hole) < 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn operations_with_itself(a in rings()) {
+        gp::initialize();
+        prop_assert!(gp::boolean::polygon_boolean(&a, &a, BooleanOp::Xor).is_empty());
+        prop_assert!(gp::boolean::polygon_boolean(&a, &a, BooleanOp::Difference).is_empty());
+        let itself = gp::boolean::polygon_boolean(&a, &[], BooleanOp::Union);
+        prop_assert_eq!(&itself, &gp::boolean::polygon_boolean(&a, &a, BooleanOp::Intersection));
+        prop_assert_eq!(&itself, &gp::boolean::polygon_boolean(&[], &a, BooleanOp::Union));
+    }
+}
+
+#[test]
+fn rings_touching_at_a_vertex() {
+    gp::initialize();
+    // The triangular hole touches the outer boundary at its corner
+    let square = vec![vec![[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]];
+    let triangle = vec![vec![[0.0, 0.0], [2.0, 1.0], [1.0, 2.0]]];
+    let difference = gp::boolean::polygon_boolean(&square, &triangle, BooleanOp::Difference);
+    assert_eq!(1, difference.len());
+    assert!(difference[0].holes().is_empty());
+    let outer = difference[0].approximate().remove(0);
+    assert_eq!(14.5, ring_area(&outer));
+    assert_eq!(2, outer.iter().filter(|&&p| p == [0.0, 0.0]).count());
+
+    // Polygons that only touch at a corner stay separate
+    let corner = vec![vec![[4.0, 4.0], [6.0, 4.0], [6.0, 6.0], [4.0, 6.0]]];
+    let union = gp::boolean::polygon_boolean(&square, &corner, BooleanOp::Union);
+    assert_eq!(2, union.len());
+    assert!(union.iter().all(|polygon| polygon.outer().len() == 4));
+}
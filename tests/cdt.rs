@@ -0,0 +1,241 @@
+//! Tests that constrained Delaunay triangulations are valid and respect their constraints, on inputs with many
+//! degeneracies.
+
+use geogram_predicates as gp;
+use gp::cdt::{Cdt2d, Region};
+use gp::expansion::Expansion;
+use proptest::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+mod common;
+
+fn points() -> impl Strategy<Value = Vec<[f64; 2]>> {
+    prop::collection::vec(prop::array::uniform2(common::coordinate(-4..4, 4.0)), 3..16)
+}
+
+/// Points, and constraints between them as pairs of indices.
+fn constrained() -> impl Strategy<Value = (Vec<[f64; 2]>, Vec<[usize; 2]>)> {
+    points().prop_flat_map(|points| {
+        let n = points.len();
+        (
+            Just(points),
+            prop::collection::vec(prop::array::uniform2(0..n), 0..6),
+        )
+    })
+}
+
+/// Whether `d` is inside of the circle through the counter-clockwise triangle `abc`, without symbolic perturbation.
+fn in_circle(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> i16 {
+    let [ad, bd, cd] = [a, b, c].map(|p| [0, 1].map(|k| Expansion::diff(p[k], d[k])));
+    let lift = |p: &[Expansion; 2]| &(&p[0] * &p[0]) + &(&p[1] * &p[1]);
+    let cross =
+        |p: &[Expansion; 2], q: &[Expansion; 2]| Expansion::det2x2(&p[0], &p[1], &q[0], &q[1]);
+    let det = &(&(&lift(&ad) * &cross(&bd, &cd)) + &(&lift(&bd) * &cross(&cd, &ad)))
+        + &(&lift(&cd) * &cross(&ad, &bd));
+    det.sign()
+}
+
+/// Computes the area of triangles from the vertices of the triangulation.
+fn area(cdt: &Cdt2d, triangles: &[[usize; 3]]) -> f64 {
+    triangles
+        .iter()
+        .map(|t| {
+            let [a, b, c] = t.map(|v| cdt.point(v));
+            ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) / 2.0
+        })
+        .sum()
+}
+
+/// Checks that the triangles are counter-clockwise and meet edge to edge, and that the unconstrained edges are
+/// locally Delaunay.
+fn check_triangulation(cdt: &Cdt2d) -> Result<(), TestCaseError> {
+    let triangles = cdt.triangles();
+    let constraints: HashSet<[usize; 2]> = cdt.constraints().into_iter().collect();
+    let mut opposite: HashMap<[usize; 2], usize> = HashMap::new();
+    for t in &triangles {
+        let [a, b, c] = t.map(|v| cdt.point(v));
+        prop_assert_eq!(1, gp::orient_2d(&a, &b, &c));
+        for k in 0..3 {
+            let edge = [t[k], t[(k + 1) % 3]];
+            prop_assert!(
+                opposite.insert(edge, t[(k + 2) % 3]).is_none(),
+                "{:?}",
+                edge
+            );
+        }
+    }
+    for (&[a, b], &c) in &opposite {
+        if let Some(&d) = opposite.get(&[b, a]) {
+            if !constraints.contains(&[a.min(b), a.max(b)]) {
+                let sign = in_circle(cdt.point(a), cdt.point(b), cdt.point(c), cdt.point(d));
+                prop_assert!(sign <= 0, "{:?} is not locally Delaunay", [a, b]);
+            }
+        }
+    }
+    for [a, b] in constraints {
+        prop_assert!(opposite.contains_key(&[a, b]) || opposite.contains_key(&[b, a]));
+    }
+    Ok(())
+}
+
+/// Tests whether two vertices are connected by constrained edges.
+fn connected(cdt: &Cdt2d, from: usize, to: usize) -> bool {
+    let constraints = cdt.constraints();
+    let mut reached = HashSet::from([from]);
+    let mut stack = vec![from];
+    while let Some(v) = stack.pop() {
+        for &[a, b] in &constraints {
+            let w = if a == v {
+                b
+            } else if b == v {
+                a
+            } else {
+                continue;
+            };
+            if reached.insert(w) {
+                stack.push(w);
+            }
+        }
+    }
+    reached.contains(&to)
+}
+
+proptest! {
+    #[test]
+    fn delaunay_triangulations_are_valid(points in points()) {
+        gp::initialize();
+        let mut cdt = Cdt2d::new();
+        for &p in &points {
+            cdt.insert(p).unwrap();
+        }
+        check_triangulation(&cdt)?;
+        if cdt.triangles().is_empty() {
+            prop_assert!(points.iter().all(|p| gp::orient_2d(&points[0], &points[1], p) == 0));
+        }
+    }
+
+    #[test]
+    fn constraints_are_edges((points, constraints) in constrained()) {
+        gp::initialize();
+        let mut cdt = Cdt2d::new();
+        let vertices: Vec<usize> = points.iter().map(|&p| cdt.insert(p).unwrap()).collect();
+        for &[i, j] in &constraints {
+            cdt.insert_constraint(vertices[i], vertices[j]);
+        }
+        check_triangulation(&cdt)?;
+        if !cdt.triangles().is_empty() {
+            for &[i, j] in &constraints {
+                prop_assert!(i == j || vertices[i] == vertices[j] || connected(&cdt, vertices[i], vertices[j]));
+            }
+        }
+    }
+
+    #[test]
+    fn regions_partition_the_hull((points, constraints) in constrained()) {
+        gp::initialize();
+        let mut cdt = Cdt2d::new();
+        let vertices: Vec<usize> = points.iter().map(|&p| cdt.insert(p).unwrap()).collect();
+        let polygon: Vec<usize> = constraints.iter().map(|&[i, _]| vertices[i]).collect();
+        cdt.insert_boundary(&polygon);
+        check_triangulation(&cdt)?;
+        let [inside, outside] = [Region::Inside, Region::Outside].map(|region| cdt.triangles_in(region));
+        prop_assert_eq!(inside.len() + outside.len(), cdt.triangles().len());
+        let total = area(&cdt, &cdt.triangles());
+        prop_assert!((area(&cdt, &inside) + area(&cdt, &outside) - total).abs() <= 1e-9 * (1.0 + total));
+    }
+}
+
+#[test]
+fn crossing_constraints_are_split() {
+    gp::initialize();
+    let mut cdt = Cdt2d::new();
+    let [a, b, c, d] =
+        [[0.0, 0.0], [4.0, 4.0], [0.0, 4.0], [4.0, 0.0]].map(|p| cdt.insert(p).unwrap());
+    let e = cdt.insert([1.0, 3.0]).unwrap();
+    cdt.insert_constraint(a, b);
+    cdt.insert_constraint(c, d);
+    // Along a piece of a split constraint, and through the center, which is now a vertex
+    cdt.insert_constraint(a, cdt.nb_vertices() - 1);
+    let f = cdt.insert([3.0, 1.0]).unwrap();
+    cdt.insert_constraint(e, f);
+
+    assert_eq!(7, cdt.nb_vertices());
+    assert_eq!([2.0, 2.0], cdt.point(5));
+    assert_eq!(
+        vec![[0, 5], [1, 5], [2, 4], [3, 6], [4, 5], [5, 6]],
+        cdt.constraints()
+    );
+}
+
+#[test]
+fn overlapping_constraints_are_merged() {
+    gp::initialize();
+    let mut cdt = Cdt2d::new();
+    let [a, b, c, d, e] = [[0.0, 0.0], [4.0, 0.0], [2.0, 0.0], [6.0, 0.0], [3.0, 2.0]]
+        .map(|p| cdt.insert(p).unwrap());
+    cdt.insert_constraint(a, b);
+    cdt.insert_constraint(c, d);
+    cdt.insert_constraint(d, a);
+    assert_eq!(vec![[0, 2], [1, 2], [1, 3]], cdt.constraints());
+    assert_eq!(3, cdt.triangles().len());
+    assert!(cdt.triangles().iter().all(|t| t.contains(&e)));
+}
+
+#[test]
+fn collinear_points_are_triangulated_later() {
+    gp::initialize();
+    let mut cdt = Cdt2d::new();
+    let line: Vec<usize> = (0..4)
+        .map(|x| cdt.insert([f64::from(x), 0.0]).unwrap())
+        .collect();
+    assert_eq!(Ok(line[1]), cdt.insert([1.0, 0.0]));
+    cdt.insert_constraint(line[0], line[3]);
+    assert!(cdt.triangles().is_empty());
+
+    // The constraint is inserted once the points span a triangle, and split at the points on it
+    cdt.insert([1.0, 1.0]).unwrap();
+    assert_eq!(3, cdt.triangles().len());
+    assert_eq!(vec![[0, 1], [1, 2], [2, 3]], cdt.constraints());
+    assert_eq!(3, cdt.triangles_in(Region::Outside).len());
+}
+
+#[test]
+fn boundaries_with_holes() {
+    gp::initialize();
+    let mut cdt = Cdt2d::new();
+    let outer = [[0.0, 0.0], [6.0, 0.0], [6.0, 6.0], [0.0, 6.0]].map(|p| cdt.insert(p).unwrap());
+    let hole = [[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]].map(|p| cdt.insert(p).unwrap());
+    let island = [[2.5, 2.5], [3.5, 2.5], [3.0, 3.5]].map(|p| cdt.insert(p).unwrap());
+    cdt.insert_boundary(&outer);
+    cdt.insert_boundary(&hole);
+    cdt.insert_boundary(&island);
+
+    let inside = cdt.triangles_in(Region::Inside);
+    let outside = cdt.triangles_in(Region::Outside);
+    assert_eq!(36.0 - 4.0 + 0.5, area(&cdt, &inside));
+    assert_eq!(4.0 - 0.5, area(&cdt, &outside));
+    assert_eq!(cdt.triangles().len(), inside.len() + outside.len());
+    assert!(outside
+        .iter()
+        .all(|t| t.iter().all(|v| hole.contains(v) || island.contains(v))));
+}
+
+#[test]
+fn non_finite_points_are_rejected() {
+    gp::initialize();
+    let mut cdt = Cdt2d::new();
+    let non_finite = gp::PredicateError::NonFinite { arg_index: 0 };
+    assert_eq!(Err(non_finite), cdt.insert([f64::NAN, 0.0]));
+    for p in [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]] {
+        cdt.insert(p).unwrap();
+    }
+    for p in [
+        [f64::NAN, 0.3],
+        [0.3, f64::INFINITY],
+        [f64::NEG_INFINITY, 0.0],
+    ] {
+        assert_eq!(Err(non_finite), cdt.insert(p));
+    }
+    assert_eq!(3, cdt.nb_vertices());
+    assert_eq!(1, cdt.triangles().len());
+}
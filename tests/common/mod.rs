@@ -0,0 +1,28 @@
+//! Strategies and assertions shared by the property tests.
+
+#![allow(dead_code)]
+
+use proptest::prelude::*;
+use std::ops::Range;
+
+/// Integers, as `f64`.
+pub fn integer(range: Range<i32>) -> impl Strategy<Value = f64> {
+    range.prop_map(f64::from)
+}
+
+/// Mostly small integers, where degenerate configurations are frequent, and otherwise arbitrary values in
+/// `-arbitrary..arbitrary`.
+pub fn coordinate(integers: Range<i32>, arbitrary: f64) -> impl Strategy<Value = f64> {
+    prop_oneof![3 => integer(integers), 1 => -arbitrary..arbitrary]
+}
+
+/// Checks that two values are equal up to rounding errors.
+pub fn assert_close(x: f64, y: f64) -> Result<(), TestCaseError> {
+    prop_assert!(
+        (x - y).abs() <= 1e-9 * (1.0 + x.abs() + y.abs()),
+        "{} != {}",
+        x,
+        y
+    );
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Compares the predicates under the different filter modes, and with their filters alone.
+//!
+//! The filter mode is global, so this file holds a single test, and runs as its own process.
+
+use geogram_predicates as gp;
+use proptest::prelude::*;
+
+mod common;
+
+fn points<const N: usize, const K: usize>() -> impl Strategy<Value = [[f64; N]; K]> {
+    prop::array::uniform(prop::array::uniform(common::coordinate(-4..4, 1e3)))
+}
+
+/// Evaluates a predicate under both modes, and checks that they agree, and that only
+/// [`gp::FilterMode::ExactOnly`] skips the filter.
+fn exact(predicate: impl Fn() -> gp::Sign) -> Result<gp::Sign, TestCaseError> {
+    gp::set_filter_mode(gp::FilterMode::ExactOnly);
+    let skipped = gp::filters_skipped();
+    let exact = predicate();
+    prop_assert!(gp::filters_skipped() > skipped, "the filter was evaluated");
+
+    gp::set_filter_mode(gp::FilterMode::FilterAndExact);
+    let skipped = gp::filters_skipped();
+    prop_assert_eq!(exact, predicate());
+    prop_assert_eq!(skipped, gp::filters_skipped(), "the filter was skipped");
+    Ok(exact)
+}
+
+/// Checks that the filter alone is either uncertain or agrees with the exact result.
+fn agree(filtered: Option<gp::Sign>, exact: gp::Sign) -> Result<(), TestCaseError> {
+    prop_assert!(
+        filtered.is_none() || filtered == Some(exact),
+        "filter {:?} against exact {}",
+        filtered,
+        exact
+    );
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn filter_modes_agree(
+        p2 in points::<2, 4>(),
+        p3 in points::<3, 5>(),
+        p4 in points::<4, 4>(),
+        h in prop::array::uniform5(common::coordinate(-4..4, 1e3)),
+    ) {
+        gp::initialize();
+
+        let [a, b, c, p] = &p2;
+        let orientation = exact(|| gp::orient_2d(a, b, c))?;
+        agree(gp::orient_2d_filtered(a, b, c), orientation)?;
+        if orientation != 0 {
+            let sign = exact(|| gp::in_circle_2d_SOS(a, b, c, p))?;
+            agree(gp::in_circle_2d_filtered(a, b, c, p), sign)?;
+            let sign = exact(|| gp::orient_2dlifted_SOS(a, b, c, p, h[0], h[1], h[2], h[3]))?;
+            agree(gp::orient_2dlifted_filtered(a, b, c, p, h[0], h[1], h[2], h[3]), sign)?;
+        }
+
+        let [a, b, c, d, p] = &p3;
+        let orientation = exact(|| gp::orient_3d(a, b, c, d))?;
+        agree(gp::orient_3d_filtered(a, b, c, d), orientation)?;
+        let sign = exact(|| gp::det_3d(a, b, c))?;
+        agree(gp::det_3d_filtered(a, b, c), sign)?;
+        let sign = exact(|| gp::dot_3d(a, b, c))?;
+        agree(gp::dot_3d_filtered(a, b, c), sign)?;
+        if !gp::points_are_colinear_3d(a, b, c) {
+            let sign = exact(|| gp::in_circle_3d_SOS(a, b, c, p))?;
+            agree(gp::in_circle_3d_filtered(a, b, c, p), sign)?;
+        }
+        let positive = match orientation {
+            -1 => Some([b, a, c, d]),
+            1 => Some([a, b, c, d]),
+            _ => None,
+        };
+        if let Some([a, b, c, d]) = positive {
+            let sign = exact(|| gp::in_sphere_3d_SOS(a, b, c, d, p))?;
+            agree(gp::in_sphere_3d_filtered(a, b, c, d, p), sign)?;
+            let sign = exact(|| gp::orient_3dlifted_SOS(a, b, c, d, p, h[0], h[1], h[2], h[3], h[4]))?;
+            agree(gp::orient_3dlifted_filtered(a, b, c, d, p, h[0], h[1], h[2], h[3], h[4]), sign)?;
+        }
+        if a != b {
+            exact(|| gp::side1_3d_SOS(a, b, c))?;
+            exact(|| gp::side_of_bisector_nd(a, b, c))?;
+            if gp::side2_3d_SOS(a, b, c, d, p).is_some() {
+                exact(|| gp::side2_3d_SOS(a, b, c, d, p).unwrap())?;
+            }
+        }
+
+        let [a, b, c, d] = &p4;
+        let sign = exact(|| gp::det_4d(a, b, c, d))?;
+        agree(gp::det_4d_filtered(a, b, c, d), sign)?;
+        if a != b {
+            exact(|| gp::side_of_bisector_nd(a, b, c))?;
+        }
+    }
+}
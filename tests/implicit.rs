@@ -0,0 +1,90 @@
+//! Tests that the predicates on implicit points agree with the explicit predicates, where the intersection points are
+//! representable.
+
+use geogram_predicates as gp;
+use gp::implicit::{ImplicitPoint2d, ImplicitPoint3d};
+use proptest::prelude::*;
+
+mod common;
+
+fn point<const N: usize>() -> impl Strategy<Value = [f64; N]> {
+    prop::array::uniform(common::coordinate(-8..8, 1e3))
+}
+
+/// Integer points, such that sums and differences of a few of them are exact.
+fn lattice<const N: usize>() -> impl Strategy<Value = [f64; N]> {
+    prop::array::uniform((-8i32..8).prop_map(f64::from))
+}
+
+fn add<const N: usize>(p: [f64; N], s: f64, d: [f64; N]) -> [f64; N] {
+    std::array::from_fn(|k| p[k] + s * d[k])
+}
+
+proptest! {
+    #[test]
+    fn intersection_2d_is_on_both_lines(u in point::<2>(), v in point::<2>(), a in point::<2>(), b in point::<2>()) {
+        gp::initialize();
+        if let Some(x) = ImplicitPoint2d::segment_intersection(&u, &v, &a, &b) {
+            prop_assert_eq!(0, gp::implicit::orient_2d(&u.into(), &v.into(), &x));
+            prop_assert_eq!(0, gp::implicit::orient_2d(&x, &a.into(), &b.into()));
+            prop_assert_eq!(
+                gp::implicit::orient_2d(&u.into(), &a.into(), &x),
+                -gp::implicit::orient_2d(&a.into(), &u.into(), &x)
+            );
+        } else {
+            prop_assert!(u == v || a == b || gp::orient_2d(&u, &v, &add(u, 1.0, [b[0] - a[0], b[1] - a[1]])) == 0);
+        }
+    }
+
+    #[test]
+    fn intersection_2d_agrees_with_explicit_point(
+        x in lattice::<2>(),
+        [d, e] in prop::array::uniform2(lattice::<2>()),
+        [p, q] in prop::array::uniform2(point::<2>()),
+    ) {
+        gp::initialize();
+        // Two lines through `x`, whose intersection is hence exactly `x`.
+        let implicit = ImplicitPoint2d::segment_intersection(&add(x, -1.0, d), &add(x, 2.0, d), &add(x, 3.0, e), &add(x, -2.0, e));
+        prop_assume!(implicit.is_some());
+        let implicit = implicit.unwrap();
+        prop_assert_eq!(x, implicit.approximate());
+        prop_assert_eq!(gp::implicit::orient_2d(&p.into(), &q.into(), &implicit), gp::orient_2d(&p, &q, &x));
+        prop_assert_eq!(gp::implicit::orient_2d(&implicit, &q.into(), &implicit), 0);
+    }
+
+    #[test]
+    fn intersection_3d_is_on_the_line_and_the_plane(
+        [u, v, w] in prop::array::uniform3(point::<3>()),
+        triangle in prop::array::uniform3(point::<3>()),
+    ) {
+        gp::initialize();
+        if let Some(x) = ImplicitPoint3d::segment_triangle_intersection(&u, &v, &triangle) {
+            let [a, b, c] = triangle.map(ImplicitPoint3d::from);
+            prop_assert_eq!(0, gp::implicit::orient_3d(&a, &b, &c, &x));
+            prop_assert_eq!(0, gp::implicit::orient_3d(&u.into(), &v.into(), &w.into(), &x));
+        }
+    }
+
+    #[test]
+    fn intersection_3d_agrees_with_explicit_point(
+        x in lattice::<3>(),
+        [d, e, f] in prop::array::uniform3(lattice::<3>()),
+        [p, q, r] in prop::array::uniform3(point::<3>()),
+    ) {
+        gp::initialize();
+        // A line through `x`, and a triangle whose plane contains `x`.
+        let triangle = [add(x, 1.0, e), add(x, 1.0, f), add(add(x, -1.0, e), -1.0, f)];
+        let implicit = ImplicitPoint3d::segment_triangle_intersection(&add(x, -1.0, d), &add(x, 3.0, d), &triangle);
+        prop_assume!(implicit.is_some());
+        let implicit = implicit.unwrap();
+        prop_assert_eq!(x, implicit.approximate());
+        prop_assert_eq!(
+            gp::implicit::orient_3d(&p.into(), &q.into(), &r.into(), &implicit),
+            gp::orient_3d(&p, &q, &r, &x)
+        );
+        prop_assert_eq!(
+            gp::implicit::orient_3d(&implicit, &p.into(), &q.into(), &r.into()),
+            -gp::orient_3d(&p, &x, &q, &r)
+        );
+    }
+}
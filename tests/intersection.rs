@@ -0,0 +1,237 @@
+//! Tests that the intersection classifications are consistent, on inputs with many degeneracies.
+
+use geogram_predicates as gp;
+use gp::intersection::{FaceIntersection, TriangleIntersection};
+use proptest::prelude::*;
+
+mod common;
+
+fn point() -> impl Strategy<Value = [f64; 3]> {
+    prop::array::uniform3(common::coordinate(-2..3, 2.0))
+}
+
+fn triangle() -> impl Strategy<Value = [[f64; 3]; 3]> {
+    prop::array::uniform3(point())
+}
+
+/// A mesh with a few faces between random vertices, which often share vertices or edges.
+fn mesh() -> impl Strategy<Value = (Vec<[f64; 3]>, Vec<[usize; 3]>)> {
+    prop::collection::vec(point(), 3..10).prop_flat_map(|points| {
+        let n = points.len();
+        (
+            Just(points),
+            prop::collection::vec(prop::array::uniform3(0..n), 0..12),
+        )
+    })
+}
+
+/// A height field over a grid, whose neighboring faces are often coplanar.
+fn terrain() -> impl Strategy<Value = (Vec<[f64; 3]>, Vec<[usize; 3]>)> {
+    prop::collection::vec(-2i32..3, 16).prop_map(|heights| {
+        let points = (0..16)
+            .map(|i| {
+                [
+                    f64::from(i % 4),
+                    f64::from(i / 4),
+                    f64::from(heights[i as usize]),
+                ]
+            })
+            .collect();
+        let mut triangles = Vec::new();
+        for y in 0..3 {
+            for x in 0..3 {
+                let v = 4 * y + x;
+                triangles.extend([[v, v + 1, v + 5], [v, v + 5, v + 4]]);
+            }
+        }
+        (points, triangles)
+    })
+}
+
+/// Finds the self-intersections by testing all pairs of faces.
+fn all_pairs(points: &[[f64; 3]], triangles: &[[usize; 3]]) -> Vec<FaceIntersection> {
+    let mut intersections = Vec::new();
+    for second in 0..triangles.len() {
+        for first in 0..second {
+            let (s, t) = (triangles[first], triangles[second]);
+            let mut shared: Vec<usize> = s.iter().copied().filter(|v| t.contains(v)).collect();
+            shared.sort_unstable();
+            shared.dedup();
+            let expected = match shared.len() {
+                0 => Some(TriangleIntersection::Disjoint),
+                1 => Some(TriangleIntersection::VertexTouch),
+                2 => Some(TriangleIntersection::EdgeTouch),
+                _ => None,
+            };
+            let kind = gp::intersection::triangle_triangle_intersection_3d(
+                &s.map(|v| points[v]),
+                &t.map(|v| points[v]),
+            );
+            if Some(kind) != expected {
+                intersections.push(FaceIntersection {
+                    first,
+                    second,
+                    kind,
+                });
+            }
+        }
+    }
+    intersections.sort_unstable_by_key(|x| (x.first, x.second));
+    intersections
+}
+
+proptest! {
+    #[test]
+    fn self_intersections_are_found_by_the_bvh((points, triangles) in mesh()) {
+        gp::initialize();
+        prop_assert_eq!(
+            all_pairs(&points, &triangles),
+            gp::intersection::mesh_self_intersections(&points, &triangles)
+        );
+    }
+
+    #[test]
+    fn neighbors_are_not_intersections((points, triangles) in terrain()) {
+        gp::initialize();
+        prop_assert_eq!(Vec::<FaceIntersection>::new(), gp::intersection::mesh_self_intersections(&points, &triangles));
+    }
+
+    #[test]
+    fn triangle_intersections_do_not_depend_on_the_order(p in triangle(), q in triangle()) {
+        gp::initialize();
+        let kind = gp::intersection::triangle_triangle_intersection_3d(&p, &q);
+        prop_assert_eq!(kind, gp::intersection::triangle_triangle_intersection_3d(&q, &p));
+        for [i, j, k] in [[0, 1, 2], [1, 2, 0], [2, 0, 1], [1, 0, 2], [0, 2, 1], [2, 1, 0]] {
+            let (p, q) = ([p[i], p[j], p[k]], [q[k], q[i], q[j]]);
+            prop_assert_eq!(kind, gp::intersection::triangle_triangle_intersection_3d(&p, &q));
+        }
+    }
+
+    #[test]
+    fn triangle_intersections_do_not_depend_on_the_scale(p in triangle(), q in triangle()) {
+        gp::initialize();
+        let kind = gp::intersection::triangle_triangle_intersection_3d(&p, &q);
+        let scale = |t: [[f64; 3]; 3]| t.map(|v| v.map(|x| -0.5 * x));
+        prop_assert_eq!(kind, gp::intersection::triangle_triangle_intersection_3d(&scale(p), &scale(q)));
+    }
+
+    #[test]
+    fn segments_through_a_shared_edge_hit_both_triangles(
+        fan in prop::array::uniform4(prop::array::uniform2(-2i32..3)),
+        u in prop::array::uniform3(-4i32..5),
+    ) {
+        gp::initialize();
+        // Two triangles in the plane z = 0 on the edge from `a` to `b`, and a segment through its midpoint, whose
+        // coordinates are exact
+        let [a, b, c, d] = fan.map(|[x, y]| [f64::from(x), f64::from(y), 0.0]);
+        let u = u.map(f64::from);
+        let s = [u, std::array::from_fn(|k| a[k] + b[k] - u[k])];
+        for t in [[a, b, c], [b, a, d]] {
+            prop_assert_ne!(
+                gp::intersection::SegmentTriangleIntersection::Miss,
+                gp::intersection::segment_triangle_intersection_3d(&s, &t)
+            );
+        }
+    }
+
+    #[test]
+    fn triangle_intersections_agree_with_segments(p in triangle(), q in triangle()) {
+        gp::initialize();
+        let kind = gp::intersection::triangle_triangle_intersection_3d(&p, &q);
+        // The triangles meet iff an edge of one meets the other one, or they are coplanar and nested.
+        let edges_meet = (0..3).any(|i| {
+            let hits = |a: &[[f64; 3]; 3], b| {
+                gp::intersection::segment_triangle_intersection_3d(&[a[i], a[(i + 1) % 3]], b)
+                    != gp::intersection::SegmentTriangleIntersection::Miss
+            };
+            hits(&p, &q) || hits(&q, &p)
+        });
+        if edges_meet {
+            prop_assert_ne!(TriangleIntersection::Disjoint, kind);
+        } else {
+            prop_assert!(matches!(kind, TriangleIntersection::Disjoint | TriangleIntersection::CoplanarOverlap));
+        }
+    }
+}
+
+#[test]
+fn degenerate_triangles_are_segments() {
+    gp::initialize();
+    let t = [[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0]];
+    let classify = |s: [[f64; 3]; 3]| gp::intersection::triangle_triangle_intersection_3d(&t, &s);
+
+    // A segment has no interior, so it touches a triangle it passes through, at a vertex or not
+    assert_eq!(
+        TriangleIntersection::EdgeTouch,
+        classify([[1.0, 1.0, -1.0], [1.0, 1.0, 0.5], [1.0, 1.0, 1.0]])
+    );
+    assert_eq!(
+        TriangleIntersection::VertexTouch,
+        classify([[1.0, 1.0, -1.0], [1.0, 1.0, 0.0], [1.0, 1.0, 1.0]])
+    );
+    // Along an edge, from a vertex and parallel above
+    assert_eq!(
+        TriangleIntersection::EdgeTouch,
+        classify([[1.0, 0.0, 0.0], [3.0, 0.0, 0.0], [2.0, 0.0, 0.0]])
+    );
+    assert_eq!(
+        TriangleIntersection::VertexTouch,
+        classify([[4.0, 0.0, 0.0], [5.0, 1.0, 0.0], [6.0, 2.0, 0.0]])
+    );
+    assert_eq!(
+        TriangleIntersection::Disjoint,
+        classify([[1.0, 1.0, 1.0], [2.0, 1.0, 1.0], [3.0, 1.0, 1.0]])
+    );
+    // A point in the interior, and two identical points
+    assert_eq!(
+        TriangleIntersection::VertexTouch,
+        classify([[1.0, 1.0, 0.0]; 3])
+    );
+    assert_eq!(
+        TriangleIntersection::VertexTouch,
+        gp::intersection::triangle_triangle_intersection_3d(
+            &[[1.0, 1.0, 1.0]; 3],
+            &[[1.0, 1.0, 1.0]; 3]
+        )
+    );
+}
+
+#[test]
+fn closed_meshes_have_no_self_intersections() {
+    gp::initialize();
+    // An octahedron, whose faces share a vertex or an edge, or are parallel
+    let points = [
+        [1.0, 0.0, 0.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, -1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 0.0, -1.0],
+    ];
+    let mut triangles = vec![
+        [0, 2, 4],
+        [2, 1, 4],
+        [1, 3, 4],
+        [3, 0, 4],
+        [2, 0, 5],
+        [1, 2, 5],
+        [3, 1, 5],
+        [0, 3, 5],
+    ];
+    assert!(gp::intersection::mesh_self_intersections(&points, &triangles).is_empty());
+
+    // A face folded onto its neighbor
+    let mut points = points.to_vec();
+    points.push([0.25, 0.25, 0.5]);
+    triangles.push([0, 2, 6]);
+    let intersections = gp::intersection::mesh_self_intersections(&points, &triangles);
+    assert_eq!(
+        vec![FaceIntersection {
+            first: 0,
+            second: 8,
+            kind: TriangleIntersection::CoplanarOverlap
+        }],
+        intersections
+    );
+    assert_eq!(intersections, all_pairs(&points, &triangles));
+}
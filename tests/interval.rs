@@ -0,0 +1,67 @@
+//! Tests that interval arithmetic encloses the exact results, and that filtered evaluation agrees with the bridged
+//! predicates.
+
+use geogram_predicates as gp;
+use gp::expansion::Expansion;
+use gp::interval::{Interval, Ring};
+use proptest::prelude::*;
+
+/// Values of many magnitudes, whose products stay in the range of exact arithmetic.
+fn value() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        (-8i32..8).prop_map(f64::from),
+        -1e3..1e3f64,
+        (-1.0..1.0f64, -90i32..90).prop_map(|(m, e)| m * 10f64.powi(e)),
+    ]
+}
+
+/// Tests whether `x` lies in `interval`, with exact comparisons.
+fn encloses(interval: Interval, x: &Expansion) -> bool {
+    let above = x - &Expansion::new(interval.lo());
+    let below = &Expansion::new(interval.hi()) - x;
+    above.sign() >= 0 && below.sign() >= 0
+}
+
+fn orient<T: Ring>(x: &[T]) -> T {
+    let [ax, ay, bx, by, cx, cy] = [0, 1, 2, 3, 4, 5].map(|i| x[i].clone());
+    (bx - ax.clone()) * (cy - ay.clone()) - (by - ay) * (cx - ax)
+}
+
+proptest! {
+    #[test]
+    fn operations_enclose_exact_results(a in value(), b in value(), c in value()) {
+        let [ia, ib, ic] = [a, b, c].map(Interval::from);
+        let [ea, eb, ec] = [a, b, c].map(Expansion::from);
+        prop_assert!(encloses(ia + ib, &(&ea + &eb)));
+        prop_assert!(encloses(ia - ib, &(&ea - &eb)));
+        prop_assert!(encloses(ia * ib, &(&ea * &eb)));
+        prop_assert!(encloses(-ia, &-&ea));
+        prop_assert!(encloses((ia + ib) * ic - ia, &(&(&(&ea + &eb) * &ec) - &ea)));
+    }
+
+    #[test]
+    fn intervals_never_give_a_wrong_sign(a in value(), b in value(), c in value()) {
+        let exact = (&(&Expansion::from(a) * &Expansion::from(b)) - &Expansion::from(c)).sign();
+        if let Some(sign) = (Interval::from(a) * Interval::from(b) - Interval::from(c)).sign() {
+            prop_assert_eq!(sign, exact);
+        }
+    }
+
+    #[test]
+    fn sign_of_agrees_with_orient_2d(
+        a in prop::array::uniform2(-8i32..8),
+        b in prop::array::uniform2(-8i32..8),
+        t in -2.0..2.0f64,
+        u in -2.0..2.0f64,
+    ) {
+        gp::initialize();
+        let [a, b] = [a, b].map(|p| p.map(f64::from));
+        // Points near the line through a and b.
+        let c = [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1]) + u * f64::EPSILON];
+        let x = [a[0], a[1], b[0], b[1], c[0], c[1]];
+        prop_assert_eq!(
+            gp::interval::sign_of(&x, orient::<Interval>, orient::<Expansion>),
+            gp::orient_2d(&a, &b, &c)
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Tests that boolean operations on triangle meshes are consistent with each other, on solids whose faces often touch
+//! or overlap.
+
+use geogram_predicates as gp;
+use gp::boolean::{BooleanOp, TriangleMesh};
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+mod common;
+
+use common::assert_close;
+
+/// An axis aligned box from its lowest corner and its size, whose faces are split into two triangles.
+fn box_mesh(lo: [f64; 3], size: [f64; 3]) -> TriangleMesh {
+    let points = (0..8)
+        .map(|i| [0, 1, 2].map(|k| lo[k] + f64::from(i >> k & 1) * size[k]))
+        .collect();
+    let triangles = vec![
+        [0, 2, 1],
+        [1, 2, 3],
+        [4, 5, 6],
+        [5, 7, 6],
+        [0, 1, 4],
+        [1, 5, 4],
+        [2, 6, 3],
+        [3, 6, 7],
+        [0, 4, 2],
+        [2, 4, 6],
+        [1, 3, 5],
+        [3, 7, 5],
+    ];
+    TriangleMesh { points, triangles }
+}
+
+/// An axis aligned box with integer corners, as its lowest and its highest corner.
+fn corners() -> impl Strategy<Value = [[f64; 3]; 2]> {
+    prop::array::uniform3((common::integer(-3..3), 1i32..4)).prop_map(|extent| {
+        let lo = extent.map(|(x, _)| x);
+        [lo, [0, 1, 2].map(|k| lo[k] + f64::from(extent[k].1))]
+    })
+}
+
+fn cuboid() -> impl Strategy<Value = TriangleMesh> {
+    corners().prop_map(|[lo, hi]| box_mesh(lo, [0, 1, 2].map(|k| hi[k] - lo[k])))
+}
+
+/// A tetrahedron, oriented outwards.
+fn tetrahedron() -> impl Strategy<Value = TriangleMesh> {
+    prop::array::uniform4(prop::array::uniform3(common::integer(-3..3)))
+        .prop_filter("flat tetrahedron", |[a, b, c, d]| {
+            gp::orient_3d(a, b, c, d) != 0
+        })
+        .prop_map(|[a, b, c, d]| {
+            let (b, c) = if gp::orient_3d(&a, &b, &c, &d) > 0 {
+                (b, c)
+            } else {
+                (c, b)
+            };
+            TriangleMesh {
+                points: vec![a, b, c, d],
+                triangles: vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]],
+            }
+        })
+}
+
+fn solid() -> impl Strategy<Value = TriangleMesh> {
+    prop_oneof![cuboid(), tetrahedron()]
+}
+
+/// Computes the volume of a closed mesh from its rounded vertices.
+fn volume(mesh: &TriangleMesh) -> f64 {
+    mesh.triangles
+        .iter()
+        .map(|t| {
+            let [p, q, r] = t.map(|v| mesh.points[v]);
+            (p[0] * (q[1] * r[2] - q[2] * r[1]) - p[1] * (q[0] * r[2] - q[2] * r[0])
+                + p[2] * (q[0] * r[1] - q[1] * r[0]))
+                / 6.0
+        })
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn volumes_satisfy_inclusion_exclusion(a in solid(), b in solid()) {
+        gp::initialize();
+        let [union, intersection, difference, xor] =
+            [BooleanOp::Union, BooleanOp::Intersection, BooleanOp::Difference, BooleanOp::Xor]
+                .map(|op| volume(&gp::boolean::mesh_boolean(&a, &b, op).approximate()));
+        assert_close(union + intersection, volume(&a) + volume(&b))?;
+        assert_close(xor, union - intersection)?;
+        assert_close(difference, volume(&a) - intersection)?;
+    }
+
+    #[test]
+    fn box_intersections_have_the_exact_volume(a in corners(), b in corners()) {
+        gp::initialize();
+        // The intersection of two boxes is a box, or empty.
+        let size = |[lo, hi]: [[f64; 3]; 2]| [0, 1, 2].map(|k| hi[k] - lo[k]);
+        let overlap = [0, 1, 2].map(|k| (a[1][k].min(b[1][k]) - a[0][k].max(b[0][k])).max(0.0));
+        let [volume_a, volume_b, intersection] = [size(a), size(b), overlap].map(|s| s[0] * s[1] * s[2]);
+        let [a, b] = [a, b].map(|[lo, hi]| box_mesh(lo, size([lo, hi])));
+        let result = |op| volume(&gp::boolean::mesh_boolean(&a, &b, op).approximate());
+        assert_close(result(BooleanOp::Intersection), intersection)?;
+        assert_close(result(BooleanOp::Union), volume_a + volume_b - intersection)?;
+        assert_close(result(BooleanOp::Difference), volume_a - intersection)?;
+        assert_close(result(BooleanOp::Xor), volume_a + volume_b - 2.0 * intersection)?;
+    }
+
+    #[test]
+    fn results_are_watertight(a in solid(), b in solid()) {
+        gp::initialize();
+        for op in [BooleanOp::Union, BooleanOp::Intersection, BooleanOp::Difference, BooleanOp::Xor] {
+            let result = gp::boolean::mesh_boolean(&a, &b, op);
+            let mut edges: HashMap<[usize; 2], i32> = HashMap::new();
+            for t in result.triangles() {
+                prop_assert!(t[0] != t[1] && t[1] != t[2] && t[2] != t[0]);
+                for k in 0..3 {
+                    let [u, v] = [t[k], t[(k + 1) % 3]];
+                    *edges.entry([u.min(v), u.max(v)]).or_default() += if u < v { 1 } else { -1 };
+                }
+            }
+            prop_assert!(edges.values().all(|&count| count == 0), "{:?}", op);
+        }
+    }
+
+    #[test]
+    fn operations_with_itself(a in solid()) {
+        gp::initialize();
+        prop_assert!(gp::boolean::mesh_boolean(&a, &a, BooleanOp::Xor).triangles().is_empty());
+        prop_assert!(gp::boolean::mesh_boolean(&a, &a, BooleanOp::Difference).triangles().is_empty());
+        let union = gp::boolean::mesh_boolean(&a, &a, BooleanOp::Union).approximate();
+        assert_close(volume(&union), volume(&a))?;
+    }
+}
+
+#[test]
+fn inscribed_tetrahedron() {
+    gp::initialize();
+    // The vertices of the tetrahedron are on the faces of the cube, and it touches the cube nowhere else, so its
+    // faces can only be classified from their interior.
+    let cube = box_mesh([0.0; 3], [4.0; 3]);
+    let tetrahedron = TriangleMesh {
+        points: vec![
+            [2.0, 2.0, 0.0],
+            [4.0, 2.0, 2.0],
+            [2.0, 4.0, 2.0],
+            [0.0, 1.0, 1.0],
+        ],
+        triangles: vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]],
+    };
+    assert_close(volume(&tetrahedron), 8.0 / 3.0).unwrap();
+
+    let result = |a, b, op| gp::boolean::mesh_boolean(a, b, op).approximate();
+    for (a, b) in [(&tetrahedron, &cube), (&cube, &tetrahedron)] {
+        let mut intersection = result(a, b, BooleanOp::Intersection);
+        intersection.points.sort_by(gp::lexico_cmp_3d);
+        let mut points = tetrahedron.points.clone();
+        points.sort_by(gp::lexico_cmp_3d);
+        assert_eq!(points, intersection.points);
+        assert_eq!(4, intersection.triangles.len());
+        assert_eq!(64.0, volume(&result(a, b, BooleanOp::Union)));
+        assert_close(volume(&result(a, b, BooleanOp::Xor)), 64.0 - 8.0 / 3.0).unwrap();
+    }
+    assert!(result(&tetrahedron, &cube, BooleanOp::Difference)
+        .triangles
+        .is_empty());
+    assert_close(
+        volume(&result(&cube, &tetrahedron, BooleanOp::Difference)),
+        64.0 - 8.0 / 3.0,
+    )
+    .unwrap();
+}
+
+#[test]
+fn inscribed_needle() {
+    gp::initialize();
+    // As above, in a box that is only four ulps wide, where points in the faces of the tetrahedron cannot be rounded
+    // without moving them off the faces.
+    let u = f64::EPSILON;
+    let thin = box_mesh([0.0, 1.0, 1.0], [4.0, 4.0 * u, 4.0 * u]);
+    let needle = TriangleMesh {
+        points: vec![
+            [0.0, 1.0 + u, 1.0 + 3.0 * u],
+            [2.0, 1.0, 1.0 + 2.0 * u],
+            [2.5, 1.0 + u, 1.0 + 4.0 * u],
+            [4.0, 1.0 + 3.0 * u, 1.0 + 2.0 * u],
+        ],
+        triangles: vec![[0, 1, 2], [0, 3, 1], [1, 3, 2], [0, 2, 3]],
+    };
+    let [a, b, c, d] = [0, 2, 1, 3].map(|v| needle.points[v]);
+    assert_eq!(1, gp::orient_3d(&a, &b, &c, &d));
+
+    let result = |a, b, op| gp::boolean::mesh_boolean(a, b, op).approximate();
+    for (a, b) in [(&needle, &thin), (&thin, &needle)] {
+        let mut intersection = result(a, b, BooleanOp::Intersection);
+        intersection.points.sort_by(gp::lexico_cmp_3d);
+        let mut points = needle.points.clone();
+        points.sort_by(gp::lexico_cmp_3d);
+        assert_eq!(points, intersection.points);
+        assert_eq!(4, intersection.triangles.len());
+    }
+    assert!(result(&needle, &thin, BooleanOp::Difference)
+        .triangles
+        .is_empty());
+}
@@ -0,0 +1,75 @@
+//! Compares the batch predicates on a thread pool with a serial loop over the same points.
+//!
+//! The queries are near-degenerate and evaluated without the filters, so that each of them takes the exact path, and
+//! the symbolic perturbation where they are degenerate. The filter mode is global, so this file holds a single test.
+
+use float_extras::f64::nextafter;
+use geogram_predicates as gp;
+use proptest::prelude::*;
+
+/// Some lattice points on the sphere of radius 3, with many coplanar and all cospherical.
+const SPHERE: [[f64; 3]; 12] = [
+    [3.0, 0.0, 0.0],
+    [-3.0, 0.0, 0.0],
+    [0.0, 3.0, 0.0],
+    [0.0, -3.0, 0.0],
+    [0.0, 0.0, 3.0],
+    [0.0, 0.0, -3.0],
+    [2.0, 2.0, 1.0],
+    [-2.0, 2.0, -1.0],
+    [2.0, -1.0, 2.0],
+    [-1.0, -2.0, 2.0],
+    [1.0, 2.0, -2.0],
+    [-2.0, -1.0, -2.0],
+];
+
+/// Points of the sphere, mostly exact and otherwise moved by one ulp along one axis.
+fn points() -> impl Strategy<Value = Vec<[f64; 3]>> {
+    let nudge = prop_oneof![3 => Just(None), 1 => (0..3usize, any::<bool>()).prop_map(Some)];
+    prop::collection::vec((0..SPHERE.len(), nudge), 24).prop_map(|picks| {
+        picks
+            .into_iter()
+            .map(|(i, nudge)| {
+                let mut p = SPHERE[i];
+                if let Some((k, up)) = nudge {
+                    p[k] = nextafter(p[k], if up { f64::INFINITY } else { f64::NEG_INFINITY });
+                }
+                p
+            })
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn parallel_matches_serial(
+        points in points(),
+        picks in prop::collection::vec(prop::array::uniform5(0..24usize), 1000),
+    ) {
+        gp::initialize();
+        gp::set_filter_mode(gp::FilterMode::ExactOnly);
+
+        let tetrahedra: Vec<[usize; 4]> = picks.iter().map(|&[a, b, c, d, _]| [a, b, c, d]).collect();
+        let serial: Vec<i16> = tetrahedra
+            .iter()
+            .map(|&[a, b, c, d]| gp::orient_3d(&points[a], &points[b], &points[c], &points[d]))
+            .collect();
+        prop_assert_eq!(&serial, &gp::parallel::orient_3d(&points, &tetrahedra));
+
+        // The tetrahedra of the in-sphere queries need to be positively oriented.
+        let queries: Vec<[usize; 5]> = picks
+            .iter()
+            .zip(&serial)
+            .filter_map(|(&[a, b, c, d, p], &orientation)| match orientation {
+                -1 => Some([b, a, c, d, p]),
+                1 => Some([a, b, c, d, p]),
+                _ => None,
+            })
+            .collect();
+        let serial: Vec<i16> = queries
+            .iter()
+            .map(|&[a, b, c, d, p]| gp::in_sphere_3d_SOS(&points[a], &points[b], &points[c], &points[d], &points[p]))
+            .collect();
+        prop_assert_eq!(serial, gp::parallel::in_sphere_3d_SOS(&points, &queries));
+    }
+}
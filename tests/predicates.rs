@@ -0,0 +1,72 @@
+//! Regression tests for fixes in the vendored geogram predicates.
+
+use geogram_predicates as gp;
+
+/// `dot_3d` used the filter of `det_3d`, which is conclusive on most input, and hence returned the sign of the
+/// determinant of the points instead of the sign of the dot product.
+#[test]
+fn dot_3d_uses_its_own_filter() {
+    gp::initialize();
+    let a = [1.0, 0.0, 0.0];
+    let b = [0.0, 1.0, 0.0];
+    let c = [0.0, 0.0, -1.0];
+
+    // The determinant is negative, while `ab = [-1, 1, 0]` and `ac = [-1, 0, -1]` have a positive dot product
+    assert_eq!(-1, gp::det_3d(&a, &b, &c));
+    assert_eq!(1, gp::dot_3d(&a, &b, &c));
+}
+
+/// The symbolic perturbation in `orient_2dlifted_SOS` had the wrong sign for some of the points, so the result on
+/// degenerate input depended on the order of the vertices of the triangle.
+#[test]
+fn orient_2dlifted_does_not_depend_on_the_order_of_the_triangle() {
+    gp::initialize();
+    // The lifted points are all on the plane `h = x + y`, and the perturbation depends on their addresses.
+    let points = [[0.0, 0.0], [2.0, 0.0], [0.0, 2.0], [0.5, 0.5], [2.0, 2.0]];
+    let height = |p: &[f64; 2]| p[0] + p[1];
+    for p in &points {
+        for triangle in [[0, 1, 2], [1, 2, 3], [0, 2, 4], [1, 3, 4]] {
+            if triangle.iter().any(|&i| std::ptr::eq(&points[i], p)) {
+                continue;
+            }
+            let [a, b, c] = triangle.map(|i| &points[i]);
+            let side = |[a, b, c]: [&[f64; 2]; 3]| {
+                gp::orient_2dlifted_SOS(a, b, c, p, height(a), height(b), height(c), height(p))
+            };
+            let sides = [
+                [a, b, c],
+                [b, c, a],
+                [c, a, b],
+                [b, a, c],
+                [a, c, b],
+                [c, b, a],
+            ]
+            .map(side);
+            assert!(
+                sides.iter().all(|&s| s == sides[0]),
+                "{:?} {:?}: {:?}",
+                triangle,
+                p,
+                sides
+            );
+        }
+    }
+}
+
+/// `side2_3d_SOS` forwarded lines parallel to the bisector to geogram, whose exact evaluation asserts that the line
+/// crosses the bisector and aborted the process.
+#[test]
+fn side2_3d_does_not_abort_on_parallel_lines() {
+    gp::initialize();
+    let p0 = [0.0, 0.0, 0.0];
+    let p1 = [2.0, 0.0, 0.0];
+    let p2 = [0.0, 2.0, 0.0];
+
+    // The y axis lies in the bisector of p0 and p1, and p1 coincides with p0 in the second case
+    assert_eq!(None, gp::side2_3d_SOS(&p0, &p1, &p2, &p0, &[0.0, 1.0, 0.0]));
+    assert_eq!(None, gp::side2_3d_SOS(&p0, &p0, &p2, &p0, &p1));
+
+    // Slightly tilted, the line crosses the bisector far away, on the side of p2
+    let q1 = [f64::EPSILON, 1.0, 0.0];
+    assert_eq!(Some(-1), gp::side2_3d_SOS(&p0, &p1, &p2, &p0, &q1));
+}
@@ -0,0 +1,498 @@
+//! Differential tests of the bridged predicates against the exact rational reference.
+
+use float_extras::f64::nextafter;
+use geogram_predicates as gp;
+use geogram_predicates::reference as exact;
+use proptest::prelude::*;
+
+mod common;
+
+/// Moves `x` by `ulps` representable numbers. Zero is left alone: its neighbours are subnormal, where expansion
+/// arithmetic underflows and geogram makes no guarantees.
+fn nudge(x: f64, ulps: i32) -> f64 {
+    if x == 0.0 {
+        return x;
+    }
+    let target = if ulps < 0 {
+        f64::NEG_INFINITY
+    } else {
+        f64::INFINITY
+    };
+    (0..ulps.abs()).fold(x, |x, _| nextafter(x, target))
+}
+
+fn point<const N: usize>() -> impl Strategy<Value = [f64; N]> {
+    prop::array::uniform(common::coordinate(-8..8, 1e3))
+}
+
+/// Offsets of a few ulps per coordinate, mostly zero.
+fn ulps<const N: usize>() -> impl Strategy<Value = [i32; N]> {
+    prop::array::uniform(prop_oneof![3 => Just(0), 1 => -3i32..=3])
+}
+
+fn nudged<const N: usize>(p: [f64; N], ulps: [i32; N]) -> [f64; N] {
+    std::array::from_fn(|i| nudge(p[i], ulps[i]))
+}
+
+/// Lattice points on the circle of radius 5.
+const CIRCLE: [[f64; 2]; 12] = [
+    [5.0, 0.0],
+    [4.0, 3.0],
+    [3.0, 4.0],
+    [0.0, 5.0],
+    [-3.0, 4.0],
+    [-4.0, 3.0],
+    [-5.0, 0.0],
+    [-4.0, -3.0],
+    [-3.0, -4.0],
+    [0.0, -5.0],
+    [3.0, -4.0],
+    [4.0, -3.0],
+];
+
+/// Some lattice points on the sphere of radius 3.
+const SPHERE: [[f64; 3]; 12] = [
+    [3.0, 0.0, 0.0],
+    [-3.0, 0.0, 0.0],
+    [0.0, 3.0, 0.0],
+    [0.0, -3.0, 0.0],
+    [0.0, 0.0, 3.0],
+    [0.0, 0.0, -3.0],
+    [2.0, 2.0, 1.0],
+    [-2.0, 2.0, -1.0],
+    [2.0, -1.0, 2.0],
+    [-1.0, -2.0, 2.0],
+    [1.0, 2.0, -2.0],
+    [-2.0, -1.0, -2.0],
+];
+
+/// Points on a circle, translated and scaled by powers of two so that they stay exactly cocircular, then nudged.
+fn cocircular() -> impl Strategy<Value = Vec<[f64; 2]>> {
+    (
+        prop::collection::vec((0..CIRCLE.len(), ulps::<2>()), 4),
+        point::<2>().prop_map(|p| p.map(f64::trunc)),
+        -4i32..4,
+    )
+        .prop_map(|(picks, center, scale)| {
+            let s = 2f64.powi(scale);
+            picks
+                .into_iter()
+                .map(|(i, u)| nudged([0, 1].map(|k| center[k] + s * CIRCLE[i][k]), u))
+                .collect()
+        })
+}
+
+/// Points on a sphere, translated and scaled by powers of two so that they stay exactly cospherical, then nudged.
+fn cospherical() -> impl Strategy<Value = Vec<[f64; 3]>> {
+    (
+        prop::collection::vec((0..SPHERE.len(), ulps::<3>()), 5),
+        point::<3>().prop_map(|p| p.map(f64::trunc)),
+        -4i32..4,
+    )
+        .prop_map(|(picks, center, scale)| {
+            let s = 2f64.powi(scale);
+            picks
+                .into_iter()
+                .map(|(i, u)| nudged([0, 1, 2].map(|k| center[k] + s * SPHERE[i][k]), u))
+                .collect()
+        })
+}
+
+/// The lattice points on the 4d sphere of radius 3.
+fn sphere_4d() -> Vec<[f64; 4]> {
+    let range = || -3i32..=3;
+    let mut points = Vec::new();
+    for x in range() {
+        for y in range() {
+            for z in range() {
+                for w in range() {
+                    if x * x + y * y + z * z + w * w == 9 {
+                        points.push([x, y, z, w].map(f64::from));
+                    }
+                }
+            }
+        }
+    }
+    points
+}
+
+/// Points on a 4d sphere, translated and scaled by powers of two so that they stay exactly cospherical, then nudged.
+fn cospherical_4d() -> impl Strategy<Value = Vec<[f64; 4]>> {
+    let sphere = sphere_4d();
+    (
+        prop::collection::vec((0..sphere.len(), ulps::<4>()), 6),
+        point::<4>().prop_map(|p| p.map(f64::trunc)),
+        -4i32..4,
+    )
+        .prop_map(move |(picks, center, scale)| {
+            let s = 2f64.powi(scale);
+            picks
+                .into_iter()
+                .map(|(i, u)| nudged([0, 1, 2, 3].map(|k| center[k] + s * sphere[i][k]), u))
+                .collect()
+        })
+}
+
+/// A center and two points on a sphere around it, scaled and translated like [`cospherical`], then nudged.
+fn equidistant() -> impl Strategy<Value = [[f64; 3]; 3]> {
+    (
+        prop::array::uniform2((0..SPHERE.len(), ulps::<3>())),
+        point::<3>().prop_map(|p| p.map(f64::trunc)),
+        ulps::<3>(),
+        -4i32..4,
+    )
+        .prop_map(|(picks, center, u, scale)| {
+            let s = 2f64.powi(scale);
+            let [q, r] =
+                picks.map(|(i, u)| nudged([0, 1, 2].map(|k| center[k] + s * SPHERE[i][k]), u));
+            [nudged(center, u), q, r]
+        })
+}
+
+/// Four distinct indices out of five for a tetrahedron, and any index for the query point.
+fn tetrahedron_picks() -> impl Strategy<Value = ([usize; 4], usize)> {
+    (Just(vec![0, 1, 2, 3, 4]).prop_shuffle(), 0..5usize)
+        .prop_map(|(shuffled, p)| ([0, 1, 2, 3].map(|i| shuffled[i]), p))
+}
+
+/// Returns the vertices in positive orientation, if they span a tetrahedron.
+fn positive(t: [&[f64; 3]; 4]) -> Option<[&[f64; 3]; 4]> {
+    match exact::orient_3d(t[0], t[1], t[2], t[3]) {
+        1 => Some(t),
+        -1 => Some([t[1], t[0], t[2], t[3]]),
+        _ => None,
+    }
+}
+
+proptest! {
+    #[test]
+    fn orient_2d_random(a in point::<2>(), b in point::<2>(), c in point::<2>()) {
+        gp::initialize();
+        prop_assert_eq!(gp::orient_2d(&a, &b, &c), exact::orient_2d(&a, &b, &c));
+    }
+
+    #[test]
+    fn orient_2d_near_collinear(a in point::<2>(), b in point::<2>(), t in -2.0..2.0f64, u in ulps::<2>()) {
+        gp::initialize();
+        let c = nudged([0, 1].map(|k| a[k] + t * (b[k] - a[k])), u);
+        prop_assert_eq!(gp::orient_2d(&a, &b, &c), exact::orient_2d(&a, &b, &c));
+    }
+
+    #[test]
+    fn orient_3d_random(a in point::<3>(), b in point::<3>(), c in point::<3>(), d in point::<3>()) {
+        gp::initialize();
+        prop_assert_eq!(gp::orient_3d(&a, &b, &c, &d), exact::orient_3d(&a, &b, &c, &d));
+    }
+
+    #[test]
+    fn orient_3d_near_coplanar(
+        a in point::<3>(),
+        b in point::<3>(),
+        c in point::<3>(),
+        s in -2.0..2.0f64,
+        t in -2.0..2.0f64,
+        u in ulps::<3>(),
+    ) {
+        gp::initialize();
+        let d = nudged([0, 1, 2].map(|k| a[k] + s * (b[k] - a[k]) + t * (c[k] - a[k])), u);
+        prop_assert_eq!(gp::orient_3d(&a, &b, &c, &d), exact::orient_3d(&a, &b, &c, &d));
+    }
+
+    #[test]
+    fn orient_3d_filtered_near_coplanar(
+        a in point::<3>(),
+        b in point::<3>(),
+        c in point::<3>(),
+        s in -2.0..2.0f64,
+        t in -2.0..2.0f64,
+        u in ulps::<3>(),
+    ) {
+        gp::initialize();
+        let d = nudged([0, 1, 2].map(|k| a[k] + s * (b[k] - a[k]) + t * (c[k] - a[k])), u);
+        if let Some(sign) = gp::orient_3d_filtered(&a, &b, &c, &d) {
+            prop_assert_eq!(sign, exact::orient_3d(&a, &b, &c, &d));
+        }
+    }
+
+    #[test]
+    fn det_3d_random(a in point::<3>(), b in point::<3>(), c in point::<3>(), s in -2.0..2.0f64, u in ulps::<3>()) {
+        gp::initialize();
+        prop_assert_eq!(gp::det_3d(&a, &b, &c), exact::det_3d(&a, &b, &c));
+        // A third row close to a multiple of the first one.
+        let c = nudged(a.map(|x| s * x), u);
+        prop_assert_eq!(gp::det_3d(&a, &b, &c), exact::det_3d(&a, &b, &c));
+    }
+
+    #[test]
+    fn det_4d_random(
+        a in point::<4>(),
+        b in point::<4>(),
+        c in point::<4>(),
+        d in point::<4>(),
+        s in -2.0..2.0f64,
+        u in ulps::<4>(),
+    ) {
+        gp::initialize();
+        prop_assert_eq!(gp::det_4d(&a, &b, &c, &d), exact::det_4d(&a, &b, &c, &d));
+        let d = nudged([0, 1, 2, 3].map(|k| a[k] + s * b[k]), u);
+        prop_assert_eq!(gp::det_4d(&a, &b, &c, &d), exact::det_4d(&a, &b, &c, &d));
+    }
+
+    #[test]
+    fn det_sign_near_singular(
+        rows in prop::collection::vec(point::<6>(), 6),
+        s in prop::collection::vec(-4i32..=4, 5),
+        u in ulps::<6>(),
+    ) {
+        gp::initialize();
+        let mut m: [[f64; 6]; 6] = std::array::from_fn(|i| rows[i]);
+        prop_assert_eq!(gp::det_sign(&m), exact::det_sign(&m));
+        // A last row close to a combination of the other ones.
+        m[5] = nudged(std::array::from_fn(|k| (0..5).map(|i| f64::from(s[i]) * m[i][k]).sum()), u);
+        prop_assert_eq!(gp::det_sign(&m), exact::det_sign(&m));
+        // The specialized 4x4 kernel, with a last row close to the difference of the first two.
+        let mut m4: [[f64; 4]; 4] = std::array::from_fn(|i| std::array::from_fn(|k| m[i][k]));
+        m4[3] = nudged(std::array::from_fn(|k| m4[0][k] - m4[1][k]), std::array::from_fn(|k| u[k]));
+        prop_assert_eq!(gp::det_sign(&m4), exact::det_sign(&m4));
+    }
+
+    #[test]
+    fn collinear_near_collinear(a in point::<3>(), b in point::<3>(), t in -2.0..2.0f64, u in ulps::<3>()) {
+        gp::initialize();
+        let c = nudged([0, 1, 2].map(|k| a[k] + t * (b[k] - a[k])), u);
+        prop_assert_eq!(gp::collinear_nd(&a, &b, &c), exact::points_are_colinear_3d(&a, &b, &c));
+        let [a, b, c] = [a, b, c].map(|p| [p[0], p[1]]);
+        prop_assert_eq!(gp::collinear_2d(&a, &b, &c), exact::orient_2d(&a, &b, &c) == 0);
+    }
+
+    #[test]
+    fn coplanar_3d_near_coplanar(
+        a in point::<3>(),
+        b in point::<3>(),
+        c in point::<3>(),
+        s in -2.0..2.0f64,
+        t in -2.0..2.0f64,
+        u in ulps::<3>(),
+    ) {
+        gp::initialize();
+        let d = nudged([0, 1, 2].map(|k| a[k] + s * (b[k] - a[k]) + t * (c[k] - a[k])), u);
+        prop_assert_eq!(gp::coplanar_3d(&a, &b, &c, &d), exact::orient_3d(&a, &b, &c, &d) == 0);
+    }
+
+    #[test]
+    fn affine_rank_of_flat_sets(
+        basis in prop::collection::vec(point::<5>(), 4),
+        rank in 0..4usize,
+        weights in prop::collection::vec(prop::collection::vec(-4i32..=4, 3), 6),
+        u in ulps::<5>(),
+    ) {
+        // Combinations of the first `rank + 1` points with small integer weights, which are exact on small integers.
+        let mut points: Vec<[f64; 5]> = weights
+            .iter()
+            .map(|w| std::array::from_fn(|k| {
+                basis[0][k] + (1..=rank).map(|i| f64::from(w[i - 1]) * (basis[i][k] - basis[0][k])).sum::<f64>()
+            }))
+            .collect();
+        prop_assert_eq!(gp::affine_rank(&points), exact::affine_rank(&points));
+        points[2] = nudged(points[2], u);
+        prop_assert_eq!(gp::affine_rank(&points), exact::affine_rank(&points));
+    }
+
+    #[test]
+    fn dot_3d_random(a in point::<3>(), b in point::<3>(), c in point::<3>(), u in ulps::<3>()) {
+        gp::initialize();
+        prop_assert_eq!(gp::dot_3d(&a, &b, &c), exact::dot_3d(&a, &b, &c));
+        // Rotate b around a by a quarter turn, to get a nearly orthogonal c.
+        let c = nudged([a[0] - (b[1] - a[1]), a[1] + (b[0] - a[0]), a[2]], u);
+        prop_assert_eq!(gp::dot_3d(&a, &b, &c), exact::dot_3d(&a, &b, &c));
+    }
+
+    #[test]
+    fn points_are_colinear_3d_near_collinear(a in point::<3>(), b in point::<3>(), t in -2.0..2.0f64, u in ulps::<3>()) {
+        gp::initialize();
+        let c = nudged([0, 1, 2].map(|k| a[k] + t * (b[k] - a[k])), u);
+        prop_assert_eq!(gp::points_are_colinear_3d(&a, &b, &c), exact::points_are_colinear_3d(&a, &b, &c));
+    }
+
+    #[test]
+    fn in_circle_2d_random(a in point::<2>(), b in point::<2>(), c in point::<2>(), p in point::<2>()) {
+        gp::initialize();
+        prop_assume!(exact::orient_2d(&a, &b, &c) != 0);
+        prop_assert_eq!(gp::in_circle_2d_SOS(&a, &b, &c, &p), exact::in_circle_2d_SOS(&a, &b, &c, &p));
+    }
+
+    #[test]
+    fn in_circle_2d_near_cocircular(points in cocircular(), picks in prop::array::uniform4(0..4usize)) {
+        gp::initialize();
+        let [a, b, c, p] = picks.map(|i| &points[i]);
+        prop_assume!(exact::orient_2d(a, b, c) != 0);
+        prop_assert_eq!(gp::in_circle_2d_SOS(a, b, c, p), exact::in_circle_2d_SOS(a, b, c, p));
+    }
+
+    #[test]
+    fn in_circle_3d_near_cocircular(points in cospherical(), picks in prop::array::uniform4(0..5usize)) {
+        gp::initialize();
+        let [a, b, c, p] = picks.map(|i| &points[i]);
+        prop_assume!(!exact::points_are_colinear_3d(a, b, c));
+        prop_assert_eq!(gp::in_circle_3d_SOS(a, b, c, p), exact::in_circle_3d_SOS(a, b, c, p));
+    }
+
+    #[test]
+    fn in_sphere_3d_random(points in prop::collection::vec(point::<3>(), 5)) {
+        gp::initialize();
+        let t = positive([0, 1, 2, 3].map(|i| &points[i]));
+        prop_assume!(t.is_some());
+        let [a, b, c, d] = t.unwrap();
+        let p = &points[4];
+        prop_assert_eq!(gp::in_sphere_3d_SOS(a, b, c, d, p), exact::in_sphere_3d_SOS(a, b, c, d, p));
+    }
+
+    #[test]
+    fn in_sphere_3d_near_cospherical(points in cospherical(), (picks, p) in tetrahedron_picks()) {
+        gp::initialize();
+        let t = positive(picks.map(|i| &points[i]));
+        prop_assume!(t.is_some());
+        let [a, b, c, d] = t.unwrap();
+        let p = &points[p];
+        prop_assert_eq!(gp::in_sphere_3d_SOS(a, b, c, d, p), exact::in_sphere_3d_SOS(a, b, c, d, p));
+    }
+
+    #[test]
+    fn in_sphere_3d_filtered_near_cospherical(points in cospherical(), (picks, p) in tetrahedron_picks()) {
+        gp::initialize();
+        let t = positive(picks.map(|i| &points[i]));
+        prop_assume!(t.is_some());
+        let [a, b, c, d] = t.unwrap();
+        let p = &points[p];
+        if let Some(sign) = gp::in_sphere_3d_filtered(a, b, c, d, p) {
+            prop_assert_eq!(sign, exact::in_sphere_3d_SOS(a, b, c, d, p));
+        }
+    }
+
+    #[test]
+    fn orient_2dlifted_near_coplanar(points in cocircular(), picks in prop::array::uniform4(0..4usize), w in ulps::<4>()) {
+        gp::initialize();
+        let [a, b, c, p] = picks.map(|i| &points[i]);
+        prop_assume!(exact::orient_2d(a, b, c) != 0);
+        // Lifted onto the paraboloid, which makes the points nearly coplanar.
+        let h = [a, b, c, p].map(|q| q[0] * q[0] + q[1] * q[1]);
+        let [h_a, h_b, h_c, h_p] = std::array::from_fn(|i| nudge(h[i], w[i]));
+        prop_assert_eq!(
+            gp::orient_2dlifted_SOS(a, b, c, p, h_a, h_b, h_c, h_p),
+            exact::orient_2dlifted_SOS(a, b, c, p, h_a, h_b, h_c, h_p)
+        );
+    }
+
+    #[test]
+    fn orient_3dlifted_near_cospherical(points in cospherical(), (picks, p) in tetrahedron_picks(), w in ulps::<5>()) {
+        gp::initialize();
+        let t = positive(picks.map(|i| &points[i]));
+        prop_assume!(t.is_some());
+        let [a, b, c, d] = t.unwrap();
+        let p = &points[p];
+        let h = [a, b, c, d, p].map(|q| q[0] * q[0] + q[1] * q[1] + q[2] * q[2]);
+        let [h_a, h_b, h_c, h_d, h_p] = std::array::from_fn(|i| nudge(h[i], w[i]));
+        prop_assert_eq!(
+            gp::orient_3dlifted_SOS(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p),
+            exact::orient_3dlifted_SOS(a, b, c, d, p, h_a, h_b, h_c, h_d, h_p)
+        );
+    }
+
+    #[test]
+    fn compare_distance_near_equidistant([p, q, r] in equidistant()) {
+        prop_assert_eq!(gp::compare_distance_3d(&p, &q, &r), exact::compare_distance_3d(&p, &q, &r));
+        prop_assert_eq!(gp::compare_distance_nd(&p, &q, &r), exact::compare_distance_3d(&p, &q, &r));
+    }
+
+    #[test]
+    fn compare_power_distance_near_equal([p, q, r] in equidistant(), w in common::coordinate(-8..8, 1e3), u in -3i32..=3) {
+        let (w_q, w_r) = (w, nudge(w, u));
+        prop_assert_eq!(
+            gp::compare_power_distance_3d(&p, &q, w_q, &r, w_r),
+            exact::compare_power_distance_3d(&p, &q, w_q, &r, w_r)
+        );
+        prop_assert_eq!(
+            gp::compare_power_distance_nd(&p, &q, w_q, &r, w_r),
+            exact::compare_power_distance_3d(&p, &q, w_q, &r, w_r)
+        );
+    }
+
+    #[test]
+    fn side_of_bisector_near_equidistant([q, p0, p1] in equidistant()) {
+        gp::initialize();
+        prop_assert_eq!(gp::side1_3d_SOS(&p0, &p1, &q), exact::side1_3d_SOS(&p0, &p1, &q));
+        prop_assert_eq!(gp::side_of_bisector_nd(&p0, &p1, &q), exact::side1_3d_SOS(&p0, &p1, &q));
+        // Extra zero coordinates leave the distances alone, and ties go to the first point of the array, in 4d where
+        // geogram is called and in 5d where it is not.
+        let expected = match exact::compare_distance_3d(&q, &p0, &p1) {
+            std::cmp::Ordering::Greater => -1,
+            _ => 1,
+        };
+        let lifted = [p0, p1, q].map(|p| [p[0], p[1], p[2], 0.0]);
+        prop_assert_eq!(gp::side_of_bisector_nd(&lifted[0], &lifted[1], &lifted[2]), expected);
+        let lifted = [p0, p1, q].map(|p| [p[0], p[1], p[2], 0.0, 0.0]);
+        prop_assert_eq!(gp::side_of_bisector_nd(&lifted[0], &lifted[1], &lifted[2]), expected);
+    }
+
+    #[test]
+    fn side2_3d_near_cospherical([c, p0, p2] in equidistant(), u in ulps::<3>()) {
+        gp::initialize();
+        // p1 is the reflection of p0 at c, so that c is the circumcenter of the edge p0, p1.
+        let p1 = nudged([0, 1, 2].map(|k| 2.0 * c[k] - p0[k]), u);
+        prop_assume!(p0 != p1);
+        prop_assert_eq!(gp::side2_3d_SOS(&p0, &p1, &p2, &p0, &p1), exact::side2_3d_SOS(&p0, &p1, &p2, &p0, &p1));
+    }
+
+    #[test]
+    fn orient_4d_near_flat(
+        points in prop::array::uniform4(point::<4>()),
+        s in prop::array::uniform3(-2.0..2.0f64),
+        u in ulps::<4>(),
+    ) {
+        let [a, b, c, d] = &points;
+        let e = nudged([0, 1, 2, 3].map(|k| a[k] + s[0] * (b[k] - a[k]) + s[1] * (c[k] - a[k]) + s[2] * (d[k] - a[k])), u);
+        prop_assert_eq!(gp::orient_4d(a, b, c, d, &e), exact::orient_4d(a, b, c, d, &e));
+    }
+
+    #[test]
+    fn in_sphere_4d_near_cospherical(points in cospherical_4d()) {
+        let [a, b, c, d, e, p] = [0, 1, 2, 3, 4, 5].map(|i| &points[i]);
+        prop_assume!(exact::orient_4d(a, b, c, d, e) != 0);
+        prop_assert_eq!(gp::in_sphere_4d_SOS(a, b, c, d, e, p), exact::in_sphere_4d_SOS(a, b, c, d, e, p));
+        // The query point shares the address of a vertex, so that the perturbation of the vertices decides.
+        prop_assert_eq!(gp::in_sphere_4d_SOS(a, b, c, d, e, a), exact::in_sphere_4d_SOS(a, b, c, d, e, a));
+    }
+
+    #[test]
+    fn orient_nd_agrees_in_low_dimensions(
+        p2 in prop::array::uniform3(point::<2>()),
+        p3 in prop::array::uniform4(point::<3>()),
+        p4 in prop::array::uniform5(point::<4>()),
+    ) {
+        gp::initialize();
+        prop_assert_eq!(gp::orient_nd(&p2), exact::orient_2d(&p2[0], &p2[1], &p2[2]));
+        prop_assert_eq!(gp::orient_nd(&p3), exact::orient_3d(&p3[0], &p3[1], &p3[2], &p3[3]));
+        prop_assert_eq!(gp::orient_nd(&p4), exact::orient_4d(&p4[0], &p4[1], &p4[2], &p4[3], &p4[4]));
+    }
+
+    #[test]
+    fn orient_nd_near_flat_in_6d(
+        points in prop::collection::vec(point::<6>(), 6),
+        s in prop::collection::vec(-4i32..=4, 5),
+        u in ulps::<6>(),
+    ) {
+        // A combination of the other points with small integer weights, which is exactly on their affine hull.
+        let q: [f64; 6] = std::array::from_fn(|k| {
+            points[0][k] + (1..6).map(|i| f64::from(s[i - 1]) * (points[i][k] - points[0][k])).sum::<f64>()
+        });
+        let mut simplex = points.clone();
+        simplex.push(nudged(q, u));
+        let sign = gp::orient_nd(&simplex);
+        simplex.swap(0, 3);
+        prop_assert_eq!(-sign, gp::orient_nd(&simplex));
+        if points.iter().flatten().all(|x| x.fract() == 0.0) && u == [0; 6] {
+            // The arithmetic above is exact on small integers.
+            prop_assert_eq!(0, sign);
+        }
+    }
+}